@@ -57,6 +57,10 @@ fn get_bytecode(input: &str) -> Result<Vec<u8>> {
     hex::decode(&bytecode_raw[2..]).map_err(|e| format_err!(e.to_string()))
 }
 
+fn get_abi(input: &str) -> Result<ethabi::Contract> {
+    ethabi::Contract::load(input.as_bytes()).map_err(|e| format_err!(e.to_string()))
+}
+
 pub fn generate_intrinsic_addr(prefix_byte: u8, suffix_num: u64) -> Result<H160> {
     let s = format!("{prefix_byte:x}{suffix_num:0>38x}");
     Ok(H160::from_str(&s)?)
@@ -231,6 +235,12 @@ lazy_static::lazy_static! {
             fixed_address: H160(slice_20b!(INTRINSICS_ADDR_PREFIX_BYTE, 0x6))
         }
     };
+
+    pub static ref DST20_ABI: ethabi::Contract =
+        get_abi(solc_artifact_content_str!("dst20_v3", "abi.json")).unwrap();
+
+    pub static ref TRANSFERDOMAIN_ABI: ethabi::Contract =
+        get_abi(solc_artifact_content_str!("transfer_domain_v1", "abi.json")).unwrap();
 }
 
 pub fn get_split_tokens_function() -> ethabi::Function {
@@ -392,6 +402,16 @@ pub fn get_dst20_v3_contract() -> FixedContract {
     DST20_V3_CONTRACT.clone()
 }
 
+/// ABI of the current DST20 implementation.
+pub fn get_dst20_abi() -> ethabi::Contract {
+    DST20_ABI.clone()
+}
+
+/// ABI of the current TransferDomain implementation.
+pub fn get_transfer_domain_abi() -> ethabi::Contract {
+    TRANSFERDOMAIN_ABI.clone()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
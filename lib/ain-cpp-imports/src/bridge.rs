@@ -96,5 +96,24 @@ pub mod ffi {
             old_amount: TokenAmount,
             new_amount: &mut TokenAmount,
         ) -> bool;
+        fn mineBlocks(count: u32) -> u32;
+        fn increaseMockTime(seconds: i64) -> i64;
+        fn rollbackToBlock(height: u32) -> bool;
+        fn replaceEVMBlockHash(old_evm_block_hash: [u8; 32], new_evm_block_hash: [u8; 32]) -> bool;
+        fn getEthRPCRateLimit() -> u32;
+        fn getEthRPCHeavyRateLimit() -> u32;
+        fn getEthRPCAllowedMethods() -> String;
+        fn getEthRPCDeniedMethods() -> String;
+        fn getEthRPCConcurrencyLimits() -> String;
+        fn isEthRPCAuthEnabled() -> bool;
+        fn getEthRPCJwtSecretPath() -> String;
+        fn getEthRPCAuthMethods() -> String;
+        fn isEthRPCAdminEnabled() -> bool;
+        fn isEthSubscriptionDebugEnabled() -> bool;
+        fn getEthRPCReadyMaxBlockAge() -> u64;
+        fn getCurrentTime() -> i64;
+        fn getEthRPCWorkers() -> u32;
+        fn getEthRPCTraceWorkers() -> u32;
+        fn getEthRPCWorkerQueue() -> u32;
     }
 }
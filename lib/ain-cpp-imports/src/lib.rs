@@ -166,6 +166,66 @@ mod ffi {
     ) -> bool {
         unimplemented!("{}", UNIMPL_MSG)
     }
+    pub fn mineBlocks(_count: u32) -> u32 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn increaseMockTime(_seconds: i64) -> i64 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn rollbackToBlock(_height: u32) -> bool {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn replaceEVMBlockHash(
+        _old_evm_block_hash: [u8; 32],
+        _new_evm_block_hash: [u8; 32],
+    ) -> bool {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCRateLimit() -> u32 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCHeavyRateLimit() -> u32 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCAllowedMethods() -> String {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCDeniedMethods() -> String {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCConcurrencyLimits() -> String {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn isEthRPCAuthEnabled() -> bool {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCJwtSecretPath() -> String {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCAuthMethods() -> String {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn isEthRPCAdminEnabled() -> bool {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn isEthSubscriptionDebugEnabled() -> bool {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCReadyMaxBlockAge() -> u64 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getCurrentTime() -> i64 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCWorkers() -> u32 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCTraceWorkers() -> u32 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
+    pub fn getEthRPCWorkerQueue() -> u32 {
+        unimplemented!("{}", UNIMPL_MSG)
+    }
 }
 
 pub use ffi::Attributes;
@@ -384,5 +444,113 @@ pub fn split_tokens_from_evm(
     ffi::migrateTokensFromEVM(mnview_ptr, old_amount, new_amount)
 }
 
+/// Mines blocks with the first `-masternode_operator` of the node. Regtest only.
+/// Returns the number of blocks minted.
+pub fn mine_blocks(count: u32) -> u32 {
+    ffi::mineBlocks(count)
+}
+
+/// Moves the node mock time forward by the given number of seconds. Regtest only.
+/// Returns the new mock time.
+pub fn increase_mock_time(seconds: i64) -> i64 {
+    ffi::increaseMockTime(seconds)
+}
+
+/// Disconnects the DVM blocks above the given height along with their EVM blocks, keeping
+/// them available to be mined again. Regtest only. Returns whether the rollback succeeded.
+pub fn rollback_to_block(height: u32) -> bool {
+    ffi::rollbackToBlock(height)
+}
+
+/// Points the DVM block mapped to an EVM block at the new hash of the EVM block, after its
+/// header was rewritten. Regtest only. Returns whether the mapping was updated.
+pub fn replace_evm_block_hash(old_evm_block_hash: [u8; 32], new_evm_block_hash: [u8; 32]) -> bool {
+    ffi::replaceEVMBlockHash(old_evm_block_hash, new_evm_block_hash)
+}
+
+/// Gets the maximum ETH-RPC requests per second allowed per client IP, set with `-ethrpcratelimit`.
+/// 0 disables the limit.
+pub fn get_eth_rpc_rate_limit() -> u32 {
+    ffi::getEthRPCRateLimit()
+}
+
+/// Gets the maximum expensive ETH-RPC requests (calls, gas estimation, logs and tracing) per second
+/// allowed per client IP, set with `-ethrpcheavyratelimit`. 0 disables the limit.
+pub fn get_eth_rpc_heavy_rate_limit() -> u32 {
+    ffi::getEthRPCHeavyRateLimit()
+}
+
+/// Gets the comma separated ETH-RPC methods the node serves exclusively, set with
+/// `-ethrpcallowmethods`.
+pub fn get_eth_rpc_allowed_methods() -> String {
+    ffi::getEthRPCAllowedMethods()
+}
+
+/// Gets the comma separated ETH-RPC methods the node refuses to serve, set with
+/// `-ethrpcdenymethods`.
+pub fn get_eth_rpc_denied_methods() -> String {
+    ffi::getEthRPCDeniedMethods()
+}
+
+/// Gets the comma separated `method=limit` caps on concurrent ETH-RPC requests, set with
+/// `-ethrpcconcurrencylimits`.
+pub fn get_eth_rpc_concurrency_limits() -> String {
+    ffi::getEthRPCConcurrencyLimits()
+}
+
+/// Whether calls of protected ETH-RPC methods require a JWT, set with `-ethrpcauth`.
+pub fn is_eth_rpc_auth_enabled() -> bool {
+    ffi::isEthRPCAuthEnabled()
+}
+
+/// Gets the absolute path of the hex encoded JWT secret, set with `-ethrpcjwtsecret`.
+pub fn get_eth_rpc_jwt_secret_path() -> String {
+    ffi::getEthRPCJwtSecretPath()
+}
+
+/// Gets the comma separated ETH-RPC methods requiring a JWT, set with `-ethrpcauthmethods`.
+pub fn get_eth_rpc_auth_methods() -> String {
+    ffi::getEthRPCAuthMethods()
+}
+
+/// Whether the ETH-RPC server serves the admin_* methods, set with `-ethrpcadmin`.
+pub fn is_eth_rpc_admin_enabled() -> bool {
+    ffi::isEthRPCAdminEnabled()
+}
+
+/// Whether the websocket server also serves the debug_* ETH RPCs, set with `-ethsubscriptiondebug`.
+pub fn is_eth_subscription_debug_enabled() -> bool {
+    ffi::isEthSubscriptionDebugEnabled()
+}
+
+/// Gets the maximum age in seconds of the latest EVM block for the ETH-RPC server to report ready,
+/// set with `-ethrpcreadymaxblockage`. 0 disables the check.
+pub fn get_eth_rpc_ready_max_block_age() -> u64 {
+    ffi::getEthRPCReadyMaxBlockAge()
+}
+
+/// Gets the node's current unix time in seconds, honouring mock time.
+pub fn get_current_time() -> i64 {
+    ffi::getCurrentTime()
+}
+
+/// Gets the number of threads executing ETH-RPC calls, gas estimation and log queries, set with
+/// `-ethrpcworkers`. 0 uses the number of cores.
+pub fn get_eth_rpc_workers() -> u32 {
+    ffi::getEthRPCWorkers()
+}
+
+/// Gets the number of threads executing ETH-RPC tracing, set with `-ethrpctraceworkers`.
+/// 0 uses half the number of cores.
+pub fn get_eth_rpc_trace_workers() -> u32 {
+    ffi::getEthRPCTraceWorkers()
+}
+
+/// Gets the maximum ETH-RPC requests waiting for a worker thread per pool, set with
+/// `-ethrpcworkerqueue`.
+pub fn get_eth_rpc_worker_queue() -> u32 {
+    ffi::getEthRPCWorkerQueue()
+}
+
 #[cfg(test)]
 mod tests {}
@@ -99,6 +99,12 @@ impl Rocks {
         self.0.flush()?;
         Ok(())
     }
+
+    /// Returns the integer value of a RocksDB property of column family `cf`,
+    /// e.g. `rocksdb.estimate-num-keys`.
+    pub fn property_int_value_cf(&self, cf: &ColumnFamily, name: &str) -> Result<Option<u64>> {
+        Ok(self.0.property_int_value_cf(cf, name)?)
+    }
 }
 
 //
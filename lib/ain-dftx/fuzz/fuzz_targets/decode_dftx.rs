@@ -0,0 +1,17 @@
+//! Decodes arbitrary custom tx payloads. Decoding may fail but must not panic, and any message
+//! decoded must re-encode to bytes that decode to the same message.
+
+#![no_main]
+
+use ain_dftx::{serialize, DfTx};
+use bitcoin::consensus::deserialize_partial;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((dftx, _)) = deserialize_partial::<DfTx>(data) {
+        let bytes = serialize(&dftx);
+        let (decoded, consumed) = deserialize_partial::<DfTx>(&bytes).unwrap();
+        assert_eq!(decoded, dftx);
+        assert_eq!(consumed, bytes.len());
+    }
+});
@@ -0,0 +1,15 @@
+//! Extracts custom txs from arbitrary transactions, covering the OP_RETURN script parsing ahead
+//! of the payload decoding.
+
+#![no_main]
+
+use ain_dftx::{deserialize, DfTx, Transaction};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(tx) = deserialize::<Transaction>(data) {
+        for metadata_validation in [false, true] {
+            let _ = DfTx::from_transaction(&tx, metadata_validation);
+        }
+    }
+});
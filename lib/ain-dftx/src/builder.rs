@@ -0,0 +1,569 @@
+//! Builders for unsigned custom tx templates.
+//!
+//! Message builders validate their amounts and return a [`CustomTxBuilder`], which places the
+//! `OP_RETURN DfTx` output first, adds the caller's inputs and change outputs and produces an
+//! unsigned [`Transaction`] ready to be signed. The inputs have to spend from every script the
+//! message needs authorization from.
+//!
+//! ```ignore
+//! let tx = AccountToAccountBuilder::new(from.clone())
+//!     .to(to, 0, COIN)
+//!     .build()?
+//!     .input(outpoint, from)
+//!     .output(change, change_amount)
+//!     .build()?;
+//! ```
+
+use std::fmt;
+
+use bitcoin::{
+    absolute::LockTime, blockdata::opcodes::all::OP_RETURN, consensus::serialize,
+    script::PushBytesBuf, transaction::Version, Amount, OutPoint, ScriptBuf, Sequence, Transaction,
+    TxIn, TxOut, Txid, Witness,
+};
+
+use crate::{
+    common::{CompactVec, VarInt},
+    types::{
+        account::{AccountToAccount, TransferDomain, TransferDomainItem, TransferDomainPair},
+        balance::{ScriptBalances, TokenBalanceUInt32, TokenBalanceVarInt},
+        governance::Vote,
+        loans::TakeLoan,
+        pool::{CompositeSwap, MaxPrice, PoolId, PoolSwap},
+        vault::DepositToVault,
+    },
+    DfTx, COIN,
+};
+
+/// Maximum amount of a single token, as defid's `MAX_MONEY`.
+pub const MAX_MONEY: i64 = 1_200_000_000 * COIN;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// Amounts must be positive and no more than [`MAX_MONEY`].
+    InvalidAmount(i64),
+    /// The message has nothing to transfer.
+    EmptyTransfer,
+    /// Custom txs need at least one input to authorize them and pay fees.
+    NoInputs,
+    /// No input spends from a script the message needs authorization from.
+    MissingAuth(ScriptBuf),
+    /// The serialized message, of the given size, does not fit in a script push.
+    PayloadTooLarge(usize),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidAmount(amount) => write!(
+                f,
+                "invalid amount {amount}, must be between 1 and {MAX_MONEY}"
+            ),
+            BuildError::EmptyTransfer => write!(f, "nothing to transfer"),
+            BuildError::NoInputs => write!(f, "custom tx requires at least one input"),
+            BuildError::MissingAuth(script) => {
+                write!(f, "no input spends from auth script {script}")
+            }
+            BuildError::PayloadTooLarge(size) => {
+                write!(f, "custom tx payload of {size} bytes is too large")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+fn check_amount(amount: i64) -> Result<i64, BuildError> {
+    if amount > 0 && amount <= MAX_MONEY {
+        Ok(amount)
+    } else {
+        Err(BuildError::InvalidAmount(amount))
+    }
+}
+
+fn token_balance(token: u32, amount: i64) -> Result<TokenBalanceVarInt, BuildError> {
+    Ok(TokenBalanceVarInt {
+        token: bitcoin::VarInt(u64::from(token)),
+        amount: check_amount(amount)?,
+    })
+}
+
+/// Appends `amount` of `token` to `script`'s balances, keeping scripts in insertion order.
+fn add_balance(
+    accounts: &mut Vec<(ScriptBuf, Vec<TokenBalanceUInt32>)>,
+    script: ScriptBuf,
+    token: u32,
+    amount: i64,
+) {
+    let balance = TokenBalanceUInt32 { token, amount };
+    match accounts.iter_mut().find(|(s, _)| *s == script) {
+        Some((_, balances)) => balances.push(balance),
+        None => accounts.push((script, vec![balance])),
+    }
+}
+
+/// Returns the witness v16 script defid uses for EVM addresses.
+pub fn evm_script(address: [u8; 20]) -> ScriptBuf {
+    let mut script = vec![0x60, 0x14];
+    script.extend(address);
+    ScriptBuf::from_bytes(script)
+}
+
+/// Builds the unsigned tx carrying a custom tx message.
+#[derive(Debug)]
+pub struct CustomTxBuilder {
+    dftx: DfTx,
+    auth_scripts: Vec<ScriptBuf>,
+    inputs: Vec<(TxIn, ScriptBuf)>,
+    outputs: Vec<TxOut>,
+}
+
+impl CustomTxBuilder {
+    #[must_use]
+    pub fn new(dftx: DfTx) -> Self {
+        Self {
+            dftx,
+            auth_scripts: Vec::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Requires an input spending from `script` to authorize the message.
+    #[must_use]
+    pub fn auth(mut self, script: ScriptBuf) -> Self {
+        if !self.auth_scripts.contains(&script) {
+            self.auth_scripts.push(script);
+        }
+        self
+    }
+
+    /// Scripts the tx inputs need to spend from for defid to accept the message.
+    pub fn auth_scripts(&self) -> &[ScriptBuf] {
+        &self.auth_scripts
+    }
+
+    /// Adds an input spending `previous_output`, which is locked by `script_pubkey`.
+    #[must_use]
+    pub fn input(mut self, previous_output: OutPoint, script_pubkey: ScriptBuf) -> Self {
+        let input = TxIn {
+            previous_output,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::MAX,
+            witness: Witness::new(),
+        };
+        self.inputs.push((input, script_pubkey));
+        self
+    }
+
+    /// Adds an output after the custom tx output, e.g. for change.
+    #[must_use]
+    pub fn output(mut self, script_pubkey: ScriptBuf, value: Amount) -> Self {
+        self.outputs.push(TxOut {
+            value,
+            script_pubkey,
+        });
+        self
+    }
+
+    pub fn build(self) -> Result<Transaction, BuildError> {
+        if self.inputs.is_empty() {
+            return Err(BuildError::NoInputs);
+        }
+        if let Some(script) = self
+            .auth_scripts
+            .into_iter()
+            .find(|script| !self.inputs.iter().any(|(_, spent)| spent == script))
+        {
+            return Err(BuildError::MissingAuth(script));
+        }
+
+        let payload = serialize(&self.dftx);
+        let size = payload.len();
+        let payload =
+            PushBytesBuf::try_from(payload).map_err(|_| BuildError::PayloadTooLarge(size))?;
+        let script_pubkey = ScriptBuf::builder()
+            .push_opcode(OP_RETURN)
+            .push_slice(payload)
+            .into_script();
+
+        let mut output = vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey,
+        }];
+        output.extend(self.outputs);
+
+        Ok(Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: self.inputs.into_iter().map(|(input, _)| input).collect(),
+            output,
+        })
+    }
+}
+
+/// Sends tokens from an account to one or more accounts.
+#[derive(Debug)]
+pub struct AccountToAccountBuilder {
+    from: ScriptBuf,
+    to: Vec<(ScriptBuf, Vec<TokenBalanceUInt32>)>,
+}
+
+impl AccountToAccountBuilder {
+    #[must_use]
+    pub fn new(from: ScriptBuf) -> Self {
+        Self {
+            from,
+            to: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn to(mut self, script: ScriptBuf, token: u32, amount: i64) -> Self {
+        add_balance(&mut self.to, script, token, amount);
+        self
+    }
+
+    pub fn build(self) -> Result<CustomTxBuilder, BuildError> {
+        if self.to.is_empty() {
+            return Err(BuildError::EmptyTransfer);
+        }
+        let to = self
+            .to
+            .into_iter()
+            .map(|(script, balances)| {
+                for balance in &balances {
+                    check_amount(balance.amount)?;
+                }
+                Ok(ScriptBalances {
+                    script,
+                    balances: CompactVec::from(balances),
+                })
+            })
+            .collect::<Result<Vec<_>, BuildError>>()?;
+
+        let dftx = DfTx::AccountToAccount(AccountToAccount {
+            from: self.from.clone(),
+            to: CompactVec::from(to),
+        });
+        Ok(CustomTxBuilder::new(dftx).auth(self.from))
+    }
+}
+
+/// Swaps through a pool pair, or through several pools as a composite swap.
+#[derive(Debug)]
+pub struct PoolSwapBuilder {
+    from_script: ScriptBuf,
+    from_token: u32,
+    from_amount: i64,
+    to_script: ScriptBuf,
+    to_token: u32,
+    max_price: Option<MaxPrice>,
+    pools: Vec<u32>,
+}
+
+impl PoolSwapBuilder {
+    #[must_use]
+    pub fn new(
+        from_script: ScriptBuf,
+        from_token: u32,
+        from_amount: i64,
+        to_script: ScriptBuf,
+        to_token: u32,
+    ) -> Self {
+        Self {
+            from_script,
+            from_token,
+            from_amount,
+            to_script,
+            to_token,
+            max_price: None,
+            pools: Vec::new(),
+        }
+    }
+
+    /// Maximum price of the swap in [`COIN`] units, unbounded by default.
+    #[must_use]
+    pub fn max_price(mut self, max_price: i64) -> Self {
+        self.max_price = Some(MaxPrice {
+            integer: max_price / COIN,
+            fraction: max_price % COIN,
+        });
+        self
+    }
+
+    /// Swaps through the given pool ids, building a composite swap.
+    #[must_use]
+    pub fn pools(mut self, pools: Vec<u32>) -> Self {
+        self.pools = pools;
+        self
+    }
+
+    pub fn build(self) -> Result<CustomTxBuilder, BuildError> {
+        let max_price = match self.max_price {
+            Some(max_price) => {
+                check_amount(max_price.integer * COIN + max_price.fraction)?;
+                max_price
+            }
+            // defid's PoolPrice::getMaxValid
+            None => MaxPrice {
+                integer: MAX_MONEY / COIN,
+                fraction: MAX_MONEY % COIN,
+            },
+        };
+        let pool_swap = PoolSwap {
+            from_script: self.from_script.clone(),
+            from_token_id: VarInt(u64::from(self.from_token)),
+            from_amount: check_amount(self.from_amount)?,
+            to_script: self.to_script,
+            to_token_id: VarInt(u64::from(self.to_token)),
+            max_price,
+        };
+
+        let dftx = if self.pools.is_empty() {
+            DfTx::PoolSwap(pool_swap)
+        } else {
+            let pools = self
+                .pools
+                .into_iter()
+                .map(|id| PoolId {
+                    id: VarInt(u64::from(id)),
+                })
+                .collect::<Vec<_>>();
+            DfTx::CompositeSwap(CompositeSwap {
+                pool_swap,
+                pools: CompactVec::from(pools),
+            })
+        };
+        Ok(CustomTxBuilder::new(dftx).auth(self.from_script))
+    }
+}
+
+/// VM domains of a transfer, as defid's `VMDomain`.
+const DVM_DOMAIN: u8 = 0x02;
+const EVM_DOMAIN: u8 = 0x03;
+
+/// Transfers tokens between the DVM and EVM domains.
+///
+/// The EVM side of each transfer carries the signed EVM tx calling the TransferDomain contract.
+#[derive(Debug, Default)]
+pub struct TransferDomainBuilder {
+    transfers: Vec<(ScriptBuf, TransferDomainPair)>,
+}
+
+impl TransferDomainBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn transfer(
+        mut self,
+        (src_address, src_domain, src_data): (ScriptBuf, u8, Vec<u8>),
+        (dst_address, dst_domain, dst_data): (ScriptBuf, u8, Vec<u8>),
+        token: u32,
+        amount: i64,
+    ) -> Self {
+        let auth = src_address.clone();
+        let amount = || TokenBalanceVarInt {
+            token: bitcoin::VarInt(u64::from(token)),
+            amount,
+        };
+        self.transfers.push((
+            auth,
+            TransferDomainPair {
+                src: TransferDomainItem {
+                    address: src_address,
+                    amount: amount(),
+                    domain: src_domain,
+                    data: src_data,
+                },
+                dst: TransferDomainItem {
+                    address: dst_address,
+                    amount: amount(),
+                    domain: dst_domain,
+                    data: dst_data,
+                },
+            },
+        ));
+        self
+    }
+
+    #[must_use]
+    pub fn dvm_to_evm(
+        self,
+        from: ScriptBuf,
+        to: ScriptBuf,
+        token: u32,
+        amount: i64,
+        evm_tx: Vec<u8>,
+    ) -> Self {
+        self.transfer(
+            (from, DVM_DOMAIN, Vec::new()),
+            (to, EVM_DOMAIN, evm_tx),
+            token,
+            amount,
+        )
+    }
+
+    #[must_use]
+    pub fn evm_to_dvm(
+        self,
+        from: ScriptBuf,
+        to: ScriptBuf,
+        token: u32,
+        amount: i64,
+        evm_tx: Vec<u8>,
+    ) -> Self {
+        self.transfer(
+            (from, EVM_DOMAIN, evm_tx),
+            (to, DVM_DOMAIN, Vec::new()),
+            token,
+            amount,
+        )
+    }
+
+    pub fn build(self) -> Result<CustomTxBuilder, BuildError> {
+        if self.transfers.is_empty() {
+            return Err(BuildError::EmptyTransfer);
+        }
+
+        let mut auth_scripts = Vec::new();
+        let mut items = Vec::with_capacity(self.transfers.len());
+        for (auth, pair) in self.transfers {
+            check_amount(pair.src.amount.amount)?;
+            // EVM sources are authorized by the signed EVM tx
+            if pair.src.domain == DVM_DOMAIN {
+                auth_scripts.push(auth);
+            }
+            items.push(pair);
+        }
+
+        let dftx = DfTx::TransferDomain(TransferDomain {
+            items: CompactVec::from(items),
+        });
+        Ok(auth_scripts
+            .into_iter()
+            .fold(CustomTxBuilder::new(dftx), CustomTxBuilder::auth))
+    }
+}
+
+/// Deposits collateral from an account into a vault.
+#[derive(Debug)]
+pub struct DepositToVaultBuilder {
+    vault_id: Txid,
+    from: ScriptBuf,
+    token: u32,
+    amount: i64,
+}
+
+impl DepositToVaultBuilder {
+    #[must_use]
+    pub fn new(vault_id: Txid, from: ScriptBuf, token: u32, amount: i64) -> Self {
+        Self {
+            vault_id,
+            from,
+            token,
+            amount,
+        }
+    }
+
+    pub fn build(self) -> Result<CustomTxBuilder, BuildError> {
+        let dftx = DfTx::DepositToVault(DepositToVault {
+            vault_id: self.vault_id,
+            from: self.from.clone(),
+            token_amount: token_balance(self.token, self.amount)?,
+        });
+        Ok(CustomTxBuilder::new(dftx).auth(self.from))
+    }
+}
+
+/// Takes loan tokens against a vault.
+///
+/// The tx has to be authorized by the vault owner, which the message does not carry: pass it
+/// with [`CustomTxBuilder::auth`].
+#[derive(Debug)]
+pub struct TakeLoanBuilder {
+    vault_id: Txid,
+    to: ScriptBuf,
+    amounts: Vec<TokenBalanceUInt32>,
+}
+
+impl TakeLoanBuilder {
+    #[must_use]
+    pub fn new(vault_id: Txid) -> Self {
+        Self {
+            vault_id,
+            to: ScriptBuf::new(),
+            amounts: Vec::new(),
+        }
+    }
+
+    /// Sends the loan to `to` instead of the vault owner.
+    #[must_use]
+    pub fn to(mut self, to: ScriptBuf) -> Self {
+        self.to = to;
+        self
+    }
+
+    #[must_use]
+    pub fn amount(mut self, token: u32, amount: i64) -> Self {
+        self.amounts.push(TokenBalanceUInt32 { token, amount });
+        self
+    }
+
+    pub fn build(self) -> Result<CustomTxBuilder, BuildError> {
+        if self.amounts.is_empty() {
+            return Err(BuildError::EmptyTransfer);
+        }
+        for balance in &self.amounts {
+            check_amount(balance.amount)?;
+        }
+
+        let dftx = DfTx::TakeLoan(TakeLoan {
+            vault_id: self.vault_id,
+            to: self.to,
+            token_amounts: CompactVec::from(self.amounts),
+        });
+        Ok(CustomTxBuilder::new(dftx))
+    }
+}
+
+/// Vote decisions, as defid's `CProposalVoteType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum VoteDecision {
+    Yes = 0x01,
+    No = 0x02,
+    Neutral = 0x03,
+}
+
+/// Votes on a proposal as a masternode.
+///
+/// The tx has to be authorized by the masternode owner: pass it with [`CustomTxBuilder::auth`].
+#[derive(Debug)]
+pub struct VoteBuilder {
+    proposal_id: Txid,
+    masternode_id: Txid,
+    decision: VoteDecision,
+}
+
+impl VoteBuilder {
+    #[must_use]
+    pub fn new(proposal_id: Txid, masternode_id: Txid, decision: VoteDecision) -> Self {
+        Self {
+            proposal_id,
+            masternode_id,
+            decision,
+        }
+    }
+
+    pub fn build(self) -> Result<CustomTxBuilder, BuildError> {
+        let dftx = DfTx::Vote(Vote {
+            proposal_id: self.proposal_id,
+            masternode_id: self.masternode_id,
+            vote_decision: self.decision as u8,
+        });
+        Ok(CustomTxBuilder::new(dftx))
+    }
+}
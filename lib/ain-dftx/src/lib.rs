@@ -1,11 +1,18 @@
+// Lets `ConsensusEncoding` derives refer to this crate by name, in here as in dependents
+extern crate self as ain_dftx;
+
+pub mod builder;
 pub mod custom_tx;
+#[cfg(feature = "serde")]
+mod serde_utils;
+mod transaction;
 pub mod types;
 
 pub use bitcoin::{
     consensus::{deserialize, serialize},
-    Block, Transaction, TxIn, TxOut,
+    Block, Script, Transaction, TxIn, TxOut,
 };
 
-pub use crate::types::*;
+pub use crate::{transaction::decode_block, types::*};
 
 pub const COIN: i64 = 100_000_000;
@@ -0,0 +1,277 @@
+//! Helpers shaping the JSON representation of DfTx types after defid's `decodecustomtx`:
+//! scripts and raw bytes as hex, amounts as decimal strings scaled by [`COIN`] and token
+//! amounts as `<amount>@<token id>`.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    common::{CompactVec, Maybe, RawBytes, VarInt},
+    COIN,
+};
+
+/// Formats an amount the same way as defid's `GetDecimalString`.
+pub fn format_amount(amount: i128) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    let abs = amount.unsigned_abs();
+    let coin = COIN as u128;
+    format!("{sign}{}.{:08}", abs / coin, abs % coin)
+}
+
+/// Parses a decimal amount string with up to 8 fractional digits.
+pub fn parse_amount(s: &str) -> Result<i128, String> {
+    let invalid = || format!("invalid amount {s}");
+    let (negative, abs) = match s.strip_prefix('-') {
+        Some(abs) => (true, abs),
+        None => (false, s),
+    };
+    let (integer, fraction) = abs.split_once('.').unwrap_or((abs, ""));
+    if integer.is_empty()
+        || fraction.len() > 8
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let integer = integer.parse::<i128>().map_err(|_| invalid())?;
+    let fraction = format!("{fraction:0<8}")
+        .parse::<i128>()
+        .map_err(|_| invalid())?;
+    let amount = integer
+        .checked_mul(i128::from(COIN))
+        .and_then(|v| v.checked_add(fraction))
+        .ok_or_else(invalid)?;
+    Ok(if negative { -amount } else { amount })
+}
+
+/// Token amounts serialized as `<amount>@<token id>`, as in defid's `CTokenAmount::ToString`.
+pub fn format_token_amount(token: u64, amount: i64) -> String {
+    format!("{}@{token}", format_amount(i128::from(amount)))
+}
+
+pub fn parse_token_amount(s: &str) -> Result<(u64, i64), String> {
+    let (amount, token) = s
+        .split_once('@')
+        .ok_or_else(|| format!("invalid token amount {s}"))?;
+    let token = token
+        .parse::<u64>()
+        .map_err(|_| format!("invalid token id {token}"))?;
+    let amount = i64::try_from(parse_amount(amount)?)
+        .map_err(|_| format!("amount out of range {amount}"))?;
+    Ok((token, amount))
+}
+
+/// `#[serde(with = "amount")]` for `i64`/`u64` amounts denominated in [`COIN`].
+pub mod amount {
+    use super::*;
+
+    pub fn serialize<S, T>(amount: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Copy + Into<i128>,
+    {
+        serializer.serialize_str(&format_amount((*amount).into()))
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<i128>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let amount = parse_amount(&s).map_err(de::Error::custom)?;
+        T::try_from(amount).map_err(|_| de::Error::custom(format!("amount out of range {s}")))
+    }
+}
+
+/// `#[serde(with = "hex_bytes")]` for raw byte vectors.
+pub mod hex_bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s).map_err(de::Error::custom)
+    }
+}
+
+/// `#[serde(with = "compact_hex")]` for length prefixed byte vectors.
+pub mod compact_hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        bytes: &CompactVec<u8>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        hex_bytes::serialize(bytes.as_ref(), serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CompactVec<u8>, D::Error> {
+        hex_bytes::deserialize(deserializer).map(CompactVec::from)
+    }
+}
+
+/// `#[serde(with = "maybe_compact_hex")]` for optional length prefixed byte vectors.
+pub mod maybe_compact_hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        bytes: &Maybe<CompactVec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match &bytes.0 {
+            Some(bytes) => compact_hex::serialize(bytes, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Maybe<CompactVec<u8>>, D::Error> {
+        let s = Option::<String>::deserialize(deserializer)?;
+        s.map(|s| hex::decode(s).map(CompactVec::from))
+            .transpose()
+            .map(Maybe)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// `#[serde(with = "var_int")]` for rust-bitcoin's `VarInt`.
+pub mod var_int {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        value: &bitcoin::VarInt,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<bitcoin::VarInt, D::Error> {
+        u64::deserialize(deserializer).map(bitcoin::VarInt)
+    }
+}
+
+impl<T: Serialize> Serialize for CompactVec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for CompactVec<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(CompactVec::from)
+    }
+}
+
+impl<T: Serialize> Serialize for Maybe<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Maybe<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::deserialize(deserializer).map(Maybe)
+    }
+}
+
+impl Serialize for RawBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        hex_bytes::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        hex_bytes::deserialize(deserializer).map(RawBytes)
+    }
+}
+
+impl Serialize for VarInt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for VarInt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(VarInt)
+    }
+}
+
+/// `#[serde(with = "accounts")]` for `CAccounts`, serialized as an object of hex script to
+/// comma separated token amounts like defid's `CBalances::ToString`.
+pub mod accounts {
+    use std::fmt;
+
+    use bitcoin::ScriptBuf;
+    use serde::ser::SerializeMap;
+
+    use super::*;
+    use crate::balance::{ScriptBalances, TokenBalanceUInt32};
+
+    pub fn serialize<S: Serializer>(
+        accounts: &CompactVec<ScriptBalances>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(accounts.as_ref().len()))?;
+        for account in accounts.as_ref() {
+            let balances = account
+                .balances
+                .as_ref()
+                .iter()
+                .map(|b| format_token_amount(u64::from(b.token), b.amount))
+                .collect::<Vec<_>>();
+            map.serialize_entry(&account.script.to_hex_string(), &balances.join(","))?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CompactVec<ScriptBalances>, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = CompactVec<ScriptBalances>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of hex scripts to token amounts")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut accounts = Vec::new();
+                while let Some((script, balances)) = map.next_entry::<String, String>()? {
+                    let script = ScriptBuf::from_hex(&script).map_err(de::Error::custom)?;
+                    let balances = balances
+                        .split(',')
+                        .filter(|s| !s.is_empty())
+                        .map(|s| {
+                            let (token, amount) = parse_token_amount(s)?;
+                            u32::try_from(token)
+                                .map(|token| TokenBalanceUInt32 { token, amount })
+                                .map_err(|_| format!("token id out of range {token}"))
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(de::Error::custom)?;
+                    accounts.push(ScriptBalances {
+                        script,
+                        balances: balances.into(),
+                    });
+                }
+                Ok(accounts.into())
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
@@ -0,0 +1,92 @@
+use bitcoin::{
+    blockdata::{opcodes::all::OP_RETURN, script::Instruction},
+    consensus::{deserialize_partial, encode::Error},
+    Block, Script, Transaction, Txid,
+};
+
+use crate::types::{DfTx, DFTX_MARKER};
+
+/// Returns the `DfTx` payload of an `OP_RETURN <push>` script, along with whether more opcodes
+/// follow the push. The push can use any of the direct push or `OP_PUSHDATA1/2/4` encodings.
+/// Mirrors defid's `ParseScriptByMarker`.
+fn parse_script(script: &Script) -> Option<(&[u8], bool)> {
+    let mut instructions = script.instructions();
+    match instructions.next() {
+        Some(Ok(Instruction::Op(OP_RETURN))) => {}
+        _ => return None,
+    }
+    let metadata = match instructions.next() {
+        Some(Ok(Instruction::PushBytes(bytes))) => bytes.as_bytes(),
+        _ => return None,
+    };
+    if metadata.len() <= DFTX_MARKER.len() || !metadata.starts_with(&DFTX_MARKER) {
+        return None;
+    }
+    let has_additional_opcodes = matches!(instructions.next(), Some(Ok(_)));
+    Some((metadata, has_additional_opcodes))
+}
+
+impl DfTx {
+    /// Decodes the custom tx carried by the first output of `tx`, following defid's
+    /// `GuessCustomTxType`. Returns `None` when `tx` is not a custom tx.
+    ///
+    /// The tx is `DfTx::Reject` when the type is unknown. With `metadata_validation`, enabled by
+    /// defid from the FortCanning height, it is also `DfTx::Reject` when any other output carries
+    /// the `DfTx` marker or when more opcodes follow the payload.
+    ///
+    /// Like defid's `CustomMetadataParse`, bytes left over after the message fail the decoding.
+    pub fn from_transaction(
+        tx: &Transaction,
+        metadata_validation: bool,
+    ) -> Result<Option<Self>, Error> {
+        let Some((first, rest)) = tx.output.split_first() else {
+            return Ok(None);
+        };
+        if metadata_validation
+            && rest
+                .iter()
+                .any(|output| parse_script(&output.script_pubkey).is_some())
+        {
+            return Ok(Some(DfTx::Reject));
+        }
+        Self::from_script(&first.script_pubkey, metadata_validation)
+    }
+
+    /// Decodes the custom tx carried by an `OP_RETURN DfTx` output script, as
+    /// [`DfTx::from_transaction`] does for the first output. Returns `None` when the script does
+    /// not carry a custom tx.
+    pub fn from_script(script: &Script, metadata_validation: bool) -> Result<Option<Self>, Error> {
+        let Some((metadata, has_additional_opcodes)) = parse_script(script) else {
+            return Ok(None);
+        };
+        if metadata_validation && has_additional_opcodes {
+            return Ok(Some(DfTx::Reject));
+        }
+
+        let dftx = match deserialize_partial::<DfTx>(metadata)? {
+            (DfTx::None, _) => DfTx::Reject,
+            (dftx, consumed) if consumed == metadata.len() => dftx,
+            _ => return Err(Error::ParseFailed("excess bytes after custom tx")),
+        };
+        Ok(Some(dftx))
+    }
+}
+
+/// Iterates over the custom txs of `block`, skipping the coinbase.
+/// Txs whose payload fails to deserialize are skipped; use [`DfTx::from_transaction`] to
+/// surface the error.
+pub fn decode_block(
+    block: &Block,
+    metadata_validation: bool,
+) -> impl Iterator<Item = (Txid, DfTx)> + '_ {
+    block
+        .txdata
+        .iter()
+        .filter(|tx| !tx.is_coinbase())
+        .filter_map(
+            move |tx| match DfTx::from_transaction(tx, metadata_validation) {
+                Ok(Some(dftx)) => Some((tx.txid(), dftx)),
+                _ => None,
+            },
+        )
+}
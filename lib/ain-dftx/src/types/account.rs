@@ -1,5 +1,5 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::{io, ScriptBuf, VarInt};
+use bitcoin::{ScriptBuf, VarInt};
 
 use super::{
     balance::{ScriptBalances, TokenBalanceUInt32, TokenBalanceVarInt},
@@ -7,52 +7,111 @@ use super::{
 };
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UtxosToAccount {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::accounts"))]
     pub to: CompactVec<ScriptBalances>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct AccountToUtxos {
     pub from: ScriptBuf,
     pub balances: CompactVec<TokenBalanceUInt32>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::var_int"))]
     pub minting_outputs_start: VarInt,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct AccountToAccount {
     pub from: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::accounts"))]
     pub to: CompactVec<ScriptBalances>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct AnyAccountsToAccounts {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::accounts"))]
     pub from: CompactVec<ScriptBalances>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::accounts"))]
     pub to: CompactVec<ScriptBalances>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TransferDomainItem {
     pub address: ScriptBuf,
     pub amount: TokenBalanceVarInt,
     pub domain: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub data: Vec<u8>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TransferDomainPair {
     pub src: TransferDomainItem,
     pub dst: TransferDomainItem,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TransferDomain {
+    #[cfg_attr(feature = "serde", serde(rename = "transfers"))]
     pub items: CompactVec<TransferDomainPair>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SetFutureSwap {
     pub owner: ScriptBuf,
     pub source: TokenBalanceVarInt,
     pub destination: u32,
     pub withdraw: bool,
 }
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SmartContract {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::accounts"))]
+    pub accounts: CompactVec<ScriptBalances>,
+}
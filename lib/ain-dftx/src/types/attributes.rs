@@ -0,0 +1,857 @@
+//! The `ATTRIBUTES` governance variable: a map of `v0/<type>/<id>/<key>` keys to typed values,
+//! following defid's `CAttributeType` and `CAttributeValue` variants.
+
+use std::{fmt, str::FromStr};
+
+use ain_macros::ConsensusEncoding;
+use bitcoin::{
+    consensus::{encode::Error, Decodable, Encodable},
+    hashes::sha256d,
+    io, ScriptBuf,
+};
+
+use super::{
+    balance::TokenBalanceUInt32,
+    common::{CompactVec, VarInt},
+    price::CurrencyPair,
+};
+
+// AttributeTypes
+pub mod attribute_type {
+    pub const LIVE: u8 = b'l';
+    pub const ORACLES: u8 = b'o';
+    pub const PARAM: u8 = b'a';
+    pub const TOKEN: u8 = b't';
+    pub const POOLPAIRS: u8 = b'p';
+    pub const LOCKS: u8 = b'L';
+    pub const GOVERNANCE: u8 = b'g';
+    pub const TRANSFER: u8 = b'b';
+    pub const EVM: u8 = b'e';
+    pub const VAULTS: u8 = b'v';
+    pub const RULES: u8 = b'r';
+}
+
+// ParamIDs, also used by the `live` and `locks` types
+pub mod param_id {
+    pub const DFIP2201: u32 = b'a' as u32;
+    pub const DFIP2203: u32 = b'b' as u32;
+    pub const TOKEN_ID: u32 = b'c' as u32;
+    pub const ECONOMY: u32 = b'e' as u32;
+    pub const DFIP2206A: u32 = b'f' as u32;
+    pub const DFIP2206F: u32 = b'g' as u32;
+    pub const FEATURE: u32 = b'h' as u32;
+    pub const AUCTION: u32 = b'i' as u32;
+    pub const FOUNDATION: u32 = b'j' as u32;
+    pub const DFIP2211F: u32 = b'k' as u32;
+    pub const GOVERNANCE: u32 = b'l' as u32;
+    pub const DTOKEN_RESTART: u32 = b'm' as u32;
+}
+
+pub mod oracle_id {
+    pub const SPLITS: u32 = b'a' as u32;
+}
+
+pub mod evm_id {
+    pub const BLOCK: u32 = b'a' as u32;
+}
+
+pub mod governance_id {
+    pub const GLOBAL: u32 = b'a' as u32;
+    pub const PROPOSALS: u32 = b'b' as u32;
+}
+
+pub mod transfer_id {
+    pub const DVM_TO_EVM: u32 = b'a' as u32;
+    pub const EVM_TO_DVM: u32 = b'b' as u32;
+}
+
+pub mod vault_id {
+    pub const DUSD_VAULT: u32 = b'a' as u32;
+    pub const PARAMETERS: u32 = b'b' as u32;
+}
+
+pub mod rules_id {
+    pub const TX_RULES: u32 = b'a' as u32;
+}
+
+pub mod evm_key {
+    pub const FINALIZED: u32 = b'a' as u32;
+    pub const GAS_LIMIT: u32 = b'b' as u32;
+    pub const GAS_TARGET_FACTOR: u32 = b'c' as u32;
+    pub const RBF_INCREMENT_MIN_PCT: u32 = b'd' as u32;
+}
+
+pub mod economy_key {
+    pub const PAYBACK_DFI_TOKENS: u32 = b'a' as u32;
+    pub const PAYBACK_TOKENS: u32 = b'b' as u32;
+    pub const DFIP2203_CURRENT: u32 = b'c' as u32;
+    pub const DFIP2203_BURNED: u32 = b'd' as u32;
+    pub const DFIP2203_MINTED: u32 = b'e' as u32;
+    pub const DFIP2206F_CURRENT: u32 = b'f' as u32;
+    pub const DFIP2206F_BURNED: u32 = b'g' as u32;
+    pub const DFIP2206F_MINTED: u32 = b'h' as u32;
+    pub const DEX_TOKENS: u32 = b'i' as u32;
+    pub const NEGATIVE_INT: u32 = b'j' as u32;
+    pub const NEGATIVE_INT_CURRENT: u32 = b'k' as u32;
+    pub const BATCH_ROUNDING_EXCESS: u32 = b'n' as u32;
+    pub const CONSOLIDATED_INTEREST: u32 = b'o' as u32;
+    pub const PAYBACK_DFI_TOKENS_PRINCIPAL: u32 = b'p' as u32;
+    pub const LOANS: u32 = b'q' as u32;
+    pub const TRANSFER_DOMAIN_STATS_LIVE: u32 = b'r' as u32;
+    pub const EVM_BLOCK_STATS_LIVE: u32 = b's' as u32;
+    pub const TOKEN_LOCK_RATIO: u32 = b't' as u32;
+    pub const LOCKED_TOKENS: u32 = b'u' as u32;
+}
+
+// DFIPKeys
+pub mod param_key {
+    pub const ACTIVE: u32 = b'a' as u32;
+    pub const PREMIUM: u32 = b'b' as u32;
+    pub const MIN_SWAP: u32 = b'c' as u32;
+    pub const REWARD_PCT: u32 = b'd' as u32;
+    pub const BLOCK_PERIOD: u32 = b'e' as u32;
+    pub const DUSD_INTEREST_BURN: u32 = b'g' as u32;
+    pub const DUSD_LOAN_BURN: u32 = b'h' as u32;
+    pub const START_BLOCK: u32 = b'i' as u32;
+    pub const GOV_UNSET: u32 = b'j' as u32;
+    pub const GOV_FOUNDATION: u32 = b'k' as u32;
+    pub const MN_SET_REWARD_ADDRESS: u32 = b'l' as u32;
+    pub const MN_SET_OPERATOR_ADDRESS: u32 = b'm' as u32;
+    pub const MN_SET_OWNER_ADDRESS: u32 = b'n' as u32;
+    pub const MEMBERS: u32 = b'p' as u32;
+    pub const GOVERNANCE_ENABLED: u32 = b'q' as u32;
+    pub const CFP_PAYOUT: u32 = b'r' as u32;
+    pub const EMISSION_UNUSED_FUND: u32 = b's' as u32;
+    pub const MINT_TOKENS: u32 = b't' as u32;
+    pub const EVM_ENABLED: u32 = b'u' as u32;
+    pub const ICX_ENABLED: u32 = b'v' as u32;
+    pub const TRANSFER_DOMAIN: u32 = b'w' as u32;
+    pub const LIQUIDITY_CALC_SAMPLING_PERIOD: u32 = b'x' as u32;
+    pub const AVERAGE_LIQUIDITY_PERCENTAGE: u32 = b'y' as u32;
+    pub const UNFREEZE_MASTERNODES: u32 = b'z' as u32;
+    pub const ASCENDING_BLOCK_TIME: u32 = b'A' as u32;
+    pub const GOV_HEIGHT_MIN_BLOCKS: u32 = b'B' as u32;
+    pub const COMMUNITY_GOVERNANCE: u32 = b'C' as u32;
+}
+
+pub mod governance_key {
+    pub const FEE_REDISTRIBUTION: u32 = b'a' as u32;
+    pub const FEE_BURN_PCT: u32 = b'b' as u32;
+    pub const CFP_FEE: u32 = b'd' as u32;
+    pub const CFP_APPROVAL_THRESHOLD: u32 = b'e' as u32;
+    pub const VOC_FEE: u32 = b'f' as u32;
+    pub const VOC_EMERGENCY_FEE: u32 = b'g' as u32;
+    pub const VOC_EMERGENCY_PERIOD: u32 = b'h' as u32;
+    pub const VOC_APPROVAL_THRESHOLD: u32 = b'i' as u32;
+    pub const QUORUM: u32 = b'j' as u32;
+    pub const VOTING_PERIOD: u32 = b'k' as u32;
+    pub const VOC_EMERGENCY_QUORUM: u32 = b'l' as u32;
+    pub const CFP_MAX_CYCLES: u32 = b'm' as u32;
+}
+
+pub mod token_key {
+    pub const PAYBACK_DFI: u32 = b'a' as u32;
+    pub const PAYBACK_DFI_FEE_PCT: u32 = b'b' as u32;
+    pub const LOAN_PAYBACK: u32 = b'c' as u32;
+    pub const LOAN_PAYBACK_FEE_PCT: u32 = b'd' as u32;
+    pub const DEX_IN_FEE_PCT: u32 = b'e' as u32;
+    pub const DEX_OUT_FEE_PCT: u32 = b'f' as u32;
+    pub const DFIP2203_ENABLED: u32 = b'g' as u32;
+    pub const FIXED_INTERVAL_PRICE_ID: u32 = b'h' as u32;
+    pub const LOAN_COLLATERAL_ENABLED: u32 = b'i' as u32;
+    pub const LOAN_COLLATERAL_FACTOR: u32 = b'j' as u32;
+    pub const LOAN_MINTING_ENABLED: u32 = b'k' as u32;
+    pub const LOAN_MINTING_INTEREST: u32 = b'l' as u32;
+    pub const ASCENDANT: u32 = b'm' as u32;
+    pub const DESCENDANT: u32 = b'n' as u32;
+    pub const EPITAPH: u32 = b'o' as u32;
+    pub const LOAN_PAYBACK_COLLATERAL: u32 = b'p' as u32;
+}
+
+pub mod pool_key {
+    pub const TOKEN_A_FEE_PCT: u32 = b'a' as u32;
+    pub const TOKEN_B_FEE_PCT: u32 = b'b' as u32;
+    pub const TOKEN_A_FEE_DIR: u32 = b'c' as u32;
+    pub const TOKEN_B_FEE_DIR: u32 = b'd' as u32;
+}
+
+pub mod transfer_key {
+    pub const TRANSFER_ENABLED: u32 = b'a' as u32;
+    pub const SRC_FORMATS: u32 = b'b' as u32;
+    pub const DEST_FORMATS: u32 = b'c' as u32;
+    pub const AUTH_FORMATS: u32 = b'd' as u32;
+    pub const NATIVE_ENABLED: u32 = b'e' as u32;
+    pub const DAT_ENABLED: u32 = b'f' as u32;
+    pub const DISALLOWED: u32 = b'g' as u32;
+}
+
+pub mod vault_key {
+    pub const CREATION_FEE: u32 = b'a' as u32;
+    pub const DUSD_VAULT_ENABLED: u32 = b'w' as u32;
+}
+
+pub mod oracle_key {
+    pub const FRACTIONAL_SPLITS: u32 = 0;
+}
+
+pub mod rules_key {
+    pub const CORE_OP_RETURN: u32 = b'a' as u32;
+    pub const DVM_OP_RETURN: u32 = b'b' as u32;
+    pub const EVM_OP_RETURN: u32 = b'c' as u32;
+}
+
+type Names = &'static [(u32, &'static str)];
+
+const TYPE_NAMES: Names = &[
+    (attribute_type::LIVE as u32, "live"),
+    (attribute_type::LOCKS as u32, "locks"),
+    (attribute_type::ORACLES as u32, "oracles"),
+    (attribute_type::PARAM as u32, "params"),
+    (attribute_type::POOLPAIRS as u32, "poolpairs"),
+    (attribute_type::TOKEN as u32, "token"),
+    (attribute_type::GOVERNANCE as u32, "gov"),
+    (attribute_type::TRANSFER as u32, "transferdomain"),
+    (attribute_type::EVM as u32, "evm"),
+    (attribute_type::VAULTS as u32, "vaults"),
+    (attribute_type::RULES as u32, "rules"),
+];
+
+const PARAM_IDS: Names = &[
+    (param_id::DFIP2201, "dfip2201"),
+    (param_id::DFIP2203, "dfip2203"),
+    (param_id::DFIP2206A, "dfip2206a"),
+    (param_id::DFIP2206F, "dfip2206f"),
+    (param_id::DFIP2211F, "dfip2211f"),
+    (param_id::FEATURE, "feature"),
+    (param_id::FOUNDATION, "foundation"),
+    (param_id::GOVERNANCE, "governance"),
+    (param_id::DTOKEN_RESTART, "dtoken_restart"),
+    (param_id::AUCTION, "auction"),
+    (param_id::ECONOMY, "economy"),
+    (param_id::TOKEN_ID, "token"),
+];
+
+const ORACLE_IDS: Names = &[(oracle_id::SPLITS, "splits")];
+
+const EVM_IDS: Names = &[(evm_id::BLOCK, "block")];
+
+const GOVERNANCE_IDS: Names = &[(governance_id::PROPOSALS, "proposals")];
+
+const TRANSFER_IDS: Names = &[
+    (transfer_id::EVM_TO_DVM, "evm-dvm"),
+    (transfer_id::DVM_TO_EVM, "dvm-evm"),
+];
+
+const VAULT_IDS: Names = &[
+    (vault_id::DUSD_VAULT, "dusd-vault"),
+    (vault_id::PARAMETERS, "params"),
+];
+
+const RULES_IDS: Names = &[(rules_id::TX_RULES, "tx")];
+
+const TOKEN_KEYS: Names = &[
+    (token_key::PAYBACK_DFI, "payback_dfi"),
+    (token_key::PAYBACK_DFI_FEE_PCT, "payback_dfi_fee_pct"),
+    (token_key::LOAN_PAYBACK, "loan_payback"),
+    (token_key::LOAN_PAYBACK_FEE_PCT, "loan_payback_fee_pct"),
+    (
+        token_key::LOAN_PAYBACK_COLLATERAL,
+        "loan_payback_collateral",
+    ),
+    (token_key::DEX_IN_FEE_PCT, "dex_in_fee_pct"),
+    (token_key::DEX_OUT_FEE_PCT, "dex_out_fee_pct"),
+    (
+        token_key::FIXED_INTERVAL_PRICE_ID,
+        "fixed_interval_price_id",
+    ),
+    (
+        token_key::LOAN_COLLATERAL_ENABLED,
+        "loan_collateral_enabled",
+    ),
+    (token_key::LOAN_COLLATERAL_FACTOR, "loan_collateral_factor"),
+    (token_key::LOAN_MINTING_ENABLED, "loan_minting_enabled"),
+    (token_key::LOAN_MINTING_INTEREST, "loan_minting_interest"),
+    (token_key::DFIP2203_ENABLED, "dfip2203"),
+    (token_key::ASCENDANT, "ascendant"),
+    (token_key::DESCENDANT, "descendant"),
+    (token_key::EPITAPH, "epitaph"),
+];
+
+const POOL_KEYS: Names = &[
+    (pool_key::TOKEN_A_FEE_PCT, "token_a_fee_pct"),
+    (pool_key::TOKEN_A_FEE_DIR, "token_a_fee_direction"),
+    (pool_key::TOKEN_B_FEE_PCT, "token_b_fee_pct"),
+    (pool_key::TOKEN_B_FEE_DIR, "token_b_fee_direction"),
+];
+
+const PARAM_KEYS: Names = &[
+    (param_key::ACTIVE, "active"),
+    (param_key::PREMIUM, "premium"),
+    (param_key::MIN_SWAP, "minswap"),
+    (param_key::REWARD_PCT, "reward_pct"),
+    (param_key::BLOCK_PERIOD, "block_period"),
+    (param_key::DUSD_INTEREST_BURN, "dusd_interest_burn"),
+    (param_key::DUSD_LOAN_BURN, "dusd_loan_burn"),
+    (param_key::START_BLOCK, "start_block"),
+    (param_key::GOV_UNSET, "gov-unset"),
+    (param_key::GOV_FOUNDATION, "gov-foundation"),
+    (param_key::MN_SET_REWARD_ADDRESS, "mn-setrewardaddress"),
+    (param_key::MN_SET_OPERATOR_ADDRESS, "mn-setoperatoraddress"),
+    (param_key::MN_SET_OWNER_ADDRESS, "mn-setowneraddress"),
+    (param_key::GOVERNANCE_ENABLED, "gov"),
+    (param_key::EVM_ENABLED, "evm"),
+    (param_key::ICX_ENABLED, "icx"),
+    (param_key::MEMBERS, "members"),
+    (param_key::CFP_PAYOUT, "gov-payout"),
+    (param_key::EMISSION_UNUSED_FUND, "emission-unused-fund"),
+    (param_key::MINT_TOKENS, "mint-tokens-to-address"),
+    (param_key::TRANSFER_DOMAIN, "transferdomain"),
+    (
+        param_key::LIQUIDITY_CALC_SAMPLING_PERIOD,
+        "liquidity_calc_sampling_period",
+    ),
+    (
+        param_key::AVERAGE_LIQUIDITY_PERCENTAGE,
+        "average_liquidity_percentage",
+    ),
+    (param_key::UNFREEZE_MASTERNODES, "unfreeze_masternodes"),
+    (param_key::COMMUNITY_GOVERNANCE, "governance"),
+    (param_key::ASCENDING_BLOCK_TIME, "ascending_block_time"),
+    (param_key::GOV_HEIGHT_MIN_BLOCKS, "govheight_min_blocks"),
+];
+
+const EVM_KEYS: Names = &[
+    (evm_key::FINALIZED, "finality_count"),
+    (evm_key::GAS_LIMIT, "gas_limit"),
+    (evm_key::GAS_TARGET_FACTOR, "gas_target_factor"),
+    (evm_key::RBF_INCREMENT_MIN_PCT, "rbf_increment_fee_pct"),
+];
+
+const ECONOMY_KEYS: Names = &[
+    (economy_key::PAYBACK_DFI_TOKENS, "dfi_payback_tokens"),
+    (
+        economy_key::PAYBACK_DFI_TOKENS_PRINCIPAL,
+        "dfi_payback_tokens_principal",
+    ),
+    (economy_key::DFIP2203_CURRENT, "dfip2203_current"),
+    (economy_key::DFIP2203_BURNED, "dfip2203_burned"),
+    (economy_key::DFIP2203_MINTED, "dfip2203_minted"),
+    (economy_key::DEX_TOKENS, "dex"),
+    (economy_key::DFIP2206F_CURRENT, "dfip2206f_current"),
+    (economy_key::DFIP2206F_BURNED, "dfip2206f_burned"),
+    (economy_key::DFIP2206F_MINTED, "dfip2206f_minted"),
+    (economy_key::NEGATIVE_INT, "negative_interest"),
+    (
+        economy_key::NEGATIVE_INT_CURRENT,
+        "negative_interest_current",
+    ),
+    (economy_key::BATCH_ROUNDING_EXCESS, "batch_rounding_excess"),
+    (economy_key::CONSOLIDATED_INTEREST, "consolidated_interest"),
+    (economy_key::LOANS, "loans"),
+    (economy_key::TRANSFER_DOMAIN_STATS_LIVE, "transferdomain"),
+    (economy_key::EVM_BLOCK_STATS_LIVE, "evm"),
+    (economy_key::TOKEN_LOCK_RATIO, "token_lock_ratio"),
+    (economy_key::LOCKED_TOKENS, "locked_tokens"),
+];
+
+const GOVERNANCE_KEYS: Names = &[
+    (governance_key::FEE_REDISTRIBUTION, "fee_redistribution"),
+    (governance_key::FEE_BURN_PCT, "fee_burn_pct"),
+    (governance_key::CFP_FEE, "cfp_fee"),
+    (
+        governance_key::CFP_APPROVAL_THRESHOLD,
+        "cfp_approval_threshold",
+    ),
+    (governance_key::VOC_FEE, "voc_fee"),
+    (governance_key::VOC_EMERGENCY_FEE, "voc_emergency_fee"),
+    (governance_key::VOC_EMERGENCY_PERIOD, "voc_emergency_period"),
+    (governance_key::VOC_EMERGENCY_QUORUM, "voc_emergency_quorum"),
+    (
+        governance_key::VOC_APPROVAL_THRESHOLD,
+        "voc_approval_threshold",
+    ),
+    (governance_key::QUORUM, "quorum"),
+    (governance_key::VOTING_PERIOD, "voting_period"),
+    (governance_key::CFP_MAX_CYCLES, "cfp_max_cycles"),
+];
+
+const TRANSFER_KEYS: Names = &[
+    (transfer_key::TRANSFER_ENABLED, "enabled"),
+    (transfer_key::SRC_FORMATS, "src-formats"),
+    (transfer_key::DEST_FORMATS, "dest-formats"),
+    (transfer_key::AUTH_FORMATS, "auth-formats"),
+    (transfer_key::NATIVE_ENABLED, "native-enabled"),
+    (transfer_key::DAT_ENABLED, "dat-enabled"),
+    (transfer_key::DISALLOWED, "disallowed"),
+];
+
+const VAULT_KEYS: Names = &[
+    (vault_key::CREATION_FEE, "creation_fee"),
+    (vault_key::DUSD_VAULT_ENABLED, "enabled"),
+];
+
+const RULES_KEYS: Names = &[
+    (rules_key::CORE_OP_RETURN, "core_op_return_max_size_bytes"),
+    (rules_key::DVM_OP_RETURN, "dvm_op_return_max_size_bytes"),
+    (rules_key::EVM_OP_RETURN, "evm_op_return_max_size_bytes"),
+];
+
+const ORACLE_KEYS: Names = &[(oracle_key::FRACTIONAL_SPLITS, "fractional_enabled")];
+
+fn id_names(r#type: u8) -> Names {
+    match r#type {
+        attribute_type::PARAM | attribute_type::LIVE | attribute_type::LOCKS => PARAM_IDS,
+        attribute_type::EVM => EVM_IDS,
+        attribute_type::ORACLES => ORACLE_IDS,
+        attribute_type::GOVERNANCE => GOVERNANCE_IDS,
+        attribute_type::TRANSFER => TRANSFER_IDS,
+        attribute_type::VAULTS => VAULT_IDS,
+        attribute_type::RULES => RULES_IDS,
+        _ => &[],
+    }
+}
+
+fn key_names(r#type: u8) -> Names {
+    match r#type {
+        attribute_type::TOKEN => TOKEN_KEYS,
+        attribute_type::POOLPAIRS => POOL_KEYS,
+        attribute_type::PARAM => PARAM_KEYS,
+        attribute_type::EVM => EVM_KEYS,
+        attribute_type::LIVE => ECONOMY_KEYS,
+        attribute_type::GOVERNANCE => GOVERNANCE_KEYS,
+        attribute_type::TRANSFER => TRANSFER_KEYS,
+        attribute_type::VAULTS => VAULT_KEYS,
+        attribute_type::RULES => RULES_KEYS,
+        attribute_type::ORACLES => ORACLE_KEYS,
+        _ => &[],
+    }
+}
+
+/// Writes the display name of `value`, falling back to the number itself when it has none.
+fn write_name(f: &mut fmt::Formatter, names: Names, value: u32) -> fmt::Result {
+    match names.iter().find(|(v, _)| *v == value) {
+        Some((_, name)) => f.write_str(name),
+        None => write!(f, "{value}"),
+    }
+}
+
+fn parse_name(names: Names, s: &str) -> Result<u32, ParseAttributeKeyError> {
+    match names.iter().find(|(_, name)| *name == s) {
+        Some((value, _)) => Ok(*value),
+        None => s.parse().map_err(|_| ParseAttributeKeyError(s.to_string())),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseAttributeKeyError(String);
+
+impl fmt::Display for ParseAttributeKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid attribute key {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseAttributeKeyError {}
+
+/// `CDataStructureV0`. `key_id` is only serialized for the per token `loan_payback` keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataStructureV0 {
+    pub r#type: u8,
+    pub type_id: u32,
+    pub key: u32,
+    pub key_id: u32,
+}
+
+impl DataStructureV0 {
+    pub fn new(r#type: u8, type_id: u32, key: u32) -> Self {
+        Self {
+            r#type,
+            type_id,
+            key,
+            key_id: 0,
+        }
+    }
+
+    pub fn is_extended_size(&self) -> bool {
+        self.r#type == attribute_type::TOKEN
+            && (self.key == token_key::LOAN_PAYBACK || self.key == token_key::LOAN_PAYBACK_FEE_PCT)
+    }
+
+    /// Keys displayed as numbers rather than names, such as token ids of locks.
+    fn is_numeric_key(&self) -> bool {
+        (self.r#type == attribute_type::ORACLES && self.key != oracle_key::FRACTIONAL_SPLITS)
+            || self.r#type == attribute_type::LOCKS
+            || (self.r#type == attribute_type::PARAM && self.type_id == param_id::DTOKEN_RESTART)
+    }
+}
+
+impl Encodable for DataStructureV0 {
+    fn consensus_encode<W: io::Write + ?Sized>(&self, writer: &mut W) -> Result<usize, io::Error> {
+        let mut len = self.r#type.consensus_encode(writer)?;
+        len += self.type_id.consensus_encode(writer)?;
+        len += VarInt(u64::from(self.key)).consensus_encode(writer)?;
+        if self.is_extended_size() {
+            len += self.key_id.consensus_encode(writer)?;
+        }
+        Ok(len)
+    }
+}
+
+impl Decodable for DataStructureV0 {
+    fn consensus_decode<R: io::Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        let r#type = u8::consensus_decode(reader)?;
+        let type_id = u32::consensus_decode(reader)?;
+        let key = u32::try_from(VarInt::consensus_decode(reader)?.0)
+            .map_err(|_| Error::ParseFailed("Attribute key out of range"))?;
+        let mut data = Self::new(r#type, type_id, key);
+        if data.is_extended_size() {
+            data.key_id = u32::consensus_decode(reader)?;
+        }
+        Ok(data)
+    }
+}
+
+/// Displays the key as the `v0/<type>/<id>/<key>` path used by defid's `getgov`.
+impl fmt::Display for DataStructureV0 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("v0/")?;
+        write_name(f, TYPE_NAMES, u32::from(self.r#type))?;
+        f.write_str("/")?;
+        write_name(f, id_names(self.r#type), self.type_id)?;
+        f.write_str("/")?;
+        if self.is_numeric_key() {
+            write!(f, "{}", self.key)?;
+        } else {
+            write_name(f, key_names(self.r#type), self.key)?;
+        }
+        if self.is_extended_size() {
+            write!(f, "/{}", self.key_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DataStructureV0 {
+    type Err = ParseAttributeKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseAttributeKeyError(s.to_string());
+        let parts = s.split('/').collect::<Vec<_>>();
+        let ["v0", r#type, type_id, key, rest @ ..] = parts.as_slice() else {
+            return Err(invalid());
+        };
+        let r#type = u8::try_from(parse_name(TYPE_NAMES, r#type)?).map_err(|_| invalid())?;
+        let type_id = parse_name(id_names(r#type), type_id)?;
+        let mut data = Self::new(r#type, type_id, 0);
+        data.key = if data.is_numeric_key() {
+            key.parse().map_err(|_| invalid())?
+        } else {
+            parse_name(key_names(r#type), key)?
+        };
+        match (data.is_extended_size(), rest) {
+            (true, [key_id]) => data.key_id = key_id.parse().map_err(|_| invalid())?,
+            (false, []) => {}
+            _ => return Err(invalid()),
+        }
+        Ok(data)
+    }
+}
+
+/// `CAttributeType`, serialized with its variant index.
+#[derive(ConsensusEncoding, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum AttributeType {
+    V0(DataStructureV0),
+    /// Reserved by defid for future use, without any data.
+    V1,
+}
+
+impl fmt::Display for AttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttributeType::V0(data) => data.fmt(f),
+            AttributeType::V1 => f.write_str("v1"),
+        }
+    }
+}
+
+impl FromStr for AttributeType {
+    type Err = ParseAttributeKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "v1" => Ok(Self::V1),
+            _ => s.parse().map(Self::V0),
+        }
+    }
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TokenPayback {
+    pub tokens_fee: CompactVec<TokenBalanceUInt32>,
+    pub tokens_payback: CompactVec<TokenBalanceUInt32>,
+}
+
+// OracleSplits
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct OracleSplit {
+    pub token_id: u32,
+    pub multiplier: i32,
+}
+
+// OracleSplits64
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct OracleSplit64 {
+    pub token_id: u32,
+    pub multiplier: i64,
+}
+
+// DescendantValue
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct Descendant {
+    pub token_id: u32,
+    pub height: i32,
+}
+
+// AscendantValue
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct Ascendant {
+    pub token_id: u32,
+    pub r#type: String,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct DexTokenInfo {
+    pub swaps: u64,
+    pub feeburn: u64,
+    pub commissions: u64,
+}
+
+// CDexBalances
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct DexBalance {
+    pub pool_id: VarInt,
+    pub total_token_a: DexTokenInfo,
+    pub total_token_b: DexTokenInfo,
+}
+
+// LegacyEntry1
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct LegacyStringEntry {
+    pub key: String,
+    pub value: String,
+}
+
+// LegacyEntry2
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct LegacyU64Entry {
+    pub key: String,
+    pub value: u64,
+}
+
+// LegacyEntry3
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct LegacyI64Entry {
+    pub key: String,
+    pub value: i64,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TransferDomainStatsLive {
+    pub dvm_evm_total: CompactVec<TokenBalanceUInt32>,
+    pub evm_dvm_total: CompactVec<TokenBalanceUInt32>,
+    pub dvm_in: CompactVec<TokenBalanceUInt32>,
+    pub evm_in: CompactVec<TokenBalanceUInt32>,
+    pub dvm_out: CompactVec<TokenBalanceUInt32>,
+    pub evm_out: CompactVec<TokenBalanceUInt32>,
+    pub dvm_current: CompactVec<TokenBalanceUInt32>,
+    pub evm_current: CompactVec<TokenBalanceUInt32>,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct EvmBlockStatsLive {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
+    pub fee_burnt: i64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
+    pub fee_burnt_min: i64,
+    pub fee_burnt_min_hash: sha256d::Hash,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
+    pub fee_burnt_max: i64,
+    pub fee_burnt_max_hash: sha256d::Hash,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
+    pub fee_priority: i64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
+    pub fee_priority_min: i64,
+    pub fee_priority_min_hash: sha256d::Hash,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
+    pub fee_priority_max: i64,
+    pub fee_priority_max_hash: sha256d::Hash,
+}
+
+/// `CAttributeValue`, serialized with its variant index. With the `serde` feature, values are
+/// tagged with the variant name since defid's display form does not identify the type.
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[repr(i32)]
+pub enum AttributeValue {
+    Bool(bool),
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
+    Amount(i64),
+    Balances(CompactVec<TokenBalanceUInt32>),
+    TokenPayback(TokenPayback),
+    CurrencyPair(CurrencyPair),
+    OracleSplits(CompactVec<OracleSplit>),
+    Descendant(Descendant),
+    Ascendant(Ascendant),
+    FeeDir(u8),
+    DexBalances(CompactVec<DexBalance>),
+    Scripts(CompactVec<ScriptBuf>),
+    Strings(CompactVec<String>),
+    LegacyEntry1(CompactVec<LegacyStringEntry>),
+    LegacyEntry2(CompactVec<LegacyU64Entry>),
+    LegacyEntry3(CompactVec<LegacyI64Entry>),
+    I32(i32),
+    U32(u32),
+    U64(u64),
+    AddressFormats(CompactVec<u8>),
+    TransferDomainStats(TransferDomainStatsLive),
+    EvmBlockStats(EvmBlockStatsLive),
+    OracleSplits64(CompactVec<OracleSplit64>),
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct Attribute {
+    pub key: AttributeType,
+    pub value: AttributeValue,
+}
+
+/// The attributes map, kept in serialized order.
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct Attributes {
+    pub key: String,
+    pub value: CompactVec<Attribute>,
+}
+
+impl Attributes {
+    pub fn get(&self, key: &DataStructureV0) -> Option<&AttributeValue> {
+        self.value
+            .as_ref()
+            .iter()
+            .find(|attr| attr.key == AttributeType::V0(*key))
+            .map(|attr| &attr.value)
+    }
+
+    /// Block gas limit set with `v0/evm/block/gas_limit`.
+    pub fn evm_block_gas_limit(&self) -> Option<u64> {
+        match self.get(&DataStructureV0::new(
+            attribute_type::EVM,
+            evm_id::BLOCK,
+            evm_key::GAS_LIMIT,
+        )) {
+            Some(AttributeValue::U64(v)) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+/// Attribute entries read from an object of `v0/<type>/<id>/<key>` paths to tagged values, in
+/// the order of the object.
+#[cfg(feature = "serde")]
+pub(crate) struct AttributeEntries(pub Vec<Attribute>);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AttributeEntries {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = AttributeEntries;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of attribute keys to values")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                use serde::de::Error;
+
+                let mut attrs = Vec::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    attrs.push(Attribute {
+                        key: key.parse().map_err(A::Error::custom)?,
+                        value: map.next_value()?,
+                    });
+                }
+                Ok(AttributeEntries(attrs))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
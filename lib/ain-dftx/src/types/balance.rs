@@ -1,5 +1,5 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::{io, ScriptBuf, VarInt};
+use bitcoin::{ScriptBuf, VarInt};
 
 use super::common::CompactVec;
 
@@ -22,3 +22,47 @@ pub struct TokenBalanceVarInt {
     pub token: VarInt,
     pub amount: i64,
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TokenBalanceUInt32 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = crate::serde_utils::format_token_amount(u64::from(self.token), self.amount);
+        serializer.serialize_str(&s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TokenBalanceUInt32 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let s = String::deserialize(deserializer)?;
+        let (token, amount) =
+            crate::serde_utils::parse_token_amount(&s).map_err(D::Error::custom)?;
+        let token = u32::try_from(token).map_err(|_| D::Error::custom("token id out of range"))?;
+        Ok(Self { token, amount })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TokenBalanceVarInt {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = crate::serde_utils::format_token_amount(self.token.0, self.amount);
+        serializer.serialize_str(&s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TokenBalanceVarInt {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let s = String::deserialize(deserializer)?;
+        let (token, amount) =
+            crate::serde_utils::parse_token_amount(&s).map_err(D::Error::custom)?;
+        Ok(Self {
+            token: VarInt(token),
+            amount,
+        })
+    }
+}
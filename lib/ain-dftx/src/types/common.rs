@@ -3,6 +3,9 @@ use bitcoin::{
     io::{self, ErrorKind},
 };
 
+/// Upper bound on the number of items preallocated when decoding a [`CompactVec`].
+const MAX_PREALLOC: u64 = 1024;
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct CompactVec<T>(Vec<T>);
 
@@ -24,7 +27,9 @@ impl<T: Decodable + std::fmt::Debug> Decodable for CompactVec<T> {
         r: &mut R,
     ) -> Result<Self, bitcoin::consensus::encode::Error> {
         let len = VarInt::consensus_decode(r)?.0;
-        let mut ret = Vec::with_capacity(len as usize);
+        // The length is untrusted, so only preallocate a bounded amount and let the vec grow
+        // as items are actually decoded
+        let mut ret = Vec::with_capacity(len.min(MAX_PREALLOC) as usize);
         for _ in 0..len {
             ret.push(Decodable::consensus_decode(r)?);
         }
@@ -80,6 +85,7 @@ impl<T> From<Option<T>> for Maybe<T> {
     }
 }
 
+/// Trailing bytes, decoded by reading the remainder of the input.
 #[derive(Debug, PartialEq, Eq)]
 pub struct RawBytes(pub Vec<u8>);
 
@@ -88,7 +94,8 @@ impl Encodable for RawBytes {
         &self,
         writer: &mut W,
     ) -> Result<usize, bitcoin::io::Error> {
-        writer.write(&self.0)
+        writer.write_all(&self.0)?;
+        Ok(self.0.len())
     }
 }
 
@@ -96,9 +103,17 @@ impl Decodable for RawBytes {
     fn consensus_decode<R: bitcoin::io::Read + ?Sized>(
         reader: &mut R,
     ) -> Result<Self, bitcoin::consensus::encode::Error> {
+        let mut bytes = Vec::new();
         let mut buf = [0u8; 512];
-        let v = reader.read(&mut buf)?;
-        Ok(Self(buf[..v].to_vec()))
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => bytes.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(Self(bytes))
     }
 }
 
@@ -1,7 +1,12 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::io;
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct EvmTx {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub raw: Vec<u8>,
 }
@@ -5,7 +5,7 @@ use bitcoin::{
     ScriptBuf, Txid, VarInt,
 };
 
-use super::common::CompactVec;
+use super::{attributes::Attributes, common::CompactVec};
 use crate::{common::RawBytes, types::common::Maybe};
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
@@ -26,6 +26,36 @@ pub struct LpSplits {
     pub value: CompactVec<LiqPoolSplit>,
 }
 
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct LpDailyLoanTokenReward {
+    pub key: String,
+    pub value: i64,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct LoanLiquidationPenalty {
+    pub key: String,
+    pub value: i64,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct OracleBlockInterval {
+    pub key: String,
+    pub value: u32,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct OracleDeviation {
+    pub key: String,
+    pub value: i64,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct IcxTakerFeePerBtc {
+    pub key: String,
+    pub value: i64,
+}
+
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
 pub struct LpUnmapped {
     pub key: String,
@@ -49,6 +79,12 @@ pub enum GovernanceVar {
     LpDailyReward(LpDailyReward),
     LpSplits(LpSplits),
     LpLoanTokenSplits(LpLoanTokenSplits),
+    LpDailyLoanTokenReward(LpDailyLoanTokenReward),
+    LoanLiquidationPenalty(LoanLiquidationPenalty),
+    OracleBlockInterval(OracleBlockInterval),
+    OracleDeviation(OracleDeviation),
+    IcxTakerFeePerBtc(IcxTakerFeePerBtc),
+    Attributes(Attributes),
     Unmapped(LpUnmapped),
 }
 
@@ -58,6 +94,12 @@ impl Encodable for GovernanceVar {
             GovernanceVar::LpDailyReward(data) => data.consensus_encode(writer),
             GovernanceVar::LpSplits(data) => data.consensus_encode(writer),
             GovernanceVar::LpLoanTokenSplits(data) => data.consensus_encode(writer),
+            GovernanceVar::LpDailyLoanTokenReward(data) => data.consensus_encode(writer),
+            GovernanceVar::LoanLiquidationPenalty(data) => data.consensus_encode(writer),
+            GovernanceVar::OracleBlockInterval(data) => data.consensus_encode(writer),
+            GovernanceVar::OracleDeviation(data) => data.consensus_encode(writer),
+            GovernanceVar::IcxTakerFeePerBtc(data) => data.consensus_encode(writer),
+            GovernanceVar::Attributes(data) => data.consensus_encode(writer),
             GovernanceVar::Unmapped(data) => data.consensus_encode(writer),
         }
     }
@@ -81,6 +123,34 @@ impl Decodable for GovernanceVar {
                 key,
                 value: <CompactVec<LoanTokenSplit>>::consensus_decode(reader)?,
             })),
+            "LP_DAILY_LOAN_TOKEN_REWARD" => {
+                Ok(Self::LpDailyLoanTokenReward(LpDailyLoanTokenReward {
+                    key,
+                    value: i64::consensus_decode(reader)?,
+                }))
+            }
+            "LOAN_LIQUIDATION_PENALTY" => {
+                Ok(Self::LoanLiquidationPenalty(LoanLiquidationPenalty {
+                    key,
+                    value: i64::consensus_decode(reader)?,
+                }))
+            }
+            "ORACLE_BLOCK_INTERVAL" => Ok(Self::OracleBlockInterval(OracleBlockInterval {
+                key,
+                value: u32::consensus_decode(reader)?,
+            })),
+            "ORACLE_DEVIATION" => Ok(Self::OracleDeviation(OracleDeviation {
+                key,
+                value: i64::consensus_decode(reader)?,
+            })),
+            "ICX_TAKERFEE_PER_BTC" => Ok(Self::IcxTakerFeePerBtc(IcxTakerFeePerBtc {
+                key,
+                value: i64::consensus_decode(reader)?,
+            })),
+            "ATTRIBUTES" => Ok(Self::Attributes(Attributes {
+                key,
+                value: CompactVec::consensus_decode(reader)?,
+            })),
             _ => Ok(Self::Unmapped(LpUnmapped {
                 key,
                 value: RawBytes::consensus_decode(reader)?,
@@ -89,6 +159,211 @@ impl Decodable for GovernanceVar {
     }
 }
 
+#[cfg(feature = "serde")]
+impl GovernanceVar {
+    fn key(&self) -> &str {
+        match self {
+            GovernanceVar::LpDailyReward(v) => &v.key,
+            GovernanceVar::LpSplits(v) => &v.key,
+            GovernanceVar::LpLoanTokenSplits(v) => &v.key,
+            GovernanceVar::LpDailyLoanTokenReward(v) => &v.key,
+            GovernanceVar::LoanLiquidationPenalty(v) => &v.key,
+            GovernanceVar::OracleBlockInterval(v) => &v.key,
+            GovernanceVar::OracleDeviation(v) => &v.key,
+            GovernanceVar::IcxTakerFeePerBtc(v) => &v.key,
+            GovernanceVar::Attributes(v) => &v.key,
+            GovernanceVar::Unmapped(v) => &v.key,
+        }
+    }
+
+    /// Reads the value for `key` from a JSON object, mirroring the consensus decoding above.
+    fn next_value<'de, A: serde::de::MapAccess<'de>>(
+        key: String,
+        map: &mut A,
+    ) -> Result<Self, A::Error> {
+        use serde::de::Error;
+
+        use crate::{serde_utils::parse_amount, types::attributes::AttributeEntries};
+
+        let amount = |s: String| {
+            parse_amount(&s).and_then(|amount| {
+                i64::try_from(amount).map_err(|_| format!("amount out of range {s}"))
+            })
+        };
+        let splits = |splits: Vec<(String, String)>| {
+            splits
+                .into_iter()
+                .map(|(token, value)| {
+                    let token = token
+                        .parse::<u64>()
+                        .map_err(|_| format!("invalid token id {token}"))?;
+                    amount(value).map(|value| (token, value))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(A::Error::custom)
+        };
+        /// Keeps the order of the splits in the object.
+        struct Splits(Vec<(String, String)>);
+
+        impl<'de> serde::Deserialize<'de> for Splits {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor;
+
+                impl<'de> serde::de::Visitor<'de> for Visitor {
+                    type Value = Splits;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str("a map of token ids to amounts")
+                    }
+
+                    fn visit_map<A: serde::de::MapAccess<'de>>(
+                        self,
+                        mut map: A,
+                    ) -> Result<Self::Value, A::Error> {
+                        let mut splits = Vec::new();
+                        while let Some(entry) = map.next_entry()? {
+                            splits.push(entry);
+                        }
+                        Ok(Splits(splits))
+                    }
+                }
+
+                deserializer.deserialize_map(Visitor)
+            }
+        }
+
+        Ok(match key.as_str() {
+            "LP_DAILY_DFI_REWARD" => Self::LpDailyReward(LpDailyReward {
+                key,
+                value: amount(map.next_value()?).map_err(A::Error::custom)?,
+            }),
+            "LP_DAILY_LOAN_TOKEN_REWARD" => Self::LpDailyLoanTokenReward(LpDailyLoanTokenReward {
+                key,
+                value: amount(map.next_value()?).map_err(A::Error::custom)?,
+            }),
+            "LOAN_LIQUIDATION_PENALTY" => Self::LoanLiquidationPenalty(LoanLiquidationPenalty {
+                key,
+                value: amount(map.next_value()?).map_err(A::Error::custom)?,
+            }),
+            "ORACLE_BLOCK_INTERVAL" => Self::OracleBlockInterval(OracleBlockInterval {
+                key,
+                value: map.next_value()?,
+            }),
+            "ORACLE_DEVIATION" => Self::OracleDeviation(OracleDeviation {
+                key,
+                value: amount(map.next_value()?).map_err(A::Error::custom)?,
+            }),
+            "ICX_TAKERFEE_PER_BTC" => Self::IcxTakerFeePerBtc(IcxTakerFeePerBtc {
+                key,
+                value: amount(map.next_value()?).map_err(A::Error::custom)?,
+            }),
+            "ATTRIBUTES" => Self::Attributes(Attributes {
+                key,
+                value: map.next_value::<AttributeEntries>()?.0.into(),
+            }),
+            "LP_SPLITS" => {
+                let value = splits(map.next_value::<Splits>()?.0)?
+                    .into_iter()
+                    .map(|(token_id, value)| {
+                        u32::try_from(token_id)
+                            .map(|token_id| LiqPoolSplit { token_id, value })
+                            .map_err(|_| A::Error::custom("token id out of range"))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Self::LpSplits(LpSplits {
+                    key,
+                    value: value.into(),
+                })
+            }
+            "LP_LOAN_TOKEN_SPLITS" => {
+                let value = splits(map.next_value::<Splits>()?.0)?
+                    .into_iter()
+                    .map(|(token_id, value)| LoanTokenSplit {
+                        token_id: VarInt(token_id),
+                        value,
+                    })
+                    .collect::<Vec<_>>();
+                Self::LpLoanTokenSplits(LpLoanTokenSplits {
+                    key,
+                    value: value.into(),
+                })
+            }
+            _ => Self::Unmapped(LpUnmapped {
+                key,
+                value: map.next_value()?,
+            }),
+        })
+    }
+}
+
+/// Serializes the value of a governance variable, keyed by its name in the enclosing object.
+#[cfg(feature = "serde")]
+struct GovernanceValue<'a>(&'a GovernanceVar);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for GovernanceValue<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        use crate::serde_utils::format_amount;
+
+        let amount = |value: i64| format_amount(i128::from(value));
+        match self.0 {
+            GovernanceVar::LpDailyReward(v) => serializer.serialize_str(&amount(v.value)),
+            GovernanceVar::LpDailyLoanTokenReward(v) => serializer.serialize_str(&amount(v.value)),
+            GovernanceVar::LoanLiquidationPenalty(v) => serializer.serialize_str(&amount(v.value)),
+            GovernanceVar::OracleBlockInterval(v) => serializer.serialize_u32(v.value),
+            GovernanceVar::OracleDeviation(v) => serializer.serialize_str(&amount(v.value)),
+            GovernanceVar::IcxTakerFeePerBtc(v) => serializer.serialize_str(&amount(v.value)),
+            GovernanceVar::Attributes(v) => serializer.collect_map(
+                v.value
+                    .as_ref()
+                    .iter()
+                    .map(|attr| (attr.key.to_string(), &attr.value)),
+            ),
+            GovernanceVar::LpSplits(v) => {
+                let mut map = serializer.serialize_map(Some(v.value.as_ref().len()))?;
+                for split in v.value.as_ref() {
+                    map.serialize_entry(&split.token_id.to_string(), &amount(split.value))?;
+                }
+                map.end()
+            }
+            GovernanceVar::LpLoanTokenSplits(v) => {
+                let mut map = serializer.serialize_map(Some(v.value.as_ref().len()))?;
+                for split in v.value.as_ref() {
+                    map.serialize_entry(&split.token_id.0.to_string(), &amount(split.value))?;
+                }
+                map.end()
+            }
+            GovernanceVar::Unmapped(v) => v.value.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for GovernanceVar {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.key(), &GovernanceValue(self))?;
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GovernanceVar {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut vars = SetGovernance::deserialize(deserializer)?.governance_vars;
+        match (vars.pop(), vars.is_empty()) {
+            (Some(var), true) => Ok(var),
+            _ => Err(D::Error::custom("expected a single governance variable")),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct SetGovernance {
     pub governance_vars: Vec<GovernanceVar>,
@@ -117,9 +392,58 @@ impl Decodable for SetGovernance {
     }
 }
 
+/// Governance variables are serialized as a single object keyed by variable name, as in
+/// defid's `decodecustomtx`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SetGovernance {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.governance_vars.len()))?;
+        for var in &self.governance_vars {
+            map.serialize_entry(var.key(), &GovernanceValue(var))?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SetGovernance {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = SetGovernance;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a map of governance variables")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut governance_vars = Vec::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    governance_vars.push(GovernanceVar::next_value(key, &mut map)?);
+                }
+                Ok(SetGovernance { governance_vars })
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SetGovernanceHeight {
     pub var: GovernanceVar,
+    #[cfg_attr(feature = "serde", serde(rename = "startHeight"))]
     pub activation_height: u32,
 }
 
@@ -138,7 +462,12 @@ impl Decodable for SetGovernanceHeight {
         let mut var = GovernanceVar::consensus_decode(reader)?;
         let activation_height = match var {
             GovernanceVar::Unmapped(ref mut v) => {
-                let height_bytes = v.value.0.drain((v.value.0.len() - 4)..).collect::<Vec<_>>();
+                let Some(at) = v.value.0.len().checked_sub(4) else {
+                    return Err(bitcoin::consensus::encode::Error::ParseFailed(
+                        "Missing activation height",
+                    ));
+                };
+                let height_bytes = v.value.0.drain(at..).collect::<Vec<_>>();
                 u32::consensus_decode(&mut height_bytes.as_slice())
             }
             _ => reader.read_u32(),
@@ -151,10 +480,76 @@ impl Decodable for SetGovernanceHeight {
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct UnsetGovernanceVar {
+    pub key: String,
+    pub values: CompactVec<String>,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+pub struct UnsetGovernance {
+    pub governance_vars: CompactVec<UnsetGovernanceVar>,
+}
+
+/// Unset variables are serialized as an object of variable name to the keys unset.
+#[cfg(feature = "serde")]
+impl serde::Serialize for UnsetGovernance {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.governance_vars.as_ref().len()))?;
+        for var in self.governance_vars.as_ref() {
+            map.serialize_entry(&var.key, &var.values)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UnsetGovernance {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = UnsetGovernance;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a map of governance variables to unset keys")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut governance_vars = Vec::new();
+                while let Some((key, values)) = map.next_entry()? {
+                    governance_vars.push(UnsetGovernanceVar { key, values });
+                }
+                Ok(UnsetGovernance {
+                    governance_vars: governance_vars.into(),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct CreateProposal {
     pub r#type: u8,
+    #[cfg_attr(feature = "serde", serde(rename = "payoutAddress"))]
     pub address: ScriptBuf,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "amount", with = "crate::serde_utils::amount")
+    )]
     pub n_amount: i64,
+    #[cfg_attr(feature = "serde", serde(rename = "cycles"))]
     pub n_cycles: u8,
     pub title: String,
     pub context: String,
@@ -163,8 +558,14 @@ pub struct CreateProposal {
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Vote {
     pub proposal_id: Txid,
     pub masternode_id: Txid,
+    #[cfg_attr(feature = "serde", serde(rename = "vote"))]
     pub vote_decision: u8,
 }
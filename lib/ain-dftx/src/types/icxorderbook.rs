@@ -1,60 +1,113 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::{io, ScriptBuf, Txid};
+use bitcoin::{ScriptBuf, Txid};
 
 use crate::common::{CompactVec, Maybe, VarInt};
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct ICXCreateOrder {
     pub order_type: u8,
     pub token_id: VarInt,
     pub owner_address: ScriptBuf,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_utils::maybe_compact_hex")
+    )]
     pub receive_pubkey: Maybe<CompactVec<u8>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub amount_from: i64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub amount_to_fill: i64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub order_price: i64,
     pub expiry: u32,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct ICXMakeOffer {
     pub order_tx: Txid,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub amount: i64,
     pub owner_address: ScriptBuf,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_utils::maybe_compact_hex")
+    )]
     pub receive_pubkey: Maybe<CompactVec<u8>>,
     pub expiry: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub taker_fee: u64,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct ICXSubmitDFCHTLC {
     pub offer_tx: Txid,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub amount: i64,
     pub hash: Txid,
     pub timeout: u32,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct ICXSubmitEXTHTLC {
     pub offer_tx: Txid,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub amount: i64,
     pub hash: Txid,
     pub htlc_script_address: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::compact_hex"))]
     pub owner_pubkey: CompactVec<u8>,
     pub timeout: u32,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct ICXClaimDFCHTLC {
+    #[cfg_attr(feature = "serde", serde(rename = "dfchtlcTx"))]
     pub dfc_htlc_tx: Txid,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hex_bytes"))]
     pub seed: Vec<u8>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct ICXCloseOrder {
     pub order_tx: Txid,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct ICXCloseOffer {
     pub offer_tx: Txid,
 }
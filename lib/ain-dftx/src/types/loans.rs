@@ -1,77 +1,162 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::{io, ScriptBuf, Txid, VarInt};
+use bitcoin::{ScriptBuf, Txid, VarInt};
 
 use super::{balance::TokenBalanceUInt32, common::CompactVec, price::CurrencyPair};
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SetLoanScheme {
+    #[cfg_attr(feature = "serde", serde(rename = "mincolratio"))]
     pub ratio: u32,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "interestrate", with = "crate::serde_utils::amount")
+    )]
     pub rate: i64,
+    #[cfg_attr(feature = "serde", serde(rename = "id"))]
     pub identifier: String,
+    #[cfg_attr(feature = "serde", serde(rename = "updateHeight"))]
     pub update: i64,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct DestroyLoanScheme {
+    #[cfg_attr(feature = "serde", serde(rename = "id"))]
     pub identifier: String,
+    #[cfg_attr(feature = "serde", serde(rename = "destroyHeight"))]
     pub height: i64,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SetDefaultLoanScheme {
+    #[cfg_attr(feature = "serde", serde(rename = "id"))]
     pub identifier: String,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SetCollateralToken {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::var_int"))]
     pub token: VarInt,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub factor: i64,
+    #[cfg_attr(feature = "serde", serde(rename = "fixedIntervalPriceId"))]
     pub currency_pair: CurrencyPair,
     pub activate_after_block: u32,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SetLoanToken {
     pub symbol: String,
     pub name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "fixedIntervalPriceId"))]
     pub currency_pair: CurrencyPair,
     pub mintable: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub interest: i64,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UpdateLoanToken {
     pub symbol: String,
     pub name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "fixedIntervalPriceId"))]
     pub currency_pair: CurrencyPair,
     pub mintable: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub interest: i64,
+    #[cfg_attr(feature = "serde", serde(rename = "id"))]
     pub token_tx: Txid,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TakeLoan {
     pub vault_id: Txid,
     pub to: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "amounts"))]
     pub token_amounts: CompactVec<TokenBalanceUInt32>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PaybackLoan {
     pub vault_id: Txid,
     pub from: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "amounts"))]
     pub token_amounts: CompactVec<TokenBalanceUInt32>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TokenPayback {
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "dToken", with = "crate::serde_utils::var_int")
+    )]
     pub d_token: VarInt,
     pub amounts: CompactVec<TokenBalanceUInt32>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PaybackLoanV2 {
     pub vault_id: Txid,
     pub from: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "dToken"))]
     pub loans: CompactVec<TokenPayback>,
 }
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PaybackWithCollateral {
+    pub vault_id: Txid,
+}
@@ -1,72 +1,93 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::{
-    consensus::{Decodable, Encodable},
-    hashes::Hash,
-    io, PubkeyHash, Txid, VarInt,
-};
+use bitcoin::{PubkeyHash, Txid};
 
-use super::common::{CompactVec, Maybe};
+use super::common::CompactVec;
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct CreateMasternode {
     pub operator_type: u8,
     pub operator_pub_key_hash: PubkeyHash,
-    pub timelock: Maybe<u16>,
+    #[consensus(optional)]
+    pub timelock: Option<u16>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct ResignMasternode {
+    #[cfg_attr(feature = "serde", serde(rename = "id"))]
     pub node_id: Txid,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UpdateMasternodeAddress {
     pub r#type: u8,
+    #[consensus(with = "key_hash")]
     pub address_pub_key_hash: Option<PubkeyHash>,
 }
 
-impl Encodable for UpdateMasternodeAddress {
-    fn consensus_encode<W: io::Write + ?Sized>(&self, writer: &mut W) -> Result<usize, io::Error> {
-        let mut len = self.r#type.consensus_encode(writer)?;
-        match self.address_pub_key_hash {
-            Some(key) => {
-                len += 20u8.consensus_encode(writer)?;
-                len += writer.write(&key.to_byte_array())?;
-            }
-            None => {
-                len += 0u8.consensus_encode(writer)?;
-            }
+/// Optional key hash, serialized as a byte vector that is empty when unset.
+mod key_hash {
+    use bitcoin::{
+        consensus::{encode::Error, Decodable, Encodable},
+        hashes::Hash,
+        io, PubkeyHash, VarInt,
+    };
+
+    pub fn consensus_encode<W: io::Write + ?Sized>(
+        hash: &Option<PubkeyHash>,
+        writer: &mut W,
+    ) -> Result<usize, io::Error> {
+        match hash {
+            Some(hash) => hash.to_byte_array().to_vec().consensus_encode(writer),
+            None => VarInt(0).consensus_encode(writer),
         }
-        Ok(len)
     }
-}
 
-impl Decodable for UpdateMasternodeAddress {
-    fn consensus_decode<R: io::Read + ?Sized>(
+    pub fn consensus_decode<R: io::Read + ?Sized>(
         reader: &mut R,
-    ) -> Result<Self, bitcoin::consensus::encode::Error> {
-        let r#type = u8::consensus_decode(reader)?;
+    ) -> Result<Option<PubkeyHash>, Error> {
         let len = VarInt::consensus_decode(reader)?;
-        let address_pub_key_hash = if len.0 > 0 {
-            Some(PubkeyHash::consensus_decode(reader)?)
+        if len.0 > 0 {
+            Ok(Some(PubkeyHash::consensus_decode(reader)?))
         } else {
-            None
-        };
-        Ok(UpdateMasternodeAddress {
-            r#type,
-            address_pub_key_hash,
-        })
+            Ok(None)
+        }
     }
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UpdateMasternodeData {
     pub r#type: u8,
     pub address: UpdateMasternodeAddress,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UpdateMasternode {
+    #[cfg_attr(feature = "serde", serde(rename = "id"))]
     pub node_id: Txid,
     pub updates: CompactVec<UpdateMasternodeData>,
 }
@@ -1,5 +1,5 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::{hash_types::Txid, io, ScriptBuf};
+use bitcoin::{hash_types::Txid, ScriptBuf};
 
 use super::{
     common::CompactVec,
@@ -7,6 +7,11 @@ use super::{
 };
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SetOracleData {
     pub oracle_id: Txid,
     pub timestamp: i64,
@@ -14,21 +19,40 @@ pub struct SetOracleData {
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct RemoveOracle {
     pub oracle_id: Txid,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct AppointOracle {
+    #[cfg_attr(feature = "serde", serde(rename = "oracleAddress"))]
     pub script: ScriptBuf,
     pub weightage: u8,
+    #[cfg_attr(feature = "serde", serde(rename = "availablePairs"))]
     pub price_feeds: CompactVec<CurrencyPair>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UpdateOracle {
     pub oracle_id: Txid,
+    #[cfg_attr(feature = "serde", serde(rename = "oracleAddress"))]
     pub script: ScriptBuf,
     pub weightage: u8,
+    #[cfg_attr(feature = "serde", serde(rename = "availablePairs"))]
     pub price_feeds: CompactVec<CurrencyPair>,
 }
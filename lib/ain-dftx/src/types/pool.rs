@@ -1,67 +1,120 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::{io, ScriptBuf};
+use bitcoin::ScriptBuf;
 
 use super::{
     balance::{ScriptBalances, TokenBalanceUInt32, TokenBalanceVarInt},
-    common::{CompactVec, Maybe},
+    common::CompactVec,
 };
 use crate::common::VarInt;
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct MaxPrice {
-    integer: i64,
-    fraction: i64,
+    pub integer: i64,
+    pub fraction: i64,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PoolSwap {
+    #[cfg_attr(feature = "serde", serde(rename = "fromAddress"))]
     pub from_script: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "fromToken"))]
     pub from_token_id: VarInt,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub from_amount: i64,
+    #[cfg_attr(feature = "serde", serde(rename = "toAddress"))]
     pub to_script: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "toToken"))]
     pub to_token_id: VarInt,
     pub max_price: MaxPrice,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PoolId {
     pub id: VarInt,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct CompositeSwap {
+    #[cfg_attr(feature = "serde", serde(flatten))]
     pub pool_swap: PoolSwap,
     pub pools: CompactVec<PoolId>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PoolAddLiquidity {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::accounts"))]
     pub from: CompactVec<ScriptBalances>,
+    #[cfg_attr(feature = "serde", serde(rename = "shareaddress"))]
     pub share_address: ScriptBuf,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PoolRemoveLiquidity {
+    #[cfg_attr(feature = "serde", serde(rename = "from"))]
     pub script: ScriptBuf,
     pub amount: TokenBalanceVarInt,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PoolCreatePair {
     pub token_a: VarInt,
     pub token_b: VarInt,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub commission: i64,
     pub owner_address: ScriptBuf,
     pub status: u8,
     pub pair_symbol: String,
-    pub custom_rewards: Maybe<CompactVec<TokenBalanceUInt32>>,
+    #[consensus(optional)]
+    pub custom_rewards: Option<CompactVec<TokenBalanceUInt32>>,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PoolUpdatePair {
     pub pool_id: u32,
     pub status: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub commission: i64,
     pub owner_address: ScriptBuf,
-    pub custom_rewards: Maybe<CompactVec<TokenBalanceUInt32>>,
+    #[consensus(optional)]
+    pub custom_rewards: Option<CompactVec<TokenBalanceUInt32>>,
 }
@@ -1,21 +1,36 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::io;
 
 use super::common::CompactVec;
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct CurrencyPair {
     pub token: String,
     pub currency: String,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TokenAmount {
     pub currency: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub amount: i64,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TokenPrice {
     pub token: String,
     pub prices: CompactVec<TokenAmount>,
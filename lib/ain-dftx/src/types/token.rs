@@ -6,12 +6,17 @@ use bitcoin::{
 use bitflags::bitflags;
 
 use super::{balance::TokenBalanceUInt32, common::CompactVec};
-use crate::common::Maybe;
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct MintToken {
     pub balances: CompactVec<TokenBalanceUInt32>,
-    pub to: Maybe<ScriptBuf>,
+    #[consensus(optional)]
+    pub to: Option<ScriptBuf>,
 }
 
 bitflags! {
@@ -50,33 +55,59 @@ impl Decodable for TokenFlags {
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct CreateToken {
     pub symbol: String,
     pub name: String,
     pub decimal: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::amount"))]
     pub limit: i64,
     pub flags: u8,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UpdateToken {
     pub creation_tx: Txid,
     pub is_dat: bool,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UpdateTokenAny {
     pub creation_tx: Txid,
     pub token: CreateToken,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct VariantScript {
     pub r#type: u32,
     pub context: ScriptBuf,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct BurnToken {
     pub amounts: CompactVec<TokenBalanceUInt32>,
     pub from: ScriptBuf,
@@ -1,45 +1,80 @@
 use ain_macros::ConsensusEncoding;
-use bitcoin::{io, ScriptBuf, Txid};
+use bitcoin::{ScriptBuf, Txid};
 
 use super::balance::TokenBalanceVarInt;
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct CreateVault {
     pub owner_address: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "loanSchemeId"))]
     pub scheme_id: String,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct UpdateVault {
     pub vault_id: Txid,
     pub owner_address: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "loanSchemeId"))]
     pub scheme_id: String,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct DepositToVault {
     pub vault_id: Txid,
     pub from: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "amount"))]
     pub token_amount: TokenBalanceVarInt,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct WithdrawFromVault {
     pub vault_id: Txid,
     pub to: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "amount"))]
     pub token_amount: TokenBalanceVarInt,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct CloseVault {
     pub vault_id: Txid,
     pub to: ScriptBuf,
 }
 
 #[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct PlaceAuctionBid {
     pub vault_id: Txid,
     pub index: u32,
     pub from: ScriptBuf,
+    #[cfg_attr(feature = "serde", serde(rename = "amount"))]
     pub token_amount: TokenBalanceVarInt,
 }
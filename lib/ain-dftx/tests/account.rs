@@ -1,3 +1,4 @@
+use ain_dftx::DfTx;
 use ain_macros::test_dftx_serialization;
 
 #[test]
@@ -15,3 +16,69 @@ fn test_account_to_account() {}
 #[test]
 #[test_dftx_serialization]
 fn test_transfer_domain() {}
+
+#[test_dftx_serialization]
+#[test]
+fn test_future_swap(index: usize, dftx: &DfTx) {
+    let DfTx::SetFutureSwap(msg) = dftx else {
+        panic!("expected SetFutureSwap, got {dftx:?}");
+    };
+    // (source token, source amount, destination, withdraw)
+    let expected = [
+        (2, 100_000_000, 0, false),
+        (4, 100_000_000, 2, false),
+        (0, 4_400_000_000, 1, false),
+        (0, 1_900_000_000, 1, true),
+        (2, 100_000_000, 0, true),
+        (4, 100_000_000, 2, true),
+        (24, 100_000, 0, false),
+    ];
+    assert_eq!(
+        (
+            msg.source.token.0,
+            msg.source.amount,
+            msg.destination,
+            msg.withdraw
+        ),
+        expected[index]
+    );
+}
+
+#[test_dftx_serialization]
+#[test]
+fn test_future_swap_execution(_: usize, dftx: &DfTx) {
+    assert_eq!(dftx, &DfTx::FutureSwapExecution);
+}
+
+#[test_dftx_serialization]
+#[test]
+fn test_future_swap_refund(_: usize, dftx: &DfTx) {
+    assert_eq!(dftx, &DfTx::FutureSwapRefund);
+}
+
+#[test_dftx_serialization]
+#[test]
+fn test_smart_contract(index: usize, dftx: &DfTx) {
+    let DfTx::SmartContract(msg) = dftx else {
+        panic!("expected SmartContract, got {dftx:?}");
+    };
+    assert_eq!(msg.name, "DFIP2201");
+    let balances = msg
+        .accounts
+        .as_ref()
+        .iter()
+        .map(|account| {
+            account
+                .balances
+                .as_ref()
+                .iter()
+                .map(|balance| (balance.token, balance.amount))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let expected: [&[&[(u32, i64)]]; 2] = [
+        &[&[(2, 100_000_000)]],
+        &[&[(2, 2_500_000_000)], &[(0, 1), (2, 3)]],
+    ];
+    assert_eq!(balances, expected[index]);
+}
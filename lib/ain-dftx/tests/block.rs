@@ -1,4 +1,4 @@
-use ain_dftx::{deserialize, Block, DfTx};
+use ain_dftx::{decode_block, deserialize, Block};
 
 #[test]
 fn test_block() {
@@ -9,21 +9,8 @@ fn test_block() {
         let hex = hex::decode(l).unwrap();
         let block = deserialize::<Block>(&hex).unwrap();
 
-        for tx in block.txdata {
-            let bytes = tx.output[0].clone().script_pubkey.into_bytes();
-            if bytes.len() > 2 && bytes[0] == 0x6a && bytes[1] <= 0x4e {
-                let offset = 1 + match bytes[1] {
-                    0x4c => 2,
-                    0x4d => 3,
-                    0x4e => 4,
-                    _ => 1,
-                };
-
-                let raw_tx = &bytes[offset..];
-
-                let tx = deserialize::<DfTx>(raw_tx).unwrap();
-                println!("tx : {:?}", tx);
-            }
+        for (txid, tx) in decode_block(&block, true) {
+            println!("{txid} : {:?}", tx);
         }
     }
 }
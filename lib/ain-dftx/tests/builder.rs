@@ -0,0 +1,182 @@
+use ain_dftx::{
+    builder::{
+        evm_script, AccountToAccountBuilder, BuildError, DepositToVaultBuilder, PoolSwapBuilder,
+        TakeLoanBuilder, TransferDomainBuilder, VoteBuilder, VoteDecision, MAX_MONEY,
+    },
+    DfTx, COIN,
+};
+use bitcoin::{hashes::Hash, Amount, OutPoint, ScriptBuf, Txid};
+
+fn script(byte: u8) -> ScriptBuf {
+    let mut bytes = vec![0x00, 0x14];
+    bytes.extend([byte; 20]);
+    ScriptBuf::from_bytes(bytes)
+}
+
+fn outpoint() -> OutPoint {
+    OutPoint::new(Txid::all_zeros(), 0)
+}
+
+#[test]
+fn test_account_to_account() {
+    let builder = AccountToAccountBuilder::new(script(1))
+        .to(script(2), 0, COIN)
+        .to(script(2), 1, 2 * COIN)
+        .to(script(3), 0, 1)
+        .build()
+        .unwrap();
+    assert_eq!(builder.auth_scripts(), &[script(1)]);
+
+    let tx = builder
+        .input(outpoint(), script(1))
+        .output(script(1), Amount::from_sat(1000))
+        .build()
+        .unwrap();
+    assert_eq!(tx.input.len(), 1);
+    assert_eq!(tx.output.len(), 2);
+    assert_eq!(tx.output[0].value, Amount::ZERO);
+    assert_eq!(tx.output[1].script_pubkey, script(1));
+
+    let Some(DfTx::AccountToAccount(msg)) = DfTx::from_transaction(&tx, true).unwrap() else {
+        panic!("expected AccountToAccount");
+    };
+    assert_eq!(msg.from, script(1));
+    let to = msg.to.as_ref();
+    assert_eq!(to.len(), 2);
+    assert_eq!(to[0].balances.as_ref().len(), 2);
+    assert_eq!(to[1].balances.as_ref()[0].amount, 1);
+}
+
+#[test]
+fn test_invalid_amounts() {
+    for amount in [0, -1, MAX_MONEY + 1] {
+        assert_eq!(
+            AccountToAccountBuilder::new(script(1))
+                .to(script(2), 0, amount)
+                .build()
+                .unwrap_err(),
+            BuildError::InvalidAmount(amount)
+        );
+        assert_eq!(
+            DepositToVaultBuilder::new(Txid::all_zeros(), script(1), 0, amount)
+                .build()
+                .unwrap_err(),
+            BuildError::InvalidAmount(amount)
+        );
+    }
+    assert_eq!(
+        AccountToAccountBuilder::new(script(1)).build().unwrap_err(),
+        BuildError::EmptyTransfer
+    );
+    assert_eq!(
+        TakeLoanBuilder::new(Txid::all_zeros()).build().unwrap_err(),
+        BuildError::EmptyTransfer
+    );
+}
+
+#[test]
+fn test_no_inputs() {
+    let builder = DepositToVaultBuilder::new(Txid::all_zeros(), script(1), 0, COIN)
+        .build()
+        .unwrap();
+    assert_eq!(builder.build().unwrap_err(), BuildError::NoInputs);
+}
+
+#[test]
+fn test_missing_auth() {
+    let builder = AccountToAccountBuilder::new(script(1))
+        .to(script(2), 0, COIN)
+        .build()
+        .unwrap()
+        .input(outpoint(), script(2));
+    assert_eq!(
+        builder.build().unwrap_err(),
+        BuildError::MissingAuth(script(1))
+    );
+}
+
+#[test]
+fn test_pool_swap() {
+    let tx = PoolSwapBuilder::new(script(1), 0, COIN, script(1), 2)
+        .build()
+        .unwrap()
+        .input(outpoint(), script(1))
+        .build()
+        .unwrap();
+    let Some(DfTx::PoolSwap(swap)) = DfTx::from_transaction(&tx, true).unwrap() else {
+        panic!("expected PoolSwap");
+    };
+    assert_eq!(swap.from_token_id.0, 0);
+    assert_eq!(swap.to_token_id.0, 2);
+    assert_eq!(
+        swap.max_price.integer * COIN + swap.max_price.fraction,
+        MAX_MONEY
+    );
+
+    let tx = PoolSwapBuilder::new(script(1), 0, COIN, script(1), 2)
+        .max_price(150_000_000)
+        .pools(vec![4, 5])
+        .build()
+        .unwrap()
+        .input(outpoint(), script(1))
+        .build()
+        .unwrap();
+    let Some(DfTx::CompositeSwap(swap)) = DfTx::from_transaction(&tx, true).unwrap() else {
+        panic!("expected CompositeSwap");
+    };
+    assert_eq!(swap.pool_swap.max_price.integer, 1);
+    assert_eq!(swap.pool_swap.max_price.fraction, 50_000_000);
+    assert_eq!(swap.pools.as_ref().len(), 2);
+}
+
+#[test]
+fn test_transfer_domain() {
+    let evm = evm_script([0xab; 20]);
+    let builder = TransferDomainBuilder::new()
+        .dvm_to_evm(script(1), evm.clone(), 0, COIN, vec![0xf8])
+        .evm_to_dvm(evm.clone(), script(2), 0, COIN, vec![0xf9])
+        .build()
+        .unwrap();
+    // EVM sources are authorized by their EVM tx
+    assert_eq!(builder.auth_scripts(), &[script(1)]);
+
+    let tx = builder.input(outpoint(), script(1)).build().unwrap();
+    let Some(DfTx::TransferDomain(msg)) = DfTx::from_transaction(&tx, true).unwrap() else {
+        panic!("expected TransferDomain");
+    };
+    let items = msg.items.as_ref();
+    assert_eq!(items[0].src.domain, 2);
+    assert!(items[0].src.data.is_empty());
+    assert_eq!(items[0].dst.domain, 3);
+    assert_eq!(items[0].dst.data, vec![0xf8]);
+    assert_eq!(items[1].src.address, evm);
+    assert_eq!(items[1].src.data, vec![0xf9]);
+}
+
+#[test]
+fn test_take_loan_and_vote() {
+    let tx = TakeLoanBuilder::new(Txid::all_zeros())
+        .amount(5, COIN)
+        .build()
+        .unwrap()
+        .auth(script(1))
+        .input(outpoint(), script(1))
+        .build()
+        .unwrap();
+    assert!(matches!(
+        DfTx::from_transaction(&tx, true).unwrap(),
+        Some(DfTx::TakeLoan(_))
+    ));
+
+    let tx = VoteBuilder::new(Txid::all_zeros(), Txid::all_zeros(), VoteDecision::Neutral)
+        .build()
+        .unwrap()
+        .auth(script(4))
+        .input(outpoint(), script(4))
+        .build()
+        .unwrap();
+    let Some(DfTx::Vote(vote)) = DfTx::from_transaction(&tx, true).unwrap() else {
+        panic!("expected Vote");
+    };
+    assert_eq!(vote.vote_decision, 3);
+}
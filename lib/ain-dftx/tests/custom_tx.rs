@@ -0,0 +1,14 @@
+use ain_dftx::DfTx;
+use ain_macros::test_dftx_serialization;
+
+#[test_dftx_serialization]
+#[test]
+fn test_auto_auth_prep(_: usize, dftx: &DfTx) {
+    assert_eq!(dftx, &DfTx::AutoAuthPrep);
+}
+
+#[test_dftx_serialization]
+#[test]
+fn test_reject(_: usize, dftx: &DfTx) {
+    assert_eq!(dftx, &DfTx::Reject);
+}
@@ -0,0 +1,110 @@
+use ain_dftx::{deserialize, serialize, ConsensusEncoding};
+use bitcoin::consensus::encode::Error;
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+struct Message {
+    #[consensus(varint)]
+    id: u32,
+    #[consensus(skip)]
+    cached: u64,
+    #[consensus(with = "inverted")]
+    flag: u8,
+    #[consensus(optional)]
+    note: Option<String>,
+}
+
+mod inverted {
+    use bitcoin::{
+        consensus::{encode::Error, Decodable, Encodable},
+        io,
+    };
+
+    pub fn consensus_encode<W: io::Write + ?Sized>(
+        v: &u8,
+        writer: &mut W,
+    ) -> Result<usize, io::Error> {
+        (!v).consensus_encode(writer)
+    }
+
+    pub fn consensus_decode<R: io::Read + ?Sized>(reader: &mut R) -> Result<u8, Error> {
+        Ok(!u8::consensus_decode(reader)?)
+    }
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Tagged {
+    A(u16) = b'a',
+    B {
+        x: u8,
+        #[consensus(varint)]
+        y: u64,
+    },
+    #[consensus(other)]
+    Unknown = 0,
+}
+
+#[derive(ConsensusEncoding, Debug, PartialEq, Eq)]
+#[repr(i32)]
+enum Indexed {
+    Zero,
+    One(u8),
+}
+
+#[test]
+fn test_struct_fields() {
+    let msg = Message {
+        id: 200,
+        cached: 5,
+        flag: 0x0f,
+        note: Some("hi".to_string()),
+    };
+    let bytes = serialize(&msg);
+    assert_eq!(hex::encode(&bytes), "8048f0026869");
+    assert_eq!(
+        deserialize::<Message>(&bytes).unwrap(),
+        Message { cached: 0, ..msg }
+    );
+
+    // Trailing optional fields may be left out
+    assert_eq!(
+        deserialize::<Message>(&hex::decode("8048f0").unwrap()).unwrap(),
+        Message {
+            id: 200,
+            cached: 0,
+            flag: 0x0f,
+            note: None,
+        }
+    );
+    // VARINT larger than the field
+    assert!(matches!(
+        deserialize::<Message>(&hex::decode("8f8080808000f0").unwrap()),
+        Err(Error::ParseFailed(_))
+    ));
+}
+
+#[test]
+fn test_enum_discriminants() {
+    for (value, hex) in [
+        (Tagged::A(1), "610100"),
+        (Tagged::B { x: 2, y: 128 }, "62028000"),
+        (Tagged::Unknown, "00"),
+    ] {
+        assert_eq!(hex::encode(serialize(&value)), hex);
+        assert_eq!(
+            deserialize::<Tagged>(&hex::decode(hex).unwrap()).unwrap(),
+            value
+        );
+    }
+    assert_eq!(deserialize::<Tagged>(&[0xff]).unwrap(), Tagged::Unknown);
+
+    assert_eq!(hex::encode(serialize(&Indexed::One(7))), "0100000007");
+    assert_eq!(
+        deserialize::<Indexed>(&hex::decode("00000000").unwrap()).unwrap(),
+        Indexed::Zero
+    );
+    assert!(matches!(
+        deserialize::<Indexed>(&hex::decode("02000000").unwrap()),
+        Err(Error::ParseFailed("Unknown Indexed variant"))
+    ));
+}
@@ -1,3 +1,15 @@
+use ain_dftx::{
+    attributes::{
+        attribute_type, evm_id, evm_key, Attribute, AttributeType, AttributeValue, Attributes,
+        DataStructureV0,
+    },
+    deserialize,
+    governance::{
+        GovernanceVar, LoanLiquidationPenalty, OracleBlockInterval, OracleDeviation, SetGovernance,
+        SetGovernanceHeight,
+    },
+    serialize, DfTx, COIN,
+};
 use ain_macros::test_dftx_serialization;
 
 #[test]
@@ -19,3 +31,171 @@ fn test_create_voc() {}
 #[test]
 #[test_dftx_serialization]
 fn test_vote() {}
+
+#[test_dftx_serialization]
+#[test]
+fn test_unset_gov_variable(index: usize, dftx: &DfTx) {
+    let DfTx::UnsetGovernance(msg) = dftx else {
+        panic!("expected UnsetGovernance, got {dftx:?}");
+    };
+    let vars = msg
+        .governance_vars
+        .as_ref()
+        .iter()
+        .map(|var| (var.key.as_str(), var.values.as_ref().len()))
+        .collect::<Vec<_>>();
+    let expected: [&[(&str, usize)]; 2] = [
+        &[("ATTRIBUTES", 1)],
+        &[("ATTRIBUTES", 2), ("LP_DAILY_DFI_REWARD", 0)],
+    ];
+    assert_eq!(vars, expected[index]);
+}
+
+#[test_dftx_serialization]
+#[test]
+fn test_proposal_fee_redistribution(_: usize, dftx: &DfTx) {
+    assert_eq!(dftx, &DfTx::ProposalFeeRedistribution);
+}
+
+fn decode_attributes(hex: &str) -> Attributes {
+    let Ok(DfTx::SetGovernance(mut gov)) = deserialize(&hex::decode(hex).unwrap()) else {
+        panic!("expected SetGovernance");
+    };
+    match gov.governance_vars.pop() {
+        Some(GovernanceVar::Attributes(attrs)) => attrs,
+        var => panic!("expected ATTRIBUTES, got {var:?}"),
+    }
+}
+
+#[test]
+fn test_attributes() {
+    let attrs = decode_attributes(
+        "44665478470a4154545249425554455301000000007403000000650100000080841e0000000000",
+    );
+    let attr = &attrs.value.as_ref()[0];
+    assert_eq!(attr.key.to_string(), "v0/token/3/dex_in_fee_pct");
+    assert_eq!(attr.value, AttributeValue::Amount(COIN / 50));
+
+    let attrs =
+        decode_attributes("44665478470a4154545249425554455301000000004c63000000060000000000");
+    let attr = &attrs.value.as_ref()[0];
+    assert_eq!(attr.key.to_string(), "v0/locks/token/6");
+    assert_eq!(attr.value, AttributeValue::Bool(false));
+
+    let attrs = decode_attributes(
+        "44665478470a415454524942555445530100000000740000000068040000000344464903555344",
+    );
+    let attr = &attrs.value.as_ref()[0];
+    assert_eq!(attr.key.to_string(), "v0/token/0/fixed_interval_price_id");
+    let AttributeValue::CurrencyPair(pair) = &attr.value else {
+        panic!("expected currency pair");
+    };
+    assert_eq!(
+        (pair.token.as_str(), pair.currency.as_str()),
+        ("DFI", "USD")
+    );
+}
+
+#[test]
+fn test_attribute_keys() {
+    for key in [
+        "v0/token/5/loan_payback/1",
+        "v0/token/5/dfip2203",
+        "v0/poolpairs/17/token_a_fee_direction",
+        "v0/params/dfip2203/start_block",
+        "v0/params/dtoken_restart/1000",
+        "v0/params/feature/evm",
+        "v0/live/economy/dex",
+        "v0/locks/token/6",
+        "v0/oracles/splits/1000",
+        "v0/oracles/splits/fractional_enabled",
+        "v0/gov/proposals/fee_redistribution",
+        "v0/transferdomain/dvm-evm/src-formats",
+        "v0/evm/block/gas_limit",
+        "v0/vaults/dusd-vault/enabled",
+        "v0/rules/tx/core_op_return_max_size_bytes",
+        "v1",
+    ] {
+        let parsed = key.parse::<AttributeType>().unwrap();
+        assert_eq!(parsed.to_string(), key);
+    }
+
+    let key = "v0/token/5/loan_payback/1"
+        .parse::<DataStructureV0>()
+        .unwrap();
+    assert_eq!((key.type_id, key.key_id), (5, 1));
+    assert_eq!(
+        hex::encode(serialize(&AttributeType::V0(key))),
+        "0000000074050000006301000000"
+    );
+
+    for key in [
+        "v0/token/5/loan_payback",
+        "v0/token/5/dex_in_fee_pct/1",
+        "v0/evm/block/unknown",
+        "v2/token/5/dfip2203",
+    ] {
+        assert!(key.parse::<AttributeType>().is_err(), "{key}");
+    }
+}
+
+#[test]
+fn test_evm_block_gas_limit() {
+    let key = DataStructureV0::new(attribute_type::EVM, evm_id::BLOCK, evm_key::GAS_LIMIT);
+    let gov = DfTx::SetGovernance(SetGovernance {
+        governance_vars: vec![GovernanceVar::Attributes(Attributes {
+            key: "ATTRIBUTES".to_string(),
+            value: vec![Attribute {
+                key: AttributeType::V0(key),
+                value: AttributeValue::U64(30_000_000),
+            }]
+            .into(),
+        })],
+    });
+    let raw = serialize(&gov);
+    assert_eq!(
+        hex::encode(&raw[16..]),
+        "01000000006561000000621100000080c3c90100000000"
+    );
+    let Ok(DfTx::SetGovernance(decoded)) = deserialize(&raw) else {
+        panic!("expected SetGovernance");
+    };
+    let GovernanceVar::Attributes(attrs) = &decoded.governance_vars[0] else {
+        panic!("expected ATTRIBUTES");
+    };
+    assert_eq!(attrs.evm_block_gas_limit(), Some(30_000_000));
+    assert_eq!(DfTx::SetGovernance(decoded), gov);
+}
+
+#[test]
+fn test_governance_var_round_trip() {
+    let gov = DfTx::SetGovernance(SetGovernance {
+        governance_vars: vec![
+            GovernanceVar::OracleBlockInterval(OracleBlockInterval {
+                key: "ORACLE_BLOCK_INTERVAL".to_string(),
+                value: 120,
+            }),
+            GovernanceVar::OracleDeviation(OracleDeviation {
+                key: "ORACLE_DEVIATION".to_string(),
+                value: COIN / 2,
+            }),
+            GovernanceVar::LoanLiquidationPenalty(LoanLiquidationPenalty {
+                key: "LOAN_LIQUIDATION_PENALTY".to_string(),
+                value: COIN / 20,
+            }),
+        ],
+    });
+    let raw = serialize(&gov);
+    assert_eq!(deserialize::<DfTx>(&raw).unwrap(), gov);
+
+    let gov = DfTx::SetGovernanceHeight(SetGovernanceHeight {
+        var: GovernanceVar::OracleBlockInterval(OracleBlockInterval {
+            key: "ORACLE_BLOCK_INTERVAL".to_string(),
+            value: 120,
+        }),
+        activation_height: 1_000_000,
+    });
+    let raw = serialize(&gov);
+    assert_eq!(hex::encode(&raw[raw.len() - 8..]), "7800000040420f00");
+    assert_eq!(deserialize::<DfTx>(&raw).unwrap(), gov);
+}
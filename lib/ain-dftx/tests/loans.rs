@@ -1,4 +1,8 @@
+use std::str::FromStr;
+
+use ain_dftx::DfTx;
 use ain_macros::test_dftx_serialization;
+use bitcoin::Txid;
 
 #[test]
 #[test_dftx_serialization]
@@ -35,3 +39,16 @@ fn test_payback_loan() {}
 #[test]
 #[test_dftx_serialization]
 fn test_payback_loan_v2() {}
+
+#[test_dftx_serialization]
+#[test]
+fn test_payback_with_collateral(index: usize, dftx: &DfTx) {
+    let DfTx::PaybackWithCollateral(msg) = dftx else {
+        panic!("expected PaybackWithCollateral, got {dftx:?}");
+    };
+    let expected = [
+        "3fe27f9a8e9dc4a6d6e3e26b107b41786e1cf34688f34d9e7e5a26c376be2351",
+        "f95268403485ab72bccd04800788cf61bfad9fb7069b894c6f741add5bd49991",
+    ];
+    assert_eq!(msg.vault_id, Txid::from_str(expected[index]).unwrap());
+}
@@ -0,0 +1,933 @@
+//! Property based round trips for every custom tx: `deserialize(serialize(x)) == x`.
+
+use std::fmt::Debug;
+
+use ain_dftx::{
+    account::*,
+    attributes::*,
+    balance::*,
+    common::{CompactVec, Maybe, RawBytes, VarInt},
+    deserialize,
+    evmtx::*,
+    governance::*,
+    icxorderbook::*,
+    loans::*,
+    masternode::*,
+    oracles::*,
+    pool::*,
+    price::*,
+    serialize,
+    token::*,
+    vault::*,
+    DfTx, Stack,
+};
+use bitcoin::{
+    consensus::deserialize_partial,
+    hashes::{sha256d, Hash},
+    PubkeyHash, ScriptBuf, Txid,
+};
+use proptest::{collection::vec, option, prelude::*, strategy::LazyJust};
+
+fn txid() -> impl Strategy<Value = Txid> {
+    any::<[u8; 32]>().prop_map(Txid::from_byte_array)
+}
+
+fn script() -> impl Strategy<Value = ScriptBuf> {
+    vec(any::<u8>(), 0..40).prop_map(ScriptBuf::from_bytes)
+}
+
+fn text() -> impl Strategy<Value = String> {
+    "\\PC{0,16}"
+}
+
+fn compact<T: Debug>(item: impl Strategy<Value = T>) -> impl Strategy<Value = CompactVec<T>> {
+    vec(item, 0..4).prop_map(CompactVec::from)
+}
+
+fn compact_bytes() -> impl Strategy<Value = CompactVec<u8>> {
+    vec(any::<u8>(), 0..200).prop_map(CompactVec::from)
+}
+
+fn var_int() -> impl Strategy<Value = VarInt> {
+    any::<u64>().prop_map(VarInt)
+}
+
+fn compact_size() -> impl Strategy<Value = bitcoin::VarInt> {
+    any::<u64>().prop_map(bitcoin::VarInt)
+}
+
+fn token_balance() -> impl Strategy<Value = TokenBalanceUInt32> {
+    (any::<u32>(), any::<i64>()).prop_map(|(token, amount)| TokenBalanceUInt32 { token, amount })
+}
+
+fn token_balance_var_int() -> impl Strategy<Value = TokenBalanceVarInt> {
+    (compact_size(), any::<i64>()).prop_map(|(token, amount)| TokenBalanceVarInt { token, amount })
+}
+
+fn balances() -> impl Strategy<Value = CompactVec<TokenBalanceUInt32>> {
+    compact(token_balance())
+}
+
+fn script_balances() -> impl Strategy<Value = CompactVec<ScriptBalances>> {
+    compact(
+        (script(), balances()).prop_map(|(script, balances)| ScriptBalances { script, balances }),
+    )
+}
+
+fn currency_pair() -> impl Strategy<Value = CurrencyPair> {
+    (text(), text()).prop_map(|(token, currency)| CurrencyPair { token, currency })
+}
+
+// account
+
+fn utxos_to_account() -> impl Strategy<Value = UtxosToAccount> {
+    script_balances().prop_map(|to| UtxosToAccount { to })
+}
+
+fn account_to_utxos() -> impl Strategy<Value = AccountToUtxos> {
+    (script(), balances(), compact_size()).prop_map(|(from, balances, minting_outputs_start)| {
+        AccountToUtxos {
+            from,
+            balances,
+            minting_outputs_start,
+        }
+    })
+}
+
+fn account_to_account() -> impl Strategy<Value = AccountToAccount> {
+    (script(), script_balances()).prop_map(|(from, to)| AccountToAccount { from, to })
+}
+
+fn any_accounts_to_accounts() -> impl Strategy<Value = AnyAccountsToAccounts> {
+    (script_balances(), script_balances()).prop_map(|(from, to)| AnyAccountsToAccounts { from, to })
+}
+
+fn transfer_domain_item() -> impl Strategy<Value = TransferDomainItem> {
+    (
+        script(),
+        token_balance_var_int(),
+        any::<u8>(),
+        vec(any::<u8>(), 0..600),
+    )
+        .prop_map(|(address, amount, domain, data)| TransferDomainItem {
+            address,
+            amount,
+            domain,
+            data,
+        })
+}
+
+fn transfer_domain() -> impl Strategy<Value = TransferDomain> {
+    compact(
+        (transfer_domain_item(), transfer_domain_item())
+            .prop_map(|(src, dst)| TransferDomainPair { src, dst }),
+    )
+    .prop_map(|items| TransferDomain { items })
+}
+
+fn set_future_swap() -> impl Strategy<Value = SetFutureSwap> {
+    (
+        script(),
+        token_balance_var_int(),
+        any::<u32>(),
+        any::<bool>(),
+    )
+        .prop_map(|(owner, source, destination, withdraw)| SetFutureSwap {
+            owner,
+            source,
+            destination,
+            withdraw,
+        })
+}
+
+fn smart_contract() -> impl Strategy<Value = SmartContract> {
+    (text(), script_balances()).prop_map(|(name, accounts)| SmartContract { name, accounts })
+}
+
+// evm
+
+fn evm_tx() -> impl Strategy<Value = EvmTx> {
+    vec(any::<u8>(), 0..1024).prop_map(|raw| EvmTx { raw })
+}
+
+// icxorderbook
+
+fn icx_create_order() -> impl Strategy<Value = ICXCreateOrder> {
+    (
+        any::<u8>(),
+        var_int(),
+        script(),
+        // Only the trailing field of a message may be absent
+        compact_bytes().prop_map(Some),
+        any::<i64>(),
+        any::<i64>(),
+        any::<i64>(),
+        any::<u32>(),
+    )
+        .prop_map(
+            |(
+                order_type,
+                token_id,
+                owner_address,
+                receive_pubkey,
+                amount_from,
+                amount_to_fill,
+                order_price,
+                expiry,
+            )| ICXCreateOrder {
+                order_type,
+                token_id,
+                owner_address,
+                receive_pubkey: Maybe(receive_pubkey),
+                amount_from,
+                amount_to_fill,
+                order_price,
+                expiry,
+            },
+        )
+}
+
+fn icx_make_offer() -> impl Strategy<Value = ICXMakeOffer> {
+    (
+        txid(),
+        any::<i64>(),
+        script(),
+        compact_bytes().prop_map(Some),
+        any::<u32>(),
+        any::<u64>(),
+    )
+        .prop_map(
+            |(order_tx, amount, owner_address, receive_pubkey, expiry, taker_fee)| ICXMakeOffer {
+                order_tx,
+                amount,
+                owner_address,
+                receive_pubkey: Maybe(receive_pubkey),
+                expiry,
+                taker_fee,
+            },
+        )
+}
+
+fn icx_submit_dfc_htlc() -> impl Strategy<Value = ICXSubmitDFCHTLC> {
+    (txid(), any::<i64>(), txid(), any::<u32>()).prop_map(|(offer_tx, amount, hash, timeout)| {
+        ICXSubmitDFCHTLC {
+            offer_tx,
+            amount,
+            hash,
+            timeout,
+        }
+    })
+}
+
+fn icx_submit_ext_htlc() -> impl Strategy<Value = ICXSubmitEXTHTLC> {
+    (
+        txid(),
+        any::<i64>(),
+        txid(),
+        text(),
+        compact_bytes(),
+        any::<u32>(),
+    )
+        .prop_map(
+            |(offer_tx, amount, hash, htlc_script_address, owner_pubkey, timeout)| {
+                ICXSubmitEXTHTLC {
+                    offer_tx,
+                    amount,
+                    hash,
+                    htlc_script_address,
+                    owner_pubkey,
+                    timeout,
+                }
+            },
+        )
+}
+
+fn icx_claim_dfc_htlc() -> impl Strategy<Value = ICXClaimDFCHTLC> {
+    (txid(), vec(any::<u8>(), 0..64))
+        .prop_map(|(dfc_htlc_tx, seed)| ICXClaimDFCHTLC { dfc_htlc_tx, seed })
+}
+
+// loans
+
+fn set_loan_scheme() -> impl Strategy<Value = SetLoanScheme> {
+    (any::<u32>(), any::<i64>(), text(), any::<i64>()).prop_map(
+        |(ratio, rate, identifier, update)| SetLoanScheme {
+            ratio,
+            rate,
+            identifier,
+            update,
+        },
+    )
+}
+
+fn set_collateral_token() -> impl Strategy<Value = SetCollateralToken> {
+    (compact_size(), any::<i64>(), currency_pair(), any::<u32>()).prop_map(
+        |(token, factor, currency_pair, activate_after_block)| SetCollateralToken {
+            token,
+            factor,
+            currency_pair,
+            activate_after_block,
+        },
+    )
+}
+
+fn set_loan_token() -> impl Strategy<Value = SetLoanToken> {
+    (text(), text(), currency_pair(), any::<bool>(), any::<i64>()).prop_map(
+        |(symbol, name, currency_pair, mintable, interest)| SetLoanToken {
+            symbol,
+            name,
+            currency_pair,
+            mintable,
+            interest,
+        },
+    )
+}
+
+fn update_loan_token() -> impl Strategy<Value = UpdateLoanToken> {
+    (set_loan_token(), txid()).prop_map(|(token, token_tx)| UpdateLoanToken {
+        symbol: token.symbol,
+        name: token.name,
+        currency_pair: token.currency_pair,
+        mintable: token.mintable,
+        interest: token.interest,
+        token_tx,
+    })
+}
+
+fn payback_loan_v2() -> impl Strategy<Value = PaybackLoanV2> {
+    (
+        txid(),
+        script(),
+        compact(
+            (compact_size(), balances())
+                .prop_map(|(d_token, amounts)| ain_dftx::loans::TokenPayback { d_token, amounts }),
+        ),
+    )
+        .prop_map(|(vault_id, from, loans)| PaybackLoanV2 {
+            vault_id,
+            from,
+            loans,
+        })
+}
+
+// masternode
+
+fn create_masternode() -> impl Strategy<Value = CreateMasternode> {
+    (any::<u8>(), any::<[u8; 20]>(), option::of(any::<u16>())).prop_map(
+        |(operator_type, hash, timelock)| CreateMasternode {
+            operator_type,
+            operator_pub_key_hash: PubkeyHash::from_byte_array(hash),
+            timelock,
+        },
+    )
+}
+
+fn update_masternode() -> impl Strategy<Value = UpdateMasternode> {
+    let update = (
+        any::<u8>(),
+        any::<u8>(),
+        option::of(any::<[u8; 20]>().prop_map(PubkeyHash::from_byte_array)),
+    )
+        .prop_map(
+            |(r#type, address_type, address_pub_key_hash)| UpdateMasternodeData {
+                r#type,
+                address: UpdateMasternodeAddress {
+                    r#type: address_type,
+                    address_pub_key_hash,
+                },
+            },
+        );
+    (txid(), compact(update)).prop_map(|(node_id, updates)| UpdateMasternode { node_id, updates })
+}
+
+// oracles
+
+fn set_oracle_data() -> impl Strategy<Value = SetOracleData> {
+    let amount =
+        (text(), any::<i64>()).prop_map(|(currency, amount)| TokenAmount { currency, amount });
+    let price = (text(), compact(amount)).prop_map(|(token, prices)| TokenPrice { token, prices });
+    (txid(), any::<i64>(), compact(price)).prop_map(|(oracle_id, timestamp, token_prices)| {
+        SetOracleData {
+            oracle_id,
+            timestamp,
+            token_prices,
+        }
+    })
+}
+
+fn appoint_oracle() -> impl Strategy<Value = AppointOracle> {
+    (script(), any::<u8>(), compact(currency_pair())).prop_map(
+        |(script, weightage, price_feeds)| AppointOracle {
+            script,
+            weightage,
+            price_feeds,
+        },
+    )
+}
+
+fn update_oracle() -> impl Strategy<Value = UpdateOracle> {
+    (txid(), appoint_oracle()).prop_map(|(oracle_id, oracle)| UpdateOracle {
+        oracle_id,
+        script: oracle.script,
+        weightage: oracle.weightage,
+        price_feeds: oracle.price_feeds,
+    })
+}
+
+// pool
+
+fn pool_swap() -> impl Strategy<Value = PoolSwap> {
+    (
+        script(),
+        var_int(),
+        any::<i64>(),
+        script(),
+        var_int(),
+        any::<i64>(),
+        any::<i64>(),
+    )
+        .prop_map(
+            |(
+                from_script,
+                from_token_id,
+                from_amount,
+                to_script,
+                to_token_id,
+                integer,
+                fraction,
+            )| {
+                PoolSwap {
+                    from_script,
+                    from_token_id,
+                    from_amount,
+                    to_script,
+                    to_token_id,
+                    max_price: MaxPrice { integer, fraction },
+                }
+            },
+        )
+}
+
+fn composite_swap() -> impl Strategy<Value = CompositeSwap> {
+    (pool_swap(), compact(var_int().prop_map(|id| PoolId { id })))
+        .prop_map(|(pool_swap, pools)| CompositeSwap { pool_swap, pools })
+}
+
+fn pool_create_pair() -> impl Strategy<Value = PoolCreatePair> {
+    (
+        var_int(),
+        var_int(),
+        any::<i64>(),
+        script(),
+        any::<u8>(),
+        text(),
+        option::of(balances()),
+    )
+        .prop_map(
+            |(token_a, token_b, commission, owner_address, status, pair_symbol, custom_rewards)| {
+                PoolCreatePair {
+                    token_a,
+                    token_b,
+                    commission,
+                    owner_address,
+                    status,
+                    pair_symbol,
+                    custom_rewards,
+                }
+            },
+        )
+}
+
+fn pool_update_pair() -> impl Strategy<Value = PoolUpdatePair> {
+    (
+        any::<u32>(),
+        any::<u8>(),
+        any::<i64>(),
+        script(),
+        option::of(balances()),
+    )
+        .prop_map(
+            |(pool_id, status, commission, owner_address, custom_rewards)| PoolUpdatePair {
+                pool_id,
+                status,
+                commission,
+                owner_address,
+                custom_rewards,
+            },
+        )
+}
+
+// token
+
+fn create_token() -> impl Strategy<Value = CreateToken> {
+    (text(), text(), any::<u8>(), any::<i64>(), any::<u8>()).prop_map(
+        |(symbol, name, decimal, limit, flags)| CreateToken {
+            symbol,
+            name,
+            decimal,
+            limit,
+            flags,
+        },
+    )
+}
+
+fn burn_token() -> impl Strategy<Value = BurnToken> {
+    (balances(), script(), any::<u8>(), any::<u32>(), script()).prop_map(
+        |(amounts, from, burn_type, r#type, context)| BurnToken {
+            amounts,
+            from,
+            burn_type,
+            variant_context: VariantScript { r#type, context },
+        },
+    )
+}
+
+// governance
+
+fn data_structure_v0() -> impl Strategy<Value = DataStructureV0> {
+    let key = prop_oneof![
+        Just(token_key::LOAN_PAYBACK),
+        Just(token_key::LOAN_PAYBACK_FEE_PCT),
+        any::<u32>(),
+    ];
+    (
+        prop_oneof![Just(attribute_type::TOKEN), any::<u8>()],
+        any::<u32>(),
+        key,
+        any::<u32>(),
+    )
+        .prop_map(|(r#type, type_id, key, key_id)| {
+            let mut key = DataStructureV0::new(r#type, type_id, key);
+            if key.is_extended_size() {
+                key.key_id = key_id;
+            }
+            key
+        })
+}
+
+fn attribute_value() -> impl Strategy<Value = AttributeValue> {
+    let hash = || any::<[u8; 32]>().prop_map(sha256d::Hash::from_byte_array);
+    let dex_token_info = || {
+        (any::<u64>(), any::<u64>(), any::<u64>()).prop_map(|(swaps, feeburn, commissions)| {
+            DexTokenInfo {
+                swaps,
+                feeburn,
+                commissions,
+            }
+        })
+    };
+    prop_oneof![
+        any::<bool>().prop_map(AttributeValue::Bool),
+        any::<i64>().prop_map(AttributeValue::Amount),
+        balances().prop_map(AttributeValue::Balances),
+        (balances(), balances()).prop_map(|(tokens_fee, tokens_payback)| {
+            AttributeValue::TokenPayback(ain_dftx::attributes::TokenPayback {
+                tokens_fee,
+                tokens_payback,
+            })
+        }),
+        currency_pair().prop_map(AttributeValue::CurrencyPair),
+        compact(
+            (any::<u32>(), any::<i32>()).prop_map(|(token_id, multiplier)| {
+                OracleSplit {
+                    token_id,
+                    multiplier,
+                }
+            })
+        )
+        .prop_map(AttributeValue::OracleSplits),
+        (any::<u32>(), any::<i32>()).prop_map(|(token_id, height)| {
+            AttributeValue::Descendant(Descendant { token_id, height })
+        }),
+        (any::<u32>(), text()).prop_map(|(token_id, r#type)| {
+            AttributeValue::Ascendant(Ascendant { token_id, r#type })
+        }),
+        any::<u8>().prop_map(AttributeValue::FeeDir),
+        compact((var_int(), dex_token_info(), dex_token_info()).prop_map(
+            |(pool_id, total_token_a, total_token_b)| DexBalance {
+                pool_id,
+                total_token_a,
+                total_token_b,
+            }
+        ))
+        .prop_map(AttributeValue::DexBalances),
+        compact(script()).prop_map(AttributeValue::Scripts),
+        compact(text()).prop_map(AttributeValue::Strings),
+        compact((text(), text()).prop_map(|(key, value)| LegacyStringEntry { key, value }))
+            .prop_map(AttributeValue::LegacyEntry1),
+        compact((text(), any::<u64>()).prop_map(|(key, value)| LegacyU64Entry { key, value }))
+            .prop_map(AttributeValue::LegacyEntry2),
+        compact((text(), any::<i64>()).prop_map(|(key, value)| LegacyI64Entry { key, value }))
+            .prop_map(AttributeValue::LegacyEntry3),
+        any::<i32>().prop_map(AttributeValue::I32),
+        any::<u32>().prop_map(AttributeValue::U32),
+        any::<u64>().prop_map(AttributeValue::U64),
+        compact_bytes().prop_map(AttributeValue::AddressFormats),
+        vec(balances(), 8).prop_map(|mut totals| {
+            let mut next = || totals.remove(0);
+            AttributeValue::TransferDomainStats(TransferDomainStatsLive {
+                dvm_evm_total: next(),
+                evm_dvm_total: next(),
+                dvm_in: next(),
+                evm_in: next(),
+                dvm_out: next(),
+                evm_out: next(),
+                dvm_current: next(),
+                evm_current: next(),
+            })
+        }),
+        (vec(any::<i64>(), 6), vec(hash(), 4)).prop_map(|(fees, hashes)| {
+            AttributeValue::EvmBlockStats(EvmBlockStatsLive {
+                fee_burnt: fees[0],
+                fee_burnt_min: fees[1],
+                fee_burnt_min_hash: hashes[0],
+                fee_burnt_max: fees[2],
+                fee_burnt_max_hash: hashes[1],
+                fee_priority: fees[3],
+                fee_priority_min: fees[4],
+                fee_priority_min_hash: hashes[2],
+                fee_priority_max: fees[5],
+                fee_priority_max_hash: hashes[3],
+            })
+        }),
+        compact(
+            (any::<u32>(), any::<i64>()).prop_map(|(token_id, multiplier)| {
+                OracleSplit64 {
+                    token_id,
+                    multiplier,
+                }
+            })
+        )
+        .prop_map(AttributeValue::OracleSplits64),
+    ]
+}
+
+fn attribute() -> impl Strategy<Value = Attribute> {
+    let key = prop_oneof![
+        data_structure_v0().prop_map(AttributeType::V0),
+        Just(AttributeType::V1),
+    ];
+    (key, attribute_value()).prop_map(|(key, value)| Attribute { key, value })
+}
+
+/// Governance variables with a typed value, which may appear anywhere in a message.
+fn governance_var() -> impl Strategy<Value = GovernanceVar> {
+    let key = |key: &str| key.to_string();
+    prop_oneof![
+        any::<i64>().prop_map(move |value| GovernanceVar::LpDailyReward(LpDailyReward {
+            key: key("LP_DAILY_DFI_REWARD"),
+            value,
+        })),
+        compact(
+            (any::<u32>(), any::<i64>())
+                .prop_map(|(token_id, value)| { LiqPoolSplit { token_id, value } })
+        )
+        .prop_map(move |value| GovernanceVar::LpSplits(LpSplits {
+            key: key("LP_SPLITS"),
+            value,
+        })),
+        compact(
+            (compact_size(), any::<i64>())
+                .prop_map(|(token_id, value)| { LoanTokenSplit { token_id, value } })
+        )
+        .prop_map(
+            move |value| GovernanceVar::LpLoanTokenSplits(LpLoanTokenSplits {
+                key: key("LP_LOAN_TOKEN_SPLITS"),
+                value,
+            })
+        ),
+        any::<i64>().prop_map(move |value| GovernanceVar::LpDailyLoanTokenReward(
+            LpDailyLoanTokenReward {
+                key: key("LP_DAILY_LOAN_TOKEN_REWARD"),
+                value,
+            }
+        )),
+        any::<i64>().prop_map(move |value| GovernanceVar::LoanLiquidationPenalty(
+            LoanLiquidationPenalty {
+                key: key("LOAN_LIQUIDATION_PENALTY"),
+                value,
+            }
+        )),
+        any::<u32>().prop_map(move |value| GovernanceVar::OracleBlockInterval(
+            OracleBlockInterval {
+                key: key("ORACLE_BLOCK_INTERVAL"),
+                value,
+            }
+        )),
+        any::<i64>().prop_map(
+            move |value| GovernanceVar::OracleDeviation(OracleDeviation {
+                key: key("ORACLE_DEVIATION"),
+                value,
+            })
+        ),
+        any::<i64>().prop_map(
+            move |value| GovernanceVar::IcxTakerFeePerBtc(IcxTakerFeePerBtc {
+                key: key("ICX_TAKERFEE_PER_BTC"),
+                value,
+            })
+        ),
+        compact(attribute()).prop_map(move |value| GovernanceVar::Attributes(Attributes {
+            key: key("ATTRIBUTES"),
+            value,
+        })),
+    ]
+}
+
+/// An unknown variable, whose value runs to the end of the message.
+fn unmapped_var() -> impl Strategy<Value = GovernanceVar> {
+    ("[A-Z_]{0,12}", vec(any::<u8>(), 0..1024)).prop_map(|(key, value)| {
+        GovernanceVar::Unmapped(LpUnmapped {
+            key: format!("UNMAPPED_{key}"),
+            value: RawBytes(value),
+        })
+    })
+}
+
+fn set_governance() -> impl Strategy<Value = SetGovernance> {
+    (vec(governance_var(), 0..4), option::of(unmapped_var())).prop_map(|(mut vars, unmapped)| {
+        vars.extend(unmapped);
+        SetGovernance {
+            governance_vars: vars,
+        }
+    })
+}
+
+fn set_governance_height() -> impl Strategy<Value = SetGovernanceHeight> {
+    (prop_oneof![governance_var(), unmapped_var()], any::<u32>()).prop_map(
+        |(var, activation_height)| SetGovernanceHeight {
+            var,
+            activation_height,
+        },
+    )
+}
+
+fn unset_governance() -> impl Strategy<Value = UnsetGovernance> {
+    compact((text(), compact(text())).prop_map(|(key, values)| UnsetGovernanceVar { key, values }))
+        .prop_map(|governance_vars| UnsetGovernance { governance_vars })
+}
+
+fn create_proposal() -> impl Strategy<Value = CreateProposal> {
+    (
+        any::<u8>(),
+        script(),
+        any::<i64>(),
+        any::<u8>(),
+        text(),
+        text(),
+        text(),
+        any::<u8>(),
+    )
+        .prop_map(
+            |(r#type, address, n_amount, n_cycles, title, context, contexthash, options)| {
+                CreateProposal {
+                    r#type,
+                    address,
+                    n_amount,
+                    n_cycles,
+                    title,
+                    context,
+                    contexthash,
+                    options,
+                }
+            },
+        )
+}
+
+fn vote() -> impl Strategy<Value = Vote> {
+    (txid(), txid(), any::<u8>()).prop_map(|(proposal_id, masternode_id, vote_decision)| Vote {
+        proposal_id,
+        masternode_id,
+        vote_decision,
+    })
+}
+
+fn dftx() -> impl Strategy<Value = DfTx> {
+    prop_oneof![
+        account_to_account().prop_map(DfTx::AccountToAccount),
+        account_to_utxos().prop_map(DfTx::AccountToUtxos),
+        any_accounts_to_accounts().prop_map(DfTx::AnyAccountsToAccounts),
+        appoint_oracle().prop_map(DfTx::AppointOracle),
+        LazyJust::new(|| DfTx::AutoAuthPrep),
+        (txid(), script()).prop_map(|(vault_id, to)| DfTx::CloseVault(CloseVault { vault_id, to })),
+        composite_swap().prop_map(DfTx::CompositeSwap),
+        create_proposal().prop_map(DfTx::CreateCfp),
+        create_masternode().prop_map(DfTx::CreateMasternode),
+        (script(), text()).prop_map(|(owner_address, scheme_id)| {
+            DfTx::CreateVault(CreateVault {
+                owner_address,
+                scheme_id,
+            })
+        }),
+        create_proposal().prop_map(DfTx::CreateVoc),
+        (txid(), script(), token_balance_var_int()).prop_map(|(vault_id, from, token_amount)| {
+            DfTx::DepositToVault(DepositToVault {
+                vault_id,
+                from,
+                token_amount,
+            })
+        }),
+        (text(), any::<i64>()).prop_map(|(identifier, height)| {
+            DfTx::DestroyLoanScheme(DestroyLoanScheme { identifier, height })
+        }),
+        evm_tx().prop_map(DfTx::EvmTx),
+        LazyJust::new(|| DfTx::FutureSwapExecution),
+        LazyJust::new(|| DfTx::FutureSwapRefund),
+        icx_claim_dfc_htlc().prop_map(DfTx::ICXClaimDFCHTLC),
+        txid().prop_map(|offer_tx| DfTx::ICXCloseOffer(ICXCloseOffer { offer_tx })),
+        txid().prop_map(|order_tx| DfTx::ICXCloseOrder(ICXCloseOrder { order_tx })),
+        icx_create_order().prop_map(DfTx::ICXCreateOrder),
+        icx_make_offer().prop_map(DfTx::ICXMakeOffer),
+        icx_submit_dfc_htlc().prop_map(DfTx::ICXSubmitDFCHTLC),
+        icx_submit_ext_htlc().prop_map(DfTx::ICXSubmitEXTHTLC),
+        (txid(), script(), balances()).prop_map(|(vault_id, from, token_amounts)| {
+            DfTx::PaybackLoan(PaybackLoan {
+                vault_id,
+                from,
+                token_amounts,
+            })
+        }),
+        payback_loan_v2().prop_map(DfTx::PaybackLoanV2),
+        txid().prop_map(|vault_id| DfTx::PaybackWithCollateral(PaybackWithCollateral { vault_id })),
+        (txid(), any::<u32>(), script(), token_balance_var_int()).prop_map(
+            |(vault_id, index, from, token_amount)| DfTx::PlaceAuctionBid(PlaceAuctionBid {
+                vault_id,
+                index,
+                from,
+                token_amount,
+            })
+        ),
+        (script_balances(), script()).prop_map(|(from, share_address)| {
+            DfTx::PoolAddLiquidity(PoolAddLiquidity {
+                from,
+                share_address,
+            })
+        }),
+        pool_create_pair().prop_map(DfTx::PoolCreatePair),
+        (script(), token_balance_var_int()).prop_map(|(script, amount)| {
+            DfTx::PoolRemoveLiquidity(PoolRemoveLiquidity { script, amount })
+        }),
+        pool_swap().prop_map(DfTx::PoolSwap),
+        pool_update_pair().prop_map(DfTx::PoolUpdatePair),
+        LazyJust::new(|| DfTx::ProposalFeeRedistribution),
+        txid().prop_map(|oracle_id| DfTx::RemoveOracle(RemoveOracle { oracle_id })),
+        txid().prop_map(|node_id| DfTx::ResignMasternode(ResignMasternode { node_id })),
+        set_collateral_token().prop_map(DfTx::SetCollateralToken),
+        text()
+            .prop_map(|identifier| DfTx::SetDefaultLoanScheme(SetDefaultLoanScheme { identifier })),
+        set_future_swap().prop_map(DfTx::SetFutureSwap),
+        set_governance().prop_map(DfTx::SetGovernance),
+        set_governance_height().prop_map(DfTx::SetGovernanceHeight),
+        set_loan_scheme().prop_map(DfTx::SetLoanScheme),
+        set_loan_token().prop_map(DfTx::SetLoanToken),
+        set_oracle_data().prop_map(DfTx::SetOracleData),
+        smart_contract().prop_map(DfTx::SmartContract),
+        (txid(), script(), balances()).prop_map(|(vault_id, to, token_amounts)| {
+            DfTx::TakeLoan(TakeLoan {
+                vault_id,
+                to,
+                token_amounts,
+            })
+        }),
+        burn_token().prop_map(DfTx::BurnToken),
+        create_token().prop_map(DfTx::CreateToken),
+        (balances(), option::of(script()))
+            .prop_map(|(balances, to)| { DfTx::MintToken(MintToken { balances, to }) }),
+        (txid(), any::<bool>()).prop_map(|(creation_tx, is_dat)| {
+            DfTx::UpdateToken(UpdateToken {
+                creation_tx,
+                is_dat,
+            })
+        }),
+        (txid(), create_token()).prop_map(|(creation_tx, token)| {
+            DfTx::UpdateTokenAny(UpdateTokenAny { creation_tx, token })
+        }),
+        LazyJust::new(|| DfTx::TokenSplit),
+        transfer_domain().prop_map(DfTx::TransferDomain),
+        unset_governance().prop_map(DfTx::UnsetGovernance),
+        update_loan_token().prop_map(DfTx::UpdateLoanToken),
+        update_masternode().prop_map(DfTx::UpdateMasternode),
+        update_oracle().prop_map(DfTx::UpdateOracle),
+        (txid(), script(), text()).prop_map(|(vault_id, owner_address, scheme_id)| {
+            DfTx::UpdateVault(UpdateVault {
+                vault_id,
+                owner_address,
+                scheme_id,
+            })
+        }),
+        utxos_to_account().prop_map(DfTx::UtxosToAccount),
+        vote().prop_map(DfTx::Vote),
+        (txid(), script(), token_balance_var_int()).prop_map(|(vault_id, to, token_amount)| {
+            DfTx::WithdrawFromVault(WithdrawFromVault {
+                vault_id,
+                to,
+                token_amount,
+            })
+        }),
+        LazyJust::new(|| DfTx::Reject),
+        LazyJust::new(|| DfTx::None),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_dftx_round_trip(dftx in dftx()) {
+        let bytes = serialize(&dftx);
+        prop_assert_eq!(deserialize::<DfTx>(&bytes).unwrap(), dftx);
+    }
+
+    /// Trailing bytes are kept in the stack rather than dropped or truncated.
+    #[test]
+    fn test_stack_round_trip(msg in account_to_account(), rest in vec(any::<u8>(), 0..2048)) {
+        let dftx = DfTx::AccountToAccount(msg);
+        let mut bytes = serialize(&dftx);
+        bytes.extend(&rest);
+        let stack = deserialize::<Stack>(&bytes).unwrap();
+        prop_assert_eq!(&stack.dftx, &dftx);
+        prop_assert_eq!(stack.rest(), &rest[..]);
+        prop_assert_eq!(serialize(&stack), bytes);
+    }
+
+    /// Decoding untrusted bytes may fail but must not panic, and anything decoded must
+    /// re-encode to an equivalent message.
+    #[test]
+    fn test_decode_arbitrary_bytes(r#type in any::<u8>(), payload in vec(any::<u8>(), 0..256)) {
+        let mut bytes = b"DfTx".to_vec();
+        bytes.push(r#type);
+        bytes.extend(payload);
+        if let Ok((dftx, _)) = deserialize_partial::<DfTx>(&bytes) {
+            let (decoded, _) = deserialize_partial::<DfTx>(&serialize(&dftx)).unwrap();
+            prop_assert_eq!(decoded, dftx);
+        }
+    }
+}
+
+#[test]
+fn test_raw_bytes_over_512() {
+    let value = (0..2000).map(|i| i as u8).collect::<Vec<_>>();
+    let dftx = DfTx::SetGovernance(SetGovernance {
+        governance_vars: vec![GovernanceVar::Unmapped(LpUnmapped {
+            key: "UNMAPPED".to_string(),
+            value: RawBytes(value.clone()),
+        })],
+    });
+    let DfTx::SetGovernance(gov) = deserialize::<DfTx>(&serialize(&dftx)).unwrap() else {
+        panic!("expected SetGovernance");
+    };
+    let [GovernanceVar::Unmapped(var)] = gov.governance_vars.as_slice() else {
+        panic!("expected a single unmapped variable");
+    };
+    assert_eq!(var.value.0, value);
+}
+
+#[test]
+fn test_set_governance_height_missing_height() {
+    let mut bytes = serialize(&DfTx::SetGovernanceHeight(SetGovernanceHeight {
+        var: GovernanceVar::Unmapped(LpUnmapped {
+            key: "UNMAPPED".to_string(),
+            value: RawBytes(vec![]),
+        }),
+        activation_height: 0,
+    }));
+    bytes.truncate(bytes.len() - 2);
+    assert!(deserialize::<DfTx>(&bytes).is_err());
+}
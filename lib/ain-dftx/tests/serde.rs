@@ -0,0 +1,94 @@
+#![cfg(feature = "serde")]
+
+use ain_dftx::DfTx;
+use serde_json::json;
+
+fn decode(line: &str) -> DfTx {
+    let hex = hex::decode(line).unwrap();
+    let offset = 1 + match hex[1] {
+        0x4c => 2,
+        0x4d => 3,
+        0x4e => 4,
+        _ => 1,
+    };
+    let (dftx, _) = bitcoin::consensus::deserialize_partial::<DfTx>(&hex[offset..]).unwrap();
+    dftx
+}
+
+#[test]
+fn test_json_round_trip() {
+    for entry in std::fs::read_dir("./tests/data").unwrap() {
+        let path = entry.unwrap().path();
+        if path.file_name().unwrap() == "block.txt" {
+            continue;
+        }
+        let s = std::fs::read_to_string(&path).unwrap();
+        for line in s.lines() {
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            let dftx = decode(line.split(' ').next().unwrap());
+            let json = serde_json::to_string(&dftx).unwrap();
+            assert_eq!(
+                serde_json::from_str::<DfTx>(&json).unwrap(),
+                dftx,
+                "{} {json}",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_json_shape() {
+    let dftx = decode(
+        "6a2a44665478511600148866af6e0455e34b5ef4ecb51b07dad4e51431790200e1f505000000000000000000",
+    );
+    assert_eq!(
+        serde_json::to_value(&dftx).unwrap(),
+        json!({
+            "type": "DFIP2203",
+            "results": {
+                "owner": "00148866af6e0455e34b5ef4ecb51b07dad4e5143179",
+                "source": "1.00000000@2",
+                "destination": 0,
+                "withdraw": false,
+            }
+        })
+    );
+
+    let dftx = decode("6a2d4466547842160014c9fc3eba0fd4ea8a1bc744e2a9b4bcb8cfbc27780116001476060adc229ddaef9e2fea605e3c42954f8605b50102000000008d380c01000000");
+    assert_eq!(
+        serde_json::to_value(&dftx).unwrap(),
+        json!({
+            "type": "AccountToAccount",
+            "results": {
+                "from": "0014c9fc3eba0fd4ea8a1bc744e2a9b4bcb8cfbc2778",
+                "to": {
+                    "001476060adc229ddaef9e2fea605e3c42954f8605b5": "45.00000000@2",
+                },
+            }
+        })
+    );
+
+    let dftx = decode(
+        "6a2744665478470a4154545249425554455301000000007403000000650100000080841e0000000000",
+    );
+    assert_eq!(
+        serde_json::to_value(&dftx).unwrap(),
+        json!({
+            "type": "SetGovVariable",
+            "results": {
+                "ATTRIBUTES": {
+                    "v0/token/3/dex_in_fee_pct": { "amount": "0.02000000" },
+                },
+            }
+        })
+    );
+
+    let dftx = decode("6a054466547801");
+    assert_eq!(
+        serde_json::to_value(&dftx).unwrap(),
+        json!({ "type": "Reject" })
+    );
+}
@@ -1,3 +1,4 @@
+use ain_dftx::DfTx;
 use ain_macros::test_dftx_serialization;
 
 #[test]
@@ -19,3 +20,9 @@ fn test_token_update_any() {}
 #[test]
 #[test_dftx_serialization]
 fn test_token_burn() {}
+
+#[test_dftx_serialization]
+#[test]
+fn test_token_split(_: usize, dftx: &DfTx) {
+    assert_eq!(dftx, &DfTx::TokenSplit);
+}
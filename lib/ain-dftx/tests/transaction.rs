@@ -0,0 +1,167 @@
+use ain_dftx::{deserialize, DfTx, Script, Transaction};
+
+/// Builds a version 2 tx with a single dummy input and the given output scripts.
+fn tx_with_outputs(scripts: &[Vec<u8>]) -> Transaction {
+    let mut raw = vec![0x02, 0x00, 0x00, 0x00, 0x01];
+    raw.extend([0u8; 36]);
+    raw.extend([0x00, 0xff, 0xff, 0xff, 0xff]);
+    raw.push(scripts.len() as u8);
+    for script in scripts {
+        raw.extend([0u8; 8]);
+        raw.push(script.len() as u8);
+        raw.extend(script);
+    }
+    raw.extend([0u8; 4]);
+    deserialize(&raw).unwrap()
+}
+
+fn op_return(push: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut script = vec![0x6a];
+    script.extend(push);
+    script.extend(payload);
+    script
+}
+
+// AutoAuthPrep
+const PAYLOAD: &[u8] = b"DfTxA";
+
+#[test]
+fn test_from_transaction_push_variants() {
+    let pushes: [&[u8]; 4] = [
+        &[0x05],
+        &[0x4c, 0x05],
+        &[0x4d, 0x05, 0x00],
+        &[0x4e, 0x05, 0x00, 0x00, 0x00],
+    ];
+    for push in pushes {
+        let tx = tx_with_outputs(&[op_return(push, PAYLOAD)]);
+        assert_eq!(
+            DfTx::from_transaction(&tx, true).unwrap(),
+            Some(DfTx::AutoAuthPrep)
+        );
+    }
+}
+
+#[test]
+fn test_from_transaction_large_payload() {
+    let s = std::fs::read_to_string("./tests/data/paybackloan.txt").unwrap();
+    let line = s.lines().find(|l| l.starts_with("6a4c")).unwrap();
+    let script = hex::decode(line.split(' ').next().unwrap()).unwrap();
+    let tx = tx_with_outputs(&[script]);
+    assert!(matches!(
+        DfTx::from_transaction(&tx, true).unwrap(),
+        Some(DfTx::PaybackLoan(_))
+    ));
+}
+
+#[test]
+fn test_from_transaction_not_custom_tx() {
+    let p2wpkh = [vec![0x00, 0x14], vec![0u8; 20]].concat();
+    for scripts in [
+        vec![],
+        vec![p2wpkh],
+        vec![op_return(&[0x04], b"DfTx")],
+        vec![op_return(&[0x05], b"DfTyB")],
+    ] {
+        let tx = tx_with_outputs(&scripts);
+        assert_eq!(DfTx::from_transaction(&tx, true).unwrap(), None);
+    }
+}
+
+#[test]
+fn test_from_transaction_reject() {
+    // Additional opcodes after the payload
+    let tx = tx_with_outputs(&[[op_return(&[0x05], PAYLOAD), vec![0x51]].concat()]);
+    assert_eq!(
+        DfTx::from_transaction(&tx, true).unwrap(),
+        Some(DfTx::Reject)
+    );
+
+    // Multiple pushes
+    let tx = tx_with_outputs(&[[op_return(&[0x05], PAYLOAD), vec![0x01, 0x00]].concat()]);
+    assert_eq!(
+        DfTx::from_transaction(&tx, true).unwrap(),
+        Some(DfTx::Reject)
+    );
+
+    // Marker in another output
+    let tx = tx_with_outputs(&[op_return(&[0x05], PAYLOAD), op_return(&[0x05], PAYLOAD)]);
+    assert_eq!(
+        DfTx::from_transaction(&tx, true).unwrap(),
+        Some(DfTx::Reject)
+    );
+
+    // Marker only in another output
+    let p2wpkh = [vec![0x00, 0x14], vec![0u8; 20]].concat();
+    let tx = tx_with_outputs(&[p2wpkh, op_return(&[0x05], PAYLOAD)]);
+    assert_eq!(
+        DfTx::from_transaction(&tx, true).unwrap(),
+        Some(DfTx::Reject)
+    );
+
+    // Unknown type
+    let tx = tx_with_outputs(&[op_return(&[0x05], b"DfTx\xff")]);
+    assert_eq!(
+        DfTx::from_transaction(&tx, true).unwrap(),
+        Some(DfTx::Reject)
+    );
+}
+
+#[test]
+fn test_from_transaction_without_metadata_validation() {
+    // Additional opcodes after the payload
+    let tx = tx_with_outputs(&[[op_return(&[0x05], PAYLOAD), vec![0x51]].concat()]);
+    assert_eq!(
+        DfTx::from_transaction(&tx, false).unwrap(),
+        Some(DfTx::AutoAuthPrep)
+    );
+
+    // Marker in another output
+    let tx = tx_with_outputs(&[op_return(&[0x05], PAYLOAD), op_return(&[0x05], PAYLOAD)]);
+    assert_eq!(
+        DfTx::from_transaction(&tx, false).unwrap(),
+        Some(DfTx::AutoAuthPrep)
+    );
+
+    // Unknown type
+    let tx = tx_with_outputs(&[op_return(&[0x05], b"DfTx\xff")]);
+    assert_eq!(
+        DfTx::from_transaction(&tx, false).unwrap(),
+        Some(DfTx::Reject)
+    );
+}
+
+#[test]
+fn test_from_transaction_excess_bytes() {
+    let tx = tx_with_outputs(&[op_return(&[0x06], b"DfTxA\x00")]);
+    assert!(DfTx::from_transaction(&tx, true).is_err());
+    assert!(DfTx::from_transaction(&tx, false).is_err());
+}
+
+#[test]
+fn test_from_script() {
+    let script = op_return(&[0x05], PAYLOAD);
+    assert_eq!(
+        DfTx::from_script(Script::from_bytes(&script), true).unwrap(),
+        Some(DfTx::AutoAuthPrep)
+    );
+
+    let script = [op_return(&[0x05], PAYLOAD), vec![0x51]].concat();
+    assert_eq!(
+        DfTx::from_script(Script::from_bytes(&script), true).unwrap(),
+        Some(DfTx::Reject)
+    );
+
+    let p2wpkh = [vec![0x00, 0x14], vec![0u8; 20]].concat();
+    assert_eq!(
+        DfTx::from_script(Script::from_bytes(&p2wpkh), true).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_from_transaction_invalid_payload() {
+    // Truncated AccountToAccount
+    let tx = tx_with_outputs(&[op_return(&[0x06], b"DfTxB\x16")]);
+    assert!(DfTx::from_transaction(&tx, true).is_err());
+}
@@ -21,6 +21,8 @@ pub struct TemplateTxItem {
     pub gas_used: U256,
     pub logs_bloom: Bloom,
     pub receipt_v3: ReceiptAndOptionalContractAddress,
+    /// Contracts deployed by the transaction, including internal creations.
+    pub created_contracts: Vec<H160>,
 }
 
 impl TemplateTxItem {
@@ -36,6 +38,7 @@ impl TemplateTxItem {
             gas_used: U256::zero(),
             logs_bloom,
             receipt_v3,
+            created_contracts: Vec::new(),
         }
     }
 }
@@ -91,6 +94,7 @@ impl BlockTemplate {
             gas_used: tx_update.gas_used,
             logs_bloom: tx_update.logs_bloom,
             receipt_v3: tx_update.receipt,
+            created_contracts: tx_update.created_contracts,
         });
         Ok(())
     }
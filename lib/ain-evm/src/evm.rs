@@ -29,9 +29,10 @@ use crate::{
     executor::AinExecutor,
     filters::FilterService,
     log::LogService,
+    metrics,
     receipt::ReceiptService,
     storage::{
-        traits::{BlockStorage, FlushableStorage},
+        traits::{BlockStorage, FlushableStorage, Rollback},
         Storage,
     },
     subscription::{Notification, SubscriptionService},
@@ -59,6 +60,7 @@ pub struct ExecTxState {
     pub logs_bloom: Bloom,
     pub gas_used: U256,
     pub gas_fees: U256,
+    pub created_contracts: Vec<H160>,
 }
 
 pub struct FinalizedBlockInfo {
@@ -117,6 +119,7 @@ impl EVMServices {
             // Init storage
             let trie_store = Arc::new(TrieDBStore::new());
             let storage = Arc::new(Storage::new(&path)?);
+            storage.load_impersonated_senders()?;
             let tx_cache = Arc::new(TransactionCache::new());
 
             Ok(Self {
@@ -139,6 +142,9 @@ impl EVMServices {
             // Init storage
             let trie_store = Arc::new(TrieDBStore::restore());
             let storage = Arc::new(Storage::restore(&path)?);
+            if ain_cpp_imports::get_network() == "regtest" {
+                storage.load_impersonated_senders()?;
+            }
             let tx_cache = Arc::new(TransactionCache::new());
 
             Ok(Self {
@@ -268,6 +274,7 @@ impl EVMServices {
     /// across all usages. Note: To be replaced with a proper lock flow later.
     ///
     pub unsafe fn commit_block(&self, template: &BlockTemplate) -> Result<()> {
+        let _timer = metrics::BLOCK_CONNECT_TIME.start_timer();
         let Some(BlockData { block, receipts }) = template.block_data.clone() else {
             return Err(format_err!("no constructed EVM block exist in template id").into());
         };
@@ -281,6 +288,12 @@ impl EVMServices {
         self.logs
             .generate_logs_from_receipts(&receipts, block.header.number)?;
         self.receipt.put_receipts(receipts)?;
+        for item in &template.transactions {
+            if !item.created_contracts.is_empty() {
+                self.storage
+                    .put_created_contracts(item.tx.hash(), &item.created_contracts)?;
+            }
+        }
         self.subscriptions
             .send(Notification::Block(block.header.hash()))?;
         self.core.clear_account_nonce();
@@ -309,6 +322,7 @@ impl EVMServices {
                     logs_bloom,
                     gas_used: apply_tx.used_gas,
                     gas_fees: apply_tx.gas_fee,
+                    created_contracts: apply_tx.created_contracts,
                 })
             }
             Err(e) => {
@@ -678,3 +692,54 @@ impl EVMServices {
         })
     }
 }
+
+// Dev methods, only to be used on regtest.
+impl EVMServices {
+    /// Applies `f` on a backend at the latest state and swaps the resulting state root into the
+    /// latest block. Returns the new state root.
+    pub fn dev_update_latest_state<F>(&self, f: F) -> Result<H256>
+    where
+        F: FnOnce(&mut EVMBackend) -> Result<()>,
+    {
+        let mut backend = self.core.get_latest_block_backend()?;
+        f(&mut backend)?;
+        let state_root = backend.commit(false)?;
+        self.dev_set_latest_state_root(state_root)?;
+        Ok(state_root)
+    }
+
+    /// Disconnects EVM blocks until `block_number` is the latest block and restores its state
+    /// root to `state_root`. The DVM blocks are expected to be rolled back by the caller.
+    pub fn dev_revert_to(&self, block_number: U256, state_root: H256) -> Result<()> {
+        while let Some(block) = self.storage.get_latest_block()? {
+            if block.header.number <= block_number {
+                break;
+            }
+            self.storage.disconnect_latest_block()?;
+        }
+        self.dev_set_latest_state_root(state_root)
+    }
+
+    fn dev_set_latest_state_root(&self, state_root: H256) -> Result<()> {
+        let mut block = self
+            .storage
+            .get_latest_block()?
+            .ok_or(format_err!("Latest block not found"))?;
+        if block.header.state_root != state_root {
+            let replaced_hash = block.header.hash();
+            block.header.state_root = state_root;
+            self.storage.replace_latest_block(&block)?;
+            // The DVM block maps to the EVM block by hash, which changes with the state root
+            if !ain_cpp_imports::replace_evm_block_hash(
+                replaced_hash.to_fixed_bytes(),
+                block.header.hash().to_fixed_bytes(),
+            ) {
+                return Err(
+                    format_err!("Failed to map block {replaced_hash:#x} to its new hash").into(),
+                );
+            }
+        }
+        self.core.clear_account_nonce();
+        Ok(())
+    }
+}
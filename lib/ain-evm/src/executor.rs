@@ -3,7 +3,7 @@ use anyhow::format_err;
 use ethereum::{AccessList, EIP658ReceiptData, Log, ReceiptV3};
 use ethereum_types::{Bloom, H160, H256, U256};
 use evm::{
-    backend::{ApplyBackend, Backend},
+    backend::{Apply, ApplyBackend, Backend},
     executor::stack::{MemoryStackState, StackExecutor, StackSubstateMetadata},
     Config, CreateScheme, ExitReason,
 };
@@ -135,6 +135,7 @@ impl<'backend> AinExecutor<'backend> {
             data,
             logs: Vec::new(),
             used_gas: executor.used_gas(),
+            created_contracts: Vec::new(),
         }
     }
 
@@ -219,7 +220,20 @@ impl<'backend> AinExecutor<'backend> {
             .into());
         }
         let (values, logs) = executor.into_state().deconstruct();
+        let values = values.into_iter().collect::<Vec<_>>();
         let logs = logs.into_iter().collect::<Vec<_>>();
+        // Includes the contracts deployed by internal CREATE and CREATE2 calls
+        let created_contracts = values
+            .iter()
+            .filter_map(|apply| match apply {
+                Apply::Modify {
+                    address,
+                    code: Some(code),
+                    ..
+                } if !code.is_empty() => Some(*address),
+                _ => None,
+            })
+            .collect();
 
         ApplyBackend::apply(self.backend, values, logs.clone(), true);
 
@@ -245,6 +259,7 @@ impl<'backend> AinExecutor<'backend> {
                 data,
                 logs,
                 used_gas,
+                created_contracts,
             },
             receipt,
         ))
@@ -292,6 +307,7 @@ impl<'backend> AinExecutor<'backend> {
                     logs: tx_response.logs,
                     gas_fee,
                     receipt: (receipt, None),
+                    created_contracts: tx_response.created_contracts,
                 })
             }
             ExecuteTx::SystemTx(SystemTx::TransferDomain(TransferDomainData {
@@ -376,6 +392,7 @@ impl<'backend> AinExecutor<'backend> {
                     logs: tx_response.logs,
                     gas_fee: U256::zero(),
                     receipt: (receipt, None),
+                    created_contracts: Vec::new(),
                 })
             }
             ExecuteTx::SystemTx(SystemTx::DST20Bridge(DST20Data {
@@ -443,6 +460,7 @@ impl<'backend> AinExecutor<'backend> {
                     logs: tx_response.logs,
                     gas_fee: U256::zero(),
                     receipt: (receipt, None),
+                    created_contracts: Vec::new(),
                 })
             }
             ExecuteTx::SystemTx(SystemTx::DeployContract(DeployContractData {
@@ -480,6 +498,7 @@ impl<'backend> AinExecutor<'backend> {
                     logs: Vec::new(),
                     gas_fee: U256::zero(),
                     receipt: (receipt, Some(address)),
+                    created_contracts: Vec::new(),
                 })
             }
             ExecuteTx::SystemTx(SystemTx::UpdateContractName(UpdateContractNameData {
@@ -507,6 +526,7 @@ impl<'backend> AinExecutor<'backend> {
                     gas_fee: U256::zero(),
                     receipt: (receipt, Some(address)),
                     exec_flag: true,
+                    created_contracts: Vec::new(),
                 })
             }
         }
@@ -521,6 +541,7 @@ pub struct ApplyTxResult {
     pub logs: Vec<Log>,
     pub gas_fee: U256,
     pub receipt: ReceiptAndOptionalContractAddress,
+    pub created_contracts: Vec<H160>,
 }
 
 #[derive(Debug)]
@@ -529,4 +550,6 @@ pub struct TxResponse {
     pub data: Vec<u8>,
     pub logs: Vec<Log>,
     pub used_gas: u64,
+    /// Contracts deployed by the applied transaction, empty for calls.
+    pub created_contracts: Vec<H160>,
 }
@@ -6,7 +6,7 @@ pub mod blocktemplate;
 pub mod bytes;
 mod contract;
 pub mod core;
-mod ecrecover;
+pub mod ecrecover;
 pub mod evm;
 pub mod executor;
 pub mod fee;
@@ -14,6 +14,7 @@ pub mod filters;
 mod gas;
 mod genesis;
 pub mod log;
+pub mod metrics;
 mod precompiles;
 pub mod receipt;
 pub mod services;
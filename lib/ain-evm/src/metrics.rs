@@ -0,0 +1,57 @@
+//! Prometheus metrics of the EVM services, registered in the default registry.
+
+use lazy_static::lazy_static;
+use prometheus::{
+    register_histogram, register_int_counter_vec, register_int_gauge_vec, Histogram, IntCounterVec,
+    IntGaugeVec,
+};
+
+use crate::{storage::Storage, Result};
+
+lazy_static! {
+    pub static ref CACHE_HITS: IntCounterVec = register_int_counter_vec!(
+        "evm_cache_hits_total",
+        "Number of cache lookups that found the entry",
+        &["cache"]
+    )
+    .unwrap();
+    pub static ref CACHE_MISSES: IntCounterVec = register_int_counter_vec!(
+        "evm_cache_misses_total",
+        "Number of cache lookups that did not find the entry",
+        &["cache"]
+    )
+    .unwrap();
+    pub static ref BLOCK_CONNECT_TIME: Histogram = register_histogram!(
+        "evm_block_connect_duration_seconds",
+        "Time taken to commit an EVM block",
+        vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5]
+    )
+    .unwrap();
+    pub static ref DB_STATS: IntGaugeVec = register_int_gauge_vec!(
+        "evm_db_property",
+        "RocksDB properties of the EVM block store",
+        &["property"]
+    )
+    .unwrap();
+}
+
+/// Counts a lookup of `cache` as a hit or a miss, passing the looked up value through.
+pub fn record_cache_lookup<T>(cache: &str, value: Option<T>) -> Option<T> {
+    let counter = if value.is_some() {
+        &CACHE_HITS
+    } else {
+        &CACHE_MISSES
+    };
+    counter.with_label_values(&[cache]).inc();
+    value
+}
+
+/// Refreshes the RocksDB gauges, which are only read on scrape.
+pub fn update_db_stats(storage: &Storage) -> Result<()> {
+    for (property, value) in storage.db_stats()? {
+        DB_STATS
+            .with_label_values(&[property])
+            .set(i64::try_from(value).unwrap_or(i64::MAX));
+    }
+    Ok(())
+}
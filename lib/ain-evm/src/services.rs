@@ -12,6 +12,7 @@ use parking_lot::Mutex;
 use tokio::{
     runtime::{Builder, Handle as AsyncHandle},
     sync::mpsc::{self, Sender},
+    task::JoinHandle as AsyncJoinHandle,
 };
 
 use crate::{evm::EVMServices, storage::traits::FlushableStorage};
@@ -41,6 +42,8 @@ pub struct Services {
     pub tokio_worker: Mutex<Option<JoinHandle<()>>>,
     pub json_rpc_handles: Mutex<Vec<ServerHandle>>,
     pub websocket_handles: Mutex<Vec<ServerHandle>>,
+    pub metrics_handles: Mutex<Vec<AsyncJoinHandle<()>>>,
+    pub ipc_handles: Mutex<Vec<AsyncJoinHandle<()>>>,
     pub evm: Arc<EVMServices>,
 }
 
@@ -66,6 +69,8 @@ impl Services {
             }))),
             json_rpc_handles: Mutex::new(vec![]),
             websocket_handles: Mutex::new(vec![]),
+            metrics_handles: Mutex::new(vec![]),
+            ipc_handles: Mutex::new(vec![]),
             evm: Arc::new(EVMServices::new().expect("Error initializing handlers")),
         }
     }
@@ -84,6 +89,20 @@ impl Services {
                 server.stop()?;
             }
         }
+
+        {
+            let metrics_handles = self.metrics_handles.lock();
+            for server in &*metrics_handles {
+                server.abort();
+            }
+        }
+
+        {
+            let ipc_handles = self.ipc_handles.lock();
+            for server in &*ipc_handles {
+                server.abort();
+            }
+        }
         Ok(())
     }
 
@@ -1,16 +1,26 @@
+use ain_contracts::{
+    get_transfer_domain_contract, get_transferdomain_dst20_transfer_function,
+    get_transferdomain_native_transfer_function, FixedContract,
+};
 use ain_db::version::{DBVersionControl, Migration};
 use ain_db::{Column, ColumnName, DBError, LedgerColumn, Rocks, TypedColumn};
 use anyhow::format_err;
-use ethereum::{BlockAny, TransactionV2};
+use ethereum::{BlockAny, ReceiptV3, TransactionV2};
 use ethereum_types::{H160, H256, U256};
 use log::{debug, info};
 use std::{
-    collections::HashMap, fmt::Write, fs, marker::PhantomData, path::Path, str::FromStr, sync::Arc,
+    collections::{BTreeSet, HashMap},
+    fmt::Write,
+    fs,
+    marker::PhantomData,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
     time::Instant,
 };
 
 use super::{
-    migration::MigrationV1,
+    migration::{MigrationV1, MigrationV2},
     traits::{BlockStorage, FlushableStorage, ReceiptStorage, Rollback, TransactionStorage},
 };
 use crate::{
@@ -20,10 +30,21 @@ use crate::{
         db::{columns, COLUMN_NAMES},
         traits::LogStorage,
     },
+    transaction::{register_impersonated_sender, unregister_impersonated_sender},
     EVMError, Result,
 };
 use ain_db::Result as DBResult;
 
+/// RocksDB properties reported by `BlockStore::db_stats`, summed over all column families.
+const DB_STATS_PROPERTIES: [&str; 6] = [
+    "rocksdb.estimate-num-keys",
+    "rocksdb.total-sst-files-size",
+    "rocksdb.live-sst-files-size",
+    "rocksdb.cur-size-all-mem-tables",
+    "rocksdb.estimate-pending-compaction-bytes",
+    "rocksdb.num-running-compactions",
+];
+
 #[derive(Debug, Clone)]
 pub struct BlockStore(Arc<Rocks>);
 
@@ -46,11 +67,28 @@ impl BlockStore {
             column: PhantomData,
         }
     }
+
+    /// Returns the RocksDB statistics of the block store as property name and value pairs.
+    pub fn db_stats(&self) -> Result<Vec<(&'static str, u64)>> {
+        let mut stats = Vec::with_capacity(DB_STATS_PROPERTIES.len());
+        for property in DB_STATS_PROPERTIES {
+            let mut value = 0;
+            for cf_name in COLUMN_NAMES {
+                let cf = self.0.cf_handle(cf_name)?;
+                value += self
+                    .0
+                    .property_int_value_cf(cf, property)?
+                    .unwrap_or_default();
+            }
+            stats.push((property, value));
+        }
+        Ok(stats)
+    }
 }
 
 impl DBVersionControl for BlockStore {
     const VERSION_KEY: &'static str = "version";
-    const CURRENT_VERSION: u32 = 1;
+    const CURRENT_VERSION: u32 = 2;
 
     fn set_version(&self, version: u32) -> DBResult<()> {
         let metadata_cf = self.column::<columns::Metadata>();
@@ -81,7 +119,7 @@ impl DBVersionControl for BlockStore {
         }
 
         let mut migrations: [Box<dyn Migration<Self>>; Self::CURRENT_VERSION as usize] =
-            [Box::new(MigrationV1)];
+            [Box::new(MigrationV1), Box::new(MigrationV2)];
         migrations.sort_by_key(|a| a.version());
 
         for migration in migrations {
@@ -212,6 +250,7 @@ impl ReceiptStorage for BlockStore {
     fn put_receipts(&self, receipts: Vec<Receipt>) -> Result<()> {
         let receipts_cf = self.column::<columns::Receipts>();
         for receipt in receipts {
+            self.put_address_index(&receipt)?;
             receipts_cf.put(&receipt.tx_hash, &receipt)?;
         }
         Ok(())
@@ -263,6 +302,223 @@ impl BlockStore {
     }
 }
 
+impl BlockStore {
+    /// Indexes the transaction of the receipt under every address it touches and records
+    /// the creator of a contract deployed by it.
+    pub fn put_address_index(&self, receipt: &Receipt) -> Result<()> {
+        let address_txs_cf = self.column::<columns::AddressTransactions>();
+        let tx_index =
+            u64::try_from(receipt.tx_index).map_err(|_| format_err!("tx index overflow"))?;
+        for address in self.receipt_addresses(receipt)? {
+            address_txs_cf.put(&(address, receipt.block_number, tx_index), &receipt.tx_hash)?;
+        }
+
+        if let Some(contract_address) = receipt.contract_address {
+            let contract_creator_cf = self.column::<columns::ContractCreator>();
+            contract_creator_cf.put(&contract_address, &(receipt.tx_hash, receipt.from))?;
+        }
+        Ok(())
+    }
+
+    fn delete_address_index(&self, receipt: &Receipt) -> Result<()> {
+        let address_txs_cf = self.column::<columns::AddressTransactions>();
+        let contract_creator_cf = self.column::<columns::ContractCreator>();
+        let created_contracts_cf = self.column::<columns::CreatedContracts>();
+        let tx_index =
+            u64::try_from(receipt.tx_index).map_err(|_| format_err!("tx index overflow"))?;
+        let created_contracts = created_contracts_cf
+            .get(&receipt.tx_hash)?
+            .unwrap_or_default();
+        for address in self
+            .receipt_addresses(receipt)?
+            .into_iter()
+            .chain(created_contracts.iter().copied())
+        {
+            address_txs_cf.delete(&(address, receipt.block_number, tx_index))?;
+        }
+
+        for contract_address in receipt.contract_address.iter().chain(&created_contracts) {
+            contract_creator_cf.delete(contract_address)?;
+        }
+        created_contracts_cf.delete(&receipt.tx_hash)?;
+        Ok(())
+    }
+
+    /// Indexes the contracts deployed by a transaction that are not its receipt's contract
+    /// address, such as the contracts created by factory calls. Expects the receipt of the
+    /// transaction to be stored.
+    pub fn put_created_contracts(&self, tx_hash: H256, contracts: &[H160]) -> Result<()> {
+        let Some(receipt) = self.column::<columns::Receipts>().get(&tx_hash)? else {
+            return Ok(());
+        };
+        let contracts = contracts
+            .iter()
+            .copied()
+            .filter(|address| Some(*address) != receipt.contract_address)
+            .collect::<Vec<_>>();
+        if contracts.is_empty() {
+            return Ok(());
+        }
+
+        let address_txs_cf = self.column::<columns::AddressTransactions>();
+        let contract_creator_cf = self.column::<columns::ContractCreator>();
+        let tx_index =
+            u64::try_from(receipt.tx_index).map_err(|_| format_err!("tx index overflow"))?;
+        for address in &contracts {
+            address_txs_cf.put(&(*address, receipt.block_number, tx_index), &tx_hash)?;
+            contract_creator_cf.put(address, &(tx_hash, receipt.from))?;
+        }
+        self.column::<columns::CreatedContracts>()
+            .put(&tx_hash, &contracts)?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` transactions the address appears in, as
+    /// `((block number, tx index), tx hash)`, starting from the `from` position inclusive.
+    /// Iterates from the most recent transaction on `Direction::Reverse` and from the
+    /// oldest on `Direction::Forward` when no position is provided.
+    pub fn get_address_transactions(
+        &self,
+        address: H160,
+        from: Option<(U256, u64)>,
+        direction: rocksdb::Direction,
+        limit: usize,
+    ) -> Result<Vec<((U256, u64), H256)>> {
+        let (block_number, tx_index) = from.unwrap_or(match direction {
+            rocksdb::Direction::Forward => (U256::zero(), 0),
+            rocksdb::Direction::Reverse => (U256::MAX, u64::MAX),
+        });
+
+        let address_txs_cf = self.column::<columns::AddressTransactions>();
+        let mut out = Vec::new();
+        for item in address_txs_cf.iter(Some((address, block_number, tx_index)), direction)? {
+            let ((item_address, block_number, tx_index), hash) = item?;
+            if item_address != address || out.len() >= limit {
+                break;
+            }
+            out.push(((block_number, tx_index), hash));
+        }
+        Ok(out)
+    }
+
+    /// Returns the creation tx hash and creator of a contract deployed by a transaction.
+    pub fn get_contract_creator(&self, address: &H160) -> Result<Option<(H256, H160)>> {
+        let contract_creator_cf = self.column::<columns::ContractCreator>();
+        Ok(contract_creator_cf.get(address)?)
+    }
+}
+
+impl BlockStore {
+    /// Persists the sender of a transaction sent from an impersonated account on regtest.
+    pub fn put_impersonated_sender(&self, hash: H256, sender: H160) -> Result<()> {
+        let impersonated_senders_cf = self.column::<columns::ImpersonatedSenders>();
+        impersonated_senders_cf.put(&hash, &sender)?;
+        register_impersonated_sender(hash, sender);
+        Ok(())
+    }
+
+    pub fn delete_impersonated_sender(&self, hash: &H256) -> Result<()> {
+        let impersonated_senders_cf = self.column::<columns::ImpersonatedSenders>();
+        impersonated_senders_cf.delete(hash)?;
+        unregister_impersonated_sender(hash);
+        Ok(())
+    }
+
+    /// Registers the persisted impersonated senders, on regtest only.
+    pub fn load_impersonated_senders(&self) -> Result<()> {
+        let impersonated_senders_cf = self.column::<columns::ImpersonatedSenders>();
+        for item in impersonated_senders_cf.iter(None, rocksdb::Direction::Forward)? {
+            let (hash, sender) = item?;
+            register_impersonated_sender(hash, sender);
+        }
+        Ok(())
+    }
+
+    /// Overwrites the block at the height of `block`. When its hash changes, the hash index
+    /// entry of the replaced block is removed and the transaction locations, receipts and logs
+    /// are moved over to the new block hash.
+    pub fn replace_block(&self, block: &BlockAny) -> Result<()> {
+        let block_number = block.header.number;
+        let hash = block.header.hash();
+        if let Some(replaced) = self.get_block_by_number(&block_number)? {
+            let replaced_hash = replaced.header.hash();
+            if replaced_hash != hash {
+                let blocks_map_cf = self.column::<columns::BlockMap>();
+                blocks_map_cf.delete(&replaced_hash)?;
+
+                let receipts_cf = self.column::<columns::Receipts>();
+                for tx in &replaced.transactions {
+                    if let Some(mut receipt) = receipts_cf.get(&tx.hash())? {
+                        receipt.block_hash = hash;
+                        receipts_cf.put(&receipt.tx_hash, &receipt)?;
+                    }
+                }
+
+                let logs_cf = self.column::<columns::AddressLogsMap>();
+                if let Some(mut logs) = logs_cf.get(&block_number)? {
+                    for log in logs.values_mut().flatten() {
+                        log.block_hash = hash;
+                    }
+                    logs_cf.put(&block_number, &logs)?;
+                }
+            }
+        }
+        // Rewrites the transaction locations with the new block hash
+        self.put_block(block)
+    }
+}
+
+impl BlockStore {
+    /// Addresses a transaction appears in: sender, recipient, created contract, log emitters
+    /// and the participants of TransferDomain and DST20 bridge system txs.
+    fn receipt_addresses(&self, receipt: &Receipt) -> Result<BTreeSet<H160>> {
+        let logs = match &receipt.receipt {
+            ReceiptV3::Legacy(r) | ReceiptV3::EIP2930(r) | ReceiptV3::EIP1559(r) => &r.logs,
+        };
+
+        let mut addresses = std::iter::once(receipt.from)
+            .chain(receipt.to)
+            .chain(receipt.contract_address)
+            .chain(logs.iter().map(|log| log.address))
+            .collect::<BTreeSet<_>>();
+        addresses.extend(self.bridge_addresses(receipt)?);
+        Ok(addresses)
+    }
+
+    /// Decodes the source and destination addresses from the input of a transfer domain
+    /// contract call, as the bridged accounts are not part of the receipt.
+    fn bridge_addresses(&self, receipt: &Receipt) -> Result<Vec<H160>> {
+        let FixedContract { fixed_address, .. } = get_transfer_domain_contract();
+        if receipt.to != Some(fixed_address) {
+            return Ok(Vec::new());
+        }
+        let Some(tx) =
+            self.get_transaction_by_block_hash_and_index(&receipt.block_hash, receipt.tx_index)?
+        else {
+            return Ok(Vec::new());
+        };
+        let input = match &tx {
+            TransactionV2::Legacy(tx) => &tx.input,
+            TransactionV2::EIP2930(tx) => &tx.input,
+            TransactionV2::EIP1559(tx) => &tx.input,
+        };
+
+        for function in [
+            get_transferdomain_native_transfer_function(),
+            get_transferdomain_dst20_transfer_function(),
+        ] {
+            if input.get(..4) == Some(function.short_signature().as_slice()) {
+                let tokens = function.decode_input(&input[4..])?;
+                return Ok(tokens
+                    .into_iter()
+                    .filter_map(ethabi::Token::into_address)
+                    .collect());
+            }
+        }
+        Ok(Vec::new())
+    }
+}
+
 impl Rollback for BlockStore {
     fn disconnect_latest_block(&self) -> Result<()> {
         if let Some(block) = self.get_latest_block()? {
@@ -273,6 +529,9 @@ impl Rollback for BlockStore {
             let transactions_cf = self.column::<columns::Transactions>();
             let receipts_cf = self.column::<columns::Receipts>();
             for tx in &block.transactions {
+                if let Some(receipt) = receipts_cf.get(&tx.hash())? {
+                    self.delete_address_index(&receipt)?;
+                }
                 transactions_cf.delete(&tx.hash())?;
                 receipts_cf.delete(&tx.hash())?;
             }
@@ -402,3 +661,291 @@ impl BlockStore {
         Ok(out)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ethereum::{
+        Block, EIP658ReceiptData, LegacyTransaction, Log, PartialHeader, TransactionAction,
+        TransactionSignature,
+    };
+    use ethereum_types::Bloom;
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn block(state_root: H256, transactions: Vec<TransactionV2>) -> BlockAny {
+        Block::new(
+            PartialHeader {
+                parent_hash: H256::zero(),
+                beneficiary: H160::zero(),
+                state_root,
+                receipts_root: H256::zero(),
+                logs_bloom: Bloom::default(),
+                difficulty: U256::zero(),
+                number: U256::one(),
+                gas_limit: U256::from(30_000_000),
+                gas_used: U256::zero(),
+                timestamp: 0,
+                extra_data: Vec::new(),
+                mix_hash: H256::zero(),
+                nonce: Default::default(),
+                base_fee: U256::zero(),
+            },
+            transactions,
+            Vec::new(),
+        )
+    }
+
+    fn receipt(tx_hash: H256, block_hash: H256) -> Receipt {
+        Receipt {
+            tx_hash,
+            receipt: ReceiptV3::Legacy(EIP658ReceiptData {
+                status_code: 1,
+                used_gas: U256::from(21000),
+                logs_bloom: Bloom::default(),
+                logs: Vec::new(),
+            }),
+            block_hash,
+            block_number: U256::one(),
+            from: H160::repeat_byte(1),
+            to: Some(H160::repeat_byte(2)),
+            tx_index: 0,
+            tx_type: 0,
+            contract_address: None,
+            logs_index: 0,
+            cumulative_gas: U256::from(21000),
+            effective_gas_price: U256::one(),
+        }
+    }
+
+    fn tx() -> TransactionV2 {
+        ethereum::EnvelopedDecodable::decode(&hex::decode("f86b02830186a0830186a094a8f7c4c78c36e54c3950ad58dad24ca5e0191b2989056bc75e2d631000008025a0b0842b0c78dd7fc33584ec9a81ab5104fe70169878de188ba6c11fe7605e298aa0735dc483f625f17d68d1e1fae779b7160612628e6dde9eecf087892fe60bba4e").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn should_move_block_references_to_replaced_block_hash() -> Result<()> {
+        let dir = TempDir::new("block_store")?;
+        let store = BlockStore::new(dir.path())?;
+
+        let tx = tx();
+        let original = block(H256::repeat_byte(1), vec![tx.clone()]);
+        let original_hash = original.header.hash();
+        store.put_block(&original)?;
+        store.put_latest_block(Some(&original))?;
+        store.put_receipts(vec![receipt(tx.hash(), original_hash)])?;
+        let log = LogIndex {
+            block_hash: original_hash,
+            block_number: U256::one(),
+            topics: Vec::new(),
+            data: Vec::new(),
+            log_index: U256::zero(),
+            address: H160::repeat_byte(2),
+            removed: false,
+            transaction_hash: tx.hash(),
+            transaction_index: U256::zero(),
+        };
+        store.put_logs(H160::repeat_byte(2), vec![log], U256::one())?;
+
+        let replaced = block(H256::repeat_byte(2), vec![tx.clone()]);
+        let replaced_hash = replaced.header.hash();
+        store.replace_block(&replaced)?;
+
+        assert!(store.get_block_by_hash(&original_hash)?.is_none());
+        assert_eq!(
+            store
+                .get_block_by_hash(&replaced_hash)?
+                .map(|b| b.header.hash()),
+            Some(replaced_hash)
+        );
+        assert_eq!(
+            store
+                .column::<columns::Transactions>()
+                .get(&tx.hash())?
+                .map(|(block_hash, _)| block_hash),
+            Some(replaced_hash)
+        );
+        assert_eq!(
+            store.get_receipt(&tx.hash())?.map(|r| r.block_hash),
+            Some(replaced_hash)
+        );
+        let logs = store.get_logs(&U256::one())?.unwrap_or_default();
+        assert!(logs
+            .values()
+            .flatten()
+            .all(|log| log.block_hash == replaced_hash));
+        Ok(())
+    }
+
+    #[test]
+    fn should_persist_impersonated_senders() -> Result<()> {
+        let dir = TempDir::new("block_store")?;
+        let hash = H256::repeat_byte(0xaa);
+        let sender = H160::repeat_byte(0xbb);
+        BlockStore::new(dir.path())?.put_impersonated_sender(hash, sender)?;
+
+        let store = BlockStore::new(dir.path())?;
+        assert_eq!(
+            store.column::<columns::ImpersonatedSenders>().get(&hash)?,
+            Some(sender)
+        );
+
+        store.delete_impersonated_sender(&hash)?;
+        assert_eq!(
+            store.column::<columns::ImpersonatedSenders>().get(&hash)?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_index_contracts_created_by_factory_calls() -> Result<()> {
+        let dir = TempDir::new("block_store")?;
+        let store = BlockStore::new(dir.path())?;
+
+        let tx = tx();
+        let block = block(H256::zero(), vec![tx.clone()]);
+        store.put_block(&block)?;
+        store.put_latest_block(Some(&block))?;
+        let receipt = receipt(tx.hash(), block.header.hash());
+        store.put_receipts(vec![receipt.clone()])?;
+        store.put_address_index(&receipt)?;
+
+        let factory = H160::repeat_byte(2);
+        let child = H160::repeat_byte(3);
+        store.put_created_contracts(tx.hash(), &[child])?;
+
+        let position = ((U256::one(), 0), tx.hash());
+        for address in [receipt.from, factory, child] {
+            assert_eq!(
+                store.get_address_transactions(address, None, rocksdb::Direction::Reverse, 10)?,
+                vec![position]
+            );
+        }
+        assert_eq!(
+            store.get_contract_creator(&child)?,
+            Some((tx.hash(), receipt.from))
+        );
+        assert!(store.get_contract_creator(&factory)?.is_none());
+
+        store.disconnect_latest_block()?;
+        for address in [receipt.from, factory, child] {
+            assert!(store
+                .get_address_transactions(address, None, rocksdb::Direction::Forward, 10)?
+                .is_empty());
+        }
+        assert!(store.get_contract_creator(&child)?.is_none());
+        assert!(store
+            .column::<columns::CreatedContracts>()
+            .get(&tx.hash())?
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn should_index_address_appearances() -> Result<()> {
+        let dir = TempDir::new("block_store")?;
+        let store = BlockStore::new(dir.path())?;
+
+        let emitter = H160::repeat_byte(4);
+        let mut first = receipt(H256::repeat_byte(0x11), H256::zero());
+        first.receipt = ReceiptV3::Legacy(EIP658ReceiptData {
+            status_code: 1,
+            used_gas: U256::from(21000),
+            logs_bloom: Bloom::default(),
+            logs: vec![Log {
+                address: emitter,
+                topics: Vec::new(),
+                data: Vec::new(),
+            }],
+        });
+        let mut second = receipt(H256::repeat_byte(0x22), H256::zero());
+        second.block_number = U256::from(2);
+        second.to = Some(H160::repeat_byte(5));
+        for receipt in [&first, &second] {
+            store.put_address_index(receipt)?;
+        }
+
+        let first_position = ((U256::one(), 0), first.tx_hash);
+        let second_position = ((U256::from(2), 0), second.tx_hash);
+        assert_eq!(
+            store.get_address_transactions(first.from, None, rocksdb::Direction::Reverse, 10)?,
+            vec![second_position, first_position]
+        );
+        assert_eq!(
+            store.get_address_transactions(first.from, None, rocksdb::Direction::Forward, 1)?,
+            vec![first_position]
+        );
+        assert_eq!(
+            store.get_address_transactions(
+                first.from,
+                Some((U256::one(), u64::MAX)),
+                rocksdb::Direction::Reverse,
+                10
+            )?,
+            vec![first_position]
+        );
+        for address in [H160::repeat_byte(2), emitter] {
+            assert_eq!(
+                store.get_address_transactions(address, None, rocksdb::Direction::Reverse, 10)?,
+                vec![first_position]
+            );
+        }
+        assert_eq!(
+            store.get_address_transactions(
+                H160::repeat_byte(5),
+                None,
+                rocksdb::Direction::Reverse,
+                10
+            )?,
+            vec![second_position]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_index_transfer_domain_participants() -> Result<()> {
+        let dir = TempDir::new("block_store")?;
+        let store = BlockStore::new(dir.path())?;
+
+        let FixedContract { fixed_address, .. } = get_transfer_domain_contract();
+        let (from, to) = (H160::repeat_byte(6), H160::repeat_byte(7));
+        let input = get_transferdomain_native_transfer_function().encode_input(&[
+            ethabi::Token::Address(from),
+            ethabi::Token::Address(to),
+            ethabi::Token::Uint(U256::one()),
+            ethabi::Token::String(String::from("bcrt1qxyz")),
+        ])?;
+        let tx = TransactionV2::Legacy(LegacyTransaction {
+            nonce: U256::zero(),
+            gas_price: U256::zero(),
+            gas_limit: U256::from(u64::MAX),
+            action: TransactionAction::Call(fixed_address),
+            value: U256::zero(),
+            input,
+            signature: TransactionSignature::new(27, H256::repeat_byte(1), H256::repeat_byte(1))
+                .unwrap(),
+        });
+        let block = block(H256::zero(), vec![tx.clone()]);
+        store.put_block(&block)?;
+        store.put_latest_block(Some(&block))?;
+        let mut receipt = receipt(tx.hash(), block.header.hash());
+        receipt.to = Some(fixed_address);
+        store.put_receipts(vec![receipt.clone()])?;
+        store.put_address_index(&receipt)?;
+
+        for address in [from, to] {
+            assert_eq!(
+                store.get_address_transactions(address, None, rocksdb::Direction::Reverse, 10)?,
+                vec![((U256::one(), 0), tx.hash())]
+            );
+        }
+
+        store.disconnect_latest_block()?;
+        for address in [from, to, fixed_address] {
+            assert!(store
+                .get_address_transactions(address, None, rocksdb::Direction::Reverse, 10)?
+                .is_empty());
+        }
+        Ok(())
+    }
+}
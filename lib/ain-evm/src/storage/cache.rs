@@ -6,7 +6,7 @@ use lru::LruCache;
 use parking_lot::Mutex;
 
 use super::traits::{BlockStorage, Rollback, TransactionStorage};
-use crate::Result;
+use crate::{metrics::record_cache_lookup, Result};
 
 #[derive(Debug)]
 pub struct Cache {
@@ -38,6 +38,18 @@ impl Cache {
         }
     }
 
+    /// Empties the LRU caches. The latest block is kept as it is not read from the block store.
+    pub fn clear(&self) {
+        self.transactions.lock().clear();
+        self.blocks.lock().clear();
+        self.block_hashes.lock().clear();
+        self.contract_code.lock().clear();
+    }
+
+    pub fn remove_block_hash(&self, block_hash: &H256) {
+        self.block_hashes.lock().pop(block_hash);
+    }
+
     pub fn put_transaction(&self, transaction: &TransactionV2) -> Result<()> {
         self.transactions
             .lock()
@@ -49,16 +61,14 @@ impl Cache {
 impl BlockStorage for Cache {
     fn get_block_by_number(&self, number: &U256) -> Result<Option<BlockAny>> {
         let block = self.blocks.lock().get(number).map(ToOwned::to_owned);
-        Ok(block)
+        Ok(record_cache_lookup("blocks", block))
     }
 
     fn get_block_by_hash(&self, block_hash: &H256) -> Result<Option<BlockAny>> {
-        self.block_hashes
-            .lock()
-            .get(block_hash)
-            .map_or(Ok(None), |block_number| {
-                self.get_block_by_number(block_number)
-            })
+        let block_number = self.block_hashes.lock().get(block_hash).copied();
+        record_cache_lookup("block_hashes", block_number).map_or(Ok(None), |block_number| {
+            self.get_block_by_number(&block_number)
+        })
     }
 
     fn put_block(&self, block: &BlockAny) -> Result<()> {
@@ -101,7 +111,7 @@ impl TransactionStorage for Cache {
 
     fn get_transaction_by_hash(&self, hash: &H256) -> Result<Option<TransactionV2>> {
         let transaction = self.transactions.lock().get(hash).map(ToOwned::to_owned);
-        Ok(transaction)
+        Ok(record_cache_lookup("transactions", transaction))
     }
 
     fn get_transaction_by_block_hash_and_index(
@@ -151,7 +161,8 @@ impl Rollback for Cache {
 
 impl Cache {
     pub fn get_code_by_hash(&self, hash: &H256) -> Result<Option<Vec<u8>>> {
-        Ok(self.contract_code.lock().get(hash).map(ToOwned::to_owned))
+        let code = self.contract_code.lock().get(hash).map(ToOwned::to_owned);
+        Ok(record_cache_lookup("contract_code", code))
     }
 
     pub fn put_code(&self, hash: H256, code: &[u8]) -> Result<()> {
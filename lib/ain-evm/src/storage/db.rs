@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use ain_db::{Column, ColumnName, Result, TypedColumn};
+use ain_db::{Column, ColumnName, DBError, Result, TypedColumn};
 use ethereum::BlockAny;
 use ethereum_types::{H160, H256, U256};
 
@@ -43,6 +43,22 @@ pub mod columns {
     #[derive(Debug)]
     /// Column family for database configuration
     pub struct Metadata;
+
+    #[derive(Debug)]
+    /// Column family for address transaction appearances
+    pub struct AddressTransactions;
+
+    #[derive(Debug)]
+    /// Column family for contract creator data
+    pub struct ContractCreator;
+
+    #[derive(Debug)]
+    /// Column family for senders of transactions from impersonated accounts
+    pub struct ImpersonatedSenders;
+
+    #[derive(Debug)]
+    /// Column family for contracts deployed by a transaction
+    pub struct CreatedContracts;
 }
 
 //
@@ -84,7 +100,23 @@ impl ColumnName for columns::Metadata {
     const NAME: &'static str = "metadata";
 }
 
-pub const COLUMN_NAMES: [&str; 9] = [
+impl ColumnName for columns::AddressTransactions {
+    const NAME: &'static str = "address_transactions";
+}
+
+impl ColumnName for columns::ContractCreator {
+    const NAME: &'static str = "contract_creator";
+}
+
+impl ColumnName for columns::ImpersonatedSenders {
+    const NAME: &'static str = "impersonated_senders";
+}
+
+impl ColumnName for columns::CreatedContracts {
+    const NAME: &'static str = "created_contracts";
+}
+
+pub const COLUMN_NAMES: [&str; 13] = [
     columns::Blocks::NAME,
     columns::Transactions::NAME,
     columns::Receipts::NAME,
@@ -94,6 +126,10 @@ pub const COLUMN_NAMES: [&str; 9] = [
     columns::AddressCodeMap::NAME,
     columns::BlockDeployedCodeHashes::NAME,
     columns::Metadata::NAME,
+    columns::AddressTransactions::NAME,
+    columns::ContractCreator::NAME,
+    columns::ImpersonatedSenders::NAME,
+    columns::CreatedContracts::NAME,
 ];
 
 //
@@ -229,6 +265,67 @@ impl Column for columns::Metadata {
     }
 }
 
+impl Column for columns::AddressTransactions {
+    type Index = (H160, U256, u64); // Address, block number and tx index
+
+    fn key(index: &Self::Index) -> Result<Vec<u8>> {
+        let mut u256_bytes = [0_u8; 32];
+        index.1.to_big_endian(&mut u256_bytes);
+
+        let mut bytes = Vec::with_capacity(20 + 32 + 8);
+        bytes.extend_from_slice(&index.0.to_fixed_bytes());
+        bytes.extend_from_slice(&u256_bytes);
+        bytes.extend_from_slice(&index.2.to_be_bytes());
+        Ok(bytes)
+    }
+
+    fn get_key(raw_key: Box<[u8]>) -> Result<Self::Index> {
+        let address = H160::from_slice(&raw_key[0..20]);
+        let block_number = U256::from_big_endian(&raw_key[20..52]);
+        let tx_index = raw_key[52..60]
+            .try_into()
+            .map(u64::from_be_bytes)
+            .map_err(|_| DBError::ParseKey)?;
+        Ok((address, block_number, tx_index))
+    }
+}
+
+impl Column for columns::ContractCreator {
+    type Index = H160;
+
+    fn key(index: &Self::Index) -> Result<Vec<u8>> {
+        Ok(index.to_fixed_bytes().to_vec())
+    }
+
+    fn get_key(raw_key: Box<[u8]>) -> Result<Self::Index> {
+        Ok(Self::Index::from_slice(&raw_key))
+    }
+}
+
+impl Column for columns::ImpersonatedSenders {
+    type Index = H256;
+
+    fn key(index: &Self::Index) -> Result<Vec<u8>> {
+        Ok(index.to_fixed_bytes().to_vec())
+    }
+
+    fn get_key(raw_key: Box<[u8]>) -> Result<Self::Index> {
+        Ok(Self::Index::from_slice(&raw_key))
+    }
+}
+
+impl Column for columns::CreatedContracts {
+    type Index = H256;
+
+    fn key(index: &Self::Index) -> Result<Vec<u8>> {
+        Ok(index.to_fixed_bytes().to_vec())
+    }
+
+    fn get_key(raw_key: Box<[u8]>) -> Result<Self::Index> {
+        Ok(Self::Index::from_slice(&raw_key))
+    }
+}
+
 //
 // TypedColumn impl
 //
@@ -263,3 +360,19 @@ impl TypedColumn for columns::AddressCodeMap {
 impl TypedColumn for columns::BlockDeployedCodeHashes {
     type Type = H256;
 }
+
+impl TypedColumn for columns::AddressTransactions {
+    type Type = H256; // Tx hash
+}
+
+impl TypedColumn for columns::ContractCreator {
+    type Type = (H256, H160); // Creation tx hash and creator
+}
+
+impl TypedColumn for columns::ImpersonatedSenders {
+    type Type = H160;
+}
+
+impl TypedColumn for columns::CreatedContracts {
+    type Type = Vec<H160>;
+}
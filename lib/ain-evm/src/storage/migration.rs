@@ -48,3 +48,37 @@ impl MigrationV1 {
         Ok(())
     }
 }
+
+/// Migration for version 2.
+/// Context:
+/// Index transactions by the addresses they touch and contracts by their creator.
+pub struct MigrationV2;
+
+impl Migration<BlockStore> for MigrationV2 {
+    fn version(&self) -> u32 {
+        2
+    }
+
+    fn migrate(&self, store: &BlockStore) -> DBResult<()> {
+        self.migrate_address_index(store)
+            .map_err(|e| DBError::Custom(format_err!("{e}")))?;
+        Ok(())
+    }
+}
+
+impl MigrationV2 {
+    /// Backfills the address transactions and contract creator indexes from stored receipts.
+    fn migrate_address_index(&self, store: &BlockStore) -> Result<()> {
+        let receipts_cf = store.column::<columns::Receipts>();
+
+        receipts_cf
+            .iter(None, rocksdb::Direction::Forward)?
+            .par_bridge()
+            .try_for_each(|el| {
+                let (_, receipt) = el?;
+                store.put_address_index(&receipt)
+            })?;
+
+        Ok(())
+    }
+}
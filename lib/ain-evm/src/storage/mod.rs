@@ -6,6 +6,9 @@ pub mod traits;
 
 use std::{collections::HashMap, path::Path};
 
+use ain_db::version::DBVersionControl;
+pub use rocksdb::Direction;
+
 use ethereum::{BlockAny, TransactionV2};
 use ethereum_types::{H160, H256, U256};
 
@@ -14,7 +17,10 @@ use self::{
     cache::Cache,
     traits::{BlockStorage, FlushableStorage, ReceiptStorage, Rollback, TransactionStorage},
 };
-use crate::{log::LogIndex, receipt::Receipt, storage::traits::LogStorage, Result};
+use crate::{
+    log::LogIndex, receipt::Receipt, storage::traits::LogStorage,
+    transaction::enable_impersonation, Result,
+};
 
 #[derive(Debug)]
 pub struct Storage {
@@ -211,6 +217,67 @@ impl Storage {
 }
 
 impl Storage {
+    pub fn get_address_transactions(
+        &self,
+        address: H160,
+        from: Option<(U256, u64)>,
+        direction: Direction,
+        limit: usize,
+    ) -> Result<Vec<((U256, u64), H256)>> {
+        self.blockstore
+            .get_address_transactions(address, from, direction, limit)
+    }
+
+    pub fn get_contract_creator(&self, address: &H160) -> Result<Option<(H256, H160)>> {
+        self.blockstore.get_contract_creator(address)
+    }
+
+    pub fn put_created_contracts(&self, tx_hash: H256, contracts: &[H160]) -> Result<()> {
+        self.blockstore.put_created_contracts(tx_hash, contracts)
+    }
+}
+
+impl Storage {
+    /// Overwrites the latest block in place and keeps the cache in sync.
+    /// Used by the regtest dev RPCs to swap in a modified state root.
+    pub fn replace_latest_block(&self, block: &BlockAny) -> Result<()> {
+        if let Some(replaced) = self.get_latest_block()? {
+            self.cache.remove_block_hash(&replaced.header.hash());
+        }
+        self.blockstore.replace_block(block)?;
+        self.cache.put_block(block)?;
+        self.put_latest_block(Some(block))
+    }
+
+    pub fn put_impersonated_sender(&self, hash: H256, sender: H160) -> Result<()> {
+        self.blockstore.put_impersonated_sender(hash, sender)
+    }
+
+    pub fn delete_impersonated_sender(&self, hash: &H256) -> Result<()> {
+        self.blockstore.delete_impersonated_sender(hash)
+    }
+
+    /// Resolves the impersonated senders persisted by the regtest dev RPCs. Must only be
+    /// called on regtest.
+    pub fn load_impersonated_senders(&self) -> Result<()> {
+        enable_impersonation();
+        self.blockstore.load_impersonated_senders()
+    }
+}
+
+impl Storage {
+    pub fn db_stats(&self) -> Result<Vec<(&'static str, u64)>> {
+        self.blockstore.db_stats()
+    }
+
+    pub fn db_version(&self) -> Result<u32> {
+        Ok(self.blockstore.get_version()?)
+    }
+
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
     pub fn dump_db(&self, arg: DumpArg, from: Option<&str>, limit: usize) -> Result<String> {
         self.blockstore.dump(&arg, from, limit)
     }
@@ -72,6 +72,9 @@ pub enum BlockscoutCallInner {
         init: Vec<u8>,
         #[serde(flatten)]
         res: CreateResult,
+        /// Created with CREATE2 rather than CREATE.
+        #[serde(skip)]
+        create2: bool,
     },
     SelfDestruct {
         #[serde(skip)]
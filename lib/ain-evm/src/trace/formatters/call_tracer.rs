@@ -74,29 +74,31 @@ impl super::ResponseFormatter for Formatter {
                                 res,
                                 value: Some(value),
                             },
-                            BlockscoutCallInner::Create { init, res } => CallTracerInner::Create {
-                                input: init,
-                                error: match res {
-                                    CreateResult::Success { .. } => None,
-                                    CreateResult::Error { ref error } => Some(error.clone()),
-                                },
-                                to: match res {
-                                    CreateResult::Success {
-                                        created_contract_address_hash,
-                                        ..
-                                    } => Some(created_contract_address_hash),
-                                    CreateResult::Error { .. } => None,
-                                },
-                                output: match res {
-                                    CreateResult::Success {
-                                        created_contract_code,
-                                        ..
-                                    } => Some(created_contract_code),
-                                    CreateResult::Error { .. } => None,
-                                },
-                                value,
-                                call_type: "CREATE".as_bytes().to_vec(),
-                            },
+                            BlockscoutCallInner::Create { init, res, .. } => {
+                                CallTracerInner::Create {
+                                    input: init,
+                                    error: match res {
+                                        CreateResult::Success { .. } => None,
+                                        CreateResult::Error { ref error } => Some(error.clone()),
+                                    },
+                                    to: match res {
+                                        CreateResult::Success {
+                                            created_contract_address_hash,
+                                            ..
+                                        } => Some(created_contract_address_hash),
+                                        CreateResult::Error { .. } => None,
+                                    },
+                                    output: match res {
+                                        CreateResult::Success {
+                                            created_contract_code,
+                                            ..
+                                        } => Some(created_contract_code),
+                                        CreateResult::Error { .. } => None,
+                                    },
+                                    value,
+                                    call_type: "CREATE".as_bytes().to_vec(),
+                                }
+                            }
                             BlockscoutCallInner::SelfDestruct { balance, to } => {
                                 CallTracerInner::SelfDestruct {
                                     value: balance,
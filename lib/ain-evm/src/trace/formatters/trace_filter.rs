@@ -83,7 +83,7 @@ impl super::ResponseFormatter for Formatter {
                         transaction_hash: H256::default(),
                         transaction_position: eth_tx_index as u32,
                     },
-                    CallInner::Create { init, res } => {
+                    CallInner::Create { init, res, .. } => {
                         TransactionTrace {
                             action: TransactionTraceAction::Create {
                                 creation_method: CreateType::Create,
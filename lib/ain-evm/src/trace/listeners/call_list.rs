@@ -19,6 +19,7 @@ use std::{collections::btree_map::BTreeMap, vec, vec::Vec};
 use crate::trace::{
     formatters::blockscout::{BlockscoutCall as Call, BlockscoutCallInner as CallInner},
     tracing::{
+        evm::CreateScheme,
         runtime::{Capture, ExitError, ExitReason, ExitSucceed},
         Event, EvmEvent, GasometerEvent, Listener as ListenerT, RuntimeEvent, StepEventFilter,
     },
@@ -96,6 +97,8 @@ struct Context {
     data: Vec<u8>,
     // to/create address
     to: H160,
+    // whether the contract is created with CREATE2
+    create2: bool,
 }
 
 impl Default for Listener {
@@ -171,6 +174,7 @@ impl Listener {
                         inner: CallInner::Create {
                             init: context.data,
                             res,
+                            create2: context.create2,
                         },
                     }
                 }
@@ -290,6 +294,7 @@ impl Listener {
                     start_gas: None,
                     data,
                     to: address,
+                    create2: false,
                 });
 
                 self.entries_next_index += 1;
@@ -316,6 +321,7 @@ impl Listener {
                     start_gas: None,
                     data: init_code,
                     to: address,
+                    create2: false,
                 });
 
                 self.entries_next_index += 1;
@@ -342,6 +348,7 @@ impl Listener {
                     start_gas: None,
                     data: init_code,
                     to: address,
+                    create2: true,
                 });
 
                 self.entries_next_index += 1;
@@ -394,6 +401,7 @@ impl Listener {
 
                         data: input.to_vec(),
                         to: code_address,
+                        create2: false,
                     });
 
                     self.entries_next_index += 1;
@@ -405,7 +413,7 @@ impl Listener {
             EvmEvent::Create {
                 caller,
                 address,
-                // scheme,
+                scheme,
                 value,
                 init_code,
                 ..
@@ -433,6 +441,7 @@ impl Listener {
                         start_gas: None,
                         data: init_code.to_vec(),
                         to: address,
+                        create2: matches!(scheme, CreateScheme::Create2 { .. }),
                     });
 
                     self.entries_next_index += 1;
@@ -579,6 +588,7 @@ impl Listener {
                             inner: CallInner::Create {
                                 init: context.data,
                                 res,
+                                create2: context.create2,
                             },
                         }
                     }
@@ -989,6 +999,41 @@ mod tests {
         assert_eq!(listener.entries[0].len(), 1);
     }
 
+    // Nested CREATE2 is flagged, the transaction root is not.
+    #[test]
+    fn create2_scheme() {
+        let mut listener = Listener::default();
+        do_transact_create_event(&mut listener);
+        do_gasometer_event(&mut listener);
+        do_evm_create_event(&mut listener);
+        do_runtime_step_event(&mut listener);
+        do_runtime_step_result_event(&mut listener);
+        listener.evm_event(EvmEvent::Create {
+            caller: H160::default(),
+            address: H160::repeat_byte(1),
+            scheme: CreateScheme::Create2 {
+                caller: H160::default(),
+                code_hash: H256::default(),
+                salt: H256::default(),
+            },
+            value: U256::zero(),
+            init_code: Vec::new(),
+            target_gas: None,
+        });
+        do_exit_event(&mut listener);
+        do_exit_event(&mut listener);
+        listener.finish_transaction();
+
+        let create2 = listener.entries[0]
+            .values()
+            .map(|call| match call.inner {
+                CallInner::Create { create2, .. } => create2,
+                _ => panic!("expected a create"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(create2, vec![false, true]);
+    }
+
     // Call Context Nested
 
     // Nested call early exit before stepping.
@@ -12,6 +12,7 @@ use crate::{
     block::INITIAL_BASE_FEE,
     core::EthCallArgs,
     executor::{AinExecutor, ExecutorContext},
+    metrics::record_cache_lookup,
     storage::{traits::BlockStorage, Storage},
     trace::{
         formatters::{
@@ -436,12 +437,12 @@ impl TracerService {
 
     fn get_tx_trace(&self, key: (H256, TracerInput)) -> Option<TransactionTrace> {
         let mut cache = self.tracer_cache.lock();
-        cache.tx_cache.get(&key).cloned()
+        record_cache_lookup("trace_tx", cache.tx_cache.get(&key).cloned())
     }
 
     fn get_block_trace(&self, key: (H256, TracerInput)) -> Option<Vec<(H256, TransactionTrace)>> {
         let mut cache = self.tracer_cache.lock();
-        cache.block_cache.get(&key).cloned()
+        record_cache_lookup("trace_block", cache.block_cache.get(&key).cloned())
     }
 
     fn cache_tx_trace(&self, key: (H256, TracerInput), trace_tx: TransactionTrace) {
@@ -457,4 +458,10 @@ impl TracerService {
         let mut cache = self.tracer_cache.lock();
         cache.block_cache.put(key, block_trace);
     }
+
+    pub fn clear_cache(&self) {
+        let mut cache = self.tracer_cache.lock();
+        cache.tx_cache.clear();
+        cache.block_cache.clear();
+    }
 }
@@ -5,7 +5,7 @@ use ethereum_types::U256;
 use log::trace;
 use lru::LruCache;
 
-use crate::{transaction::SignedTx, Result};
+use crate::{metrics::record_cache_lookup, transaction::SignedTx, Result};
 
 #[derive(Debug, Default)]
 pub struct TransactionCache {
@@ -20,6 +20,12 @@ impl TransactionCache {
             tx_validation_cache: TxValidationCache::default(),
         }
     }
+
+    /// Empties the signed transaction and validation caches.
+    pub fn clear(&self) {
+        self.signed_tx_cache.inner.lock().clear();
+        self.tx_validation_cache.stateless.lock().clear();
+    }
 }
 
 /// Signed transactions cache methods
@@ -61,7 +67,8 @@ impl TransactionCache {
 /// Transaction validation cache methods
 impl TransactionCache {
     pub fn get_stateless(&self, key: &str) -> Option<ValidateTxInfo> {
-        self.tx_validation_cache.stateless.lock().get(key).cloned()
+        let info = self.tx_validation_cache.stateless.lock().get(key).cloned();
+        record_cache_lookup("tx_validation", info)
     }
 
     pub fn set_stateless(&self, key: String, value: ValidateTxInfo) -> ValidateTxInfo {
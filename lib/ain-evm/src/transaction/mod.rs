@@ -7,6 +7,7 @@ use ethereum::{
     TransactionSignature, TransactionV2,
 };
 use ethereum_types::{H160, H256, U256};
+use parking_lot::RwLock;
 use rlp::RlpStream;
 use sha3::Digest;
 
@@ -21,6 +22,45 @@ pub const LOWER_H256: H256 = H256([
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
 ]);
 
+lazy_static::lazy_static! {
+    // Senders of the regtest transactions sent from impersonated accounts, by tx hash.
+    // Their placeholder signatures do not recover to the sender.
+    static ref IMPERSONATED_SENDERS: RwLock<HashMap<H256, H160>> = RwLock::new(HashMap::new());
+}
+
+/// Set on regtest, the only network resolving impersonated senders.
+static IMPERSONATION_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Resolves the registered impersonated senders. Must only be called on regtest, as it skips
+/// the signature recovery of these transactions.
+pub fn enable_impersonation() {
+    IMPERSONATION_ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Records the sender of a transaction sent from an impersonated account. Entries are never
+/// evicted so that the sender keeps resolving for the lifetime of the node; the block store
+/// persists and reloads them across restarts.
+pub fn register_impersonated_sender(hash: H256, sender: H160) {
+    IMPERSONATED_SENDERS.write().insert(hash, sender);
+}
+
+/// Removes the sender of a transaction that failed to be published.
+pub fn unregister_impersonated_sender(hash: &H256) {
+    IMPERSONATED_SENDERS.write().remove(hash);
+}
+
+fn impersonated_sender(tx: &TransactionV2) -> Option<(H256, H160)> {
+    if !IMPERSONATION_ENABLED.load(Ordering::SeqCst) {
+        return None;
+    }
+    let senders = IMPERSONATED_SENDERS.read();
+    if senders.is_empty() {
+        return None;
+    }
+    let hash = tx.hash();
+    senders.get(&hash).map(|sender| (hash, *sender))
+}
+
 #[derive(Clone, Debug)]
 pub struct LegacyUnsignedTransaction {
     pub nonce: U256,
@@ -131,6 +171,13 @@ impl TryFrom<TransactionV2> for SignedTx {
     type Error = TransactionError;
 
     fn try_from(src: TransactionV2) -> Result<Self, Self::Error> {
+        if let Some((hash, sender)) = impersonated_sender(&src) {
+            return Ok(SignedTx {
+                transaction: src,
+                sender,
+                hash_cache: Cell::new(Some(hash)),
+            });
+        }
         let pubkey = match &src {
             TransactionV2::Legacy(tx) => {
                 let msg = ethereum::LegacyTransactionMessage {
@@ -361,8 +408,10 @@ impl SignedTx {
 use std::{
     cell::Cell,
     cmp::min,
+    collections::HashMap,
     convert::{TryFrom, TryInto},
     fmt,
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 #[derive(Debug)]
@@ -424,7 +473,13 @@ mod tests {
     use ethereum_types::{H160, H256, U256, U64};
     use serde::Deserialize;
 
-    use crate::{bytes::Bytes, transaction::SignedTx};
+    use crate::{
+        bytes::Bytes,
+        transaction::{
+            cache::{SignedTxCache, TransactionCache, TxValidationCache},
+            SignedTx,
+        },
+    };
 
     #[test]
     fn test_signed_tx_from_raw_tx() {
@@ -545,6 +600,54 @@ mod tests {
         Ok(())
     }
 
+    fn impersonated_tx(sender: H160, nonce: u64) -> ethereum::TransactionV2 {
+        ethereum::TransactionV2::EIP1559(ethereum::EIP1559Transaction {
+            chain_id: 1133,
+            nonce: U256::from(nonce),
+            max_priority_fee_per_gas: U256::zero(),
+            max_fee_per_gas: U256::from(10_000_000_000u64),
+            gas_limit: U256::from(21000),
+            action: ethereum::TransactionAction::Call(H160::repeat_byte(0x22)),
+            value: U256::one(),
+            input: Vec::new(),
+            access_list: Vec::new(),
+            odd_y_parity: false,
+            r: H256::from(sender),
+            s: H256::from_low_u64_be(1),
+        })
+    }
+
+    #[test]
+    fn test_impersonated_sender_survives_cache_eviction_and_clear() {
+        let sender = H160::repeat_byte(0x11);
+        let tx = impersonated_tx(sender, 0);
+        assert!(SignedTx::try_from(tx.clone()).map_or(true, |signed| signed.sender != sender));
+
+        super::register_impersonated_sender(tx.hash(), sender);
+        // Only resolved on regtest
+        assert!(SignedTx::try_from(tx.clone()).map_or(true, |signed| signed.sender != sender));
+        super::enable_impersonation();
+        let signed = SignedTx::try_from(tx.clone()).unwrap();
+        assert_eq!(signed.sender, sender);
+        assert_eq!(signed.hash(), tx.hash());
+
+        let cache = TransactionCache {
+            signed_tx_cache: SignedTxCache::new(1),
+            tx_validation_cache: TxValidationCache::new(1),
+        };
+        let key = hex::encode(tx.encode());
+        assert_eq!(cache.try_get_or_create(&key).unwrap().sender, sender);
+
+        // Evicts the impersonated entry
+        let other = impersonated_tx(H160::repeat_byte(0x33), 1);
+        super::register_impersonated_sender(other.hash(), H160::repeat_byte(0x33));
+        cache.try_get_or_create_from_tx(&other).unwrap();
+        assert_eq!(cache.try_get_or_create(&key).unwrap().sender, sender);
+
+        cache.clear();
+        assert_eq!(cache.try_get_or_create(&key).unwrap().sender, sender);
+    }
+
     #[test]
     fn test_tx_type() -> Result<(), Box<dyn Error>> {
         let raw_legacy_tx = "0xf90152808522ecb25c008307a1208080b8fe608060405234801561001057600080fd5b5060df8061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b603c6038366004605f565b604e565b60405190815260200160405180910390f35b600060588284607f565b9392505050565b600080604083850312156070578182fd5b50508035926020909101359150565b600081600019048311821515161560a457634e487b7160e01b81526011600452602481fd5b50029056fea2646970667358221220223df7833fd08eb1cd3ce363a9c4cb4619c1068a5f5517ea8bb862ed45d994f764736f6c634300080200338208fda0f6d889435dbfe9ea49b984fe1cab94cae59fa774b602256abc9f657c2abdd5bea0609176ffd6023896913cd9e12b61a004a970187cc53623ef49afb0417cb44929";
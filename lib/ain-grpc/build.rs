@@ -10,11 +10,16 @@ use std::{
 };
 
 use anyhow::{format_err, Result};
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::{Span, TokenStream};
 use prost_build::{Config, Service, ServiceGenerator};
 use quote::{quote, ToTokens};
 use regex::Regex;
-use syn::{Attribute, Fields, GenericArgument, Ident, Item, ItemStruct, PathArguments, Type};
+use serde_json::{json, Map, Value};
+use syn::{
+    ext::IdentExt, Attribute, Expr, ExprLit, Fields, FnArg, GenericArgument, Ident, Item, ItemEnum,
+    ItemStruct, Lit, Meta, Pat, PathArguments, ReturnType, TraitItem, TraitItemFn, Type, UseTree,
+};
 
 fn main() -> Result<()> {
     let manifest_dir_path = PathBuf::from(env::var("CARGO_MANIFEST_DIR")?);
@@ -38,6 +43,11 @@ fn main() -> Result<()> {
         Path::new(&proto_include),
     );
     modify_generate_code(methods, &Path::new(&proto_rs_target_path).join("types.rs"));
+    generate_openrpc(
+        &manifest_dir_path,
+        &proto_rs_target_path.join("types.rs"),
+        &out_dir,
+    )?;
 
     println!(
         "cargo:rerun-if-changed={}",
@@ -407,3 +417,911 @@ fn get_path_bracketed_ty_simple(ty: &Type) -> Type {
         _ => panic!("unsupported type {}", quote!(#ty)),
     }
 }
+
+/// RPC traits described by the OpenRPC document served through `rpc_discover`.
+const OPENRPC_TRAITS: &[&str] = &[
+    "rpc/eth.rs",
+    "rpc/debug.rs",
+    "rpc/net.rs",
+    "rpc/web3.rs",
+    "rpc/personal.rs",
+    "rpc/txpool.rs",
+    "rpc/dev.rs",
+    "rpc/ots.rs",
+    "rpc/metachain.rs",
+    "rpc/admin.rs",
+    "rpc/discover.rs",
+    "subscription/eth.rs",
+];
+
+/// Schemas of types with a hand written `Deserialize` implementation, or defined in
+/// external crates.
+const OPENRPC_SCHEMA_OVERRIDES: &[(&str, &str)] = &[
+    (
+        "AccessList",
+        r#"{
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "address": { "type": "string", "pattern": "^0x[0-9a-fA-F]{40}$" },
+                "storageKeys": { "type": "array", "items": { "type": "string", "pattern": "^0x[0-9a-fA-F]{64}$" } }
+            },
+            "required": ["address", "storageKeys"]
+        }
+    }"#,
+    ),
+    (
+        "AccessListItem",
+        r#"{
+        "type": "object",
+        "properties": {
+            "address": { "type": "string", "pattern": "^0x[0-9a-fA-F]{40}$" },
+            "storageKeys": { "type": "array", "items": { "type": "string", "pattern": "^0x[0-9a-fA-F]{64}$" } }
+        },
+        "required": ["address", "storageKeys"]
+    }"#,
+    ),
+    (
+        "BlockNumber",
+        r#"{
+        "oneOf": [
+            { "title": "Block number", "type": "string", "pattern": "^0x([1-9a-fA-F][0-9a-fA-F]*|0)$" },
+            { "title": "Block tag", "type": "string", "enum": ["latest", "earliest", "pending", "safe", "finalized"] },
+            {
+                "title": "Block number object",
+                "type": "object",
+                "properties": { "blockNumber": { "type": "string", "pattern": "^0x([1-9a-fA-F][0-9a-fA-F]*|0)$" } },
+                "required": ["blockNumber"]
+            },
+            {
+                "title": "Block hash object",
+                "type": "object",
+                "properties": {
+                    "blockHash": { "type": "string", "pattern": "^0x[0-9a-fA-F]{64}$" },
+                    "requireCanonical": { "type": "boolean" }
+                },
+                "required": ["blockHash"]
+            }
+        ]
+    }"#,
+    ),
+];
+
+/// Generates the OpenRPC document of the `OPENRPC_TRAITS` namespaces from their `#[rpc]`
+/// traits and the serde definitions of their parameter and result types. Types without a
+/// known schema are reported as build warnings.
+/// The document is written to `OUT_DIR` for `rpc_discover`, and also to `OPENRPC_SPEC_PATH`
+/// when set.
+fn generate_openrpc(manifest_dir: &Path, proto_types: &Path, out_dir: &Path) -> Result<()> {
+    let src_path = manifest_dir.join("src");
+    let evm_src_path = manifest_dir
+        .parent()
+        .ok_or(format_err!("path err: no parent"))?
+        .join("ain-evm")
+        .join("src");
+
+    let mut generator = OpenRpcGenerator::default();
+    generator.load_crate(&src_path, "crate")?;
+    generator.load_crate(&evm_src_path, "ain_evm")?;
+    generator.load_file(proto_types, "crate::codegen::types".to_string(), "crate")?;
+
+    let mut methods = Vec::new();
+    for path in OPENRPC_TRAITS {
+        let path = src_path.join(path);
+        let (file, scope) = parse_module(&path, module_path(&src_path, &path, "crate"), "crate")?;
+        methods.extend(generator.methods(&file, &scope));
+    }
+
+    let document = json!({
+        "openrpc": "1.2.6",
+        "info": {
+            "title": "Metachain JSON-RPC API",
+            "version": env::var("CARGO_PKG_VERSION")?,
+        },
+        "methods": methods,
+        "components": { "schemas": generator.schemas },
+    });
+    let contents = serde_json::to_string_pretty(&document)?;
+    fs::write(out_dir.join("openrpc.json"), &contents)?;
+
+    println!("cargo:rerun-if-env-changed=OPENRPC_SPEC_PATH");
+    if let Some(path) = env::var_os("OPENRPC_SPEC_PATH").map(PathBuf::from) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &contents)?;
+    }
+    Ok(())
+}
+
+/// Module path and imports of a source file, to resolve the types it refers to.
+struct Scope {
+    module: String,
+    imports: HashMap<String, String>,
+}
+
+impl Scope {
+    fn new(file: &syn::File, module: String, krate: &str) -> Self {
+        let mut imports = HashMap::new();
+        for item in &file.items {
+            if let Item::Use(item) = item {
+                collect_imports(&item.tree, &mut Vec::new(), &module, krate, &mut imports);
+            }
+        }
+        Self { module, imports }
+    }
+}
+
+fn collect_imports(
+    tree: &UseTree,
+    prefix: &mut Vec<String>,
+    module: &str,
+    krate: &str,
+    imports: &mut HashMap<String, String>,
+) {
+    let mut insert = |prefix: &[String], ident: &Ident, alias: &Ident| {
+        let mut segments = prefix.to_vec();
+        segments.push(ident.to_string());
+        imports.insert(alias.to_string(), absolute_path(&segments, module, krate));
+    };
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            collect_imports(&path.tree, prefix, module, krate, imports);
+            prefix.pop();
+        }
+        UseTree::Name(name) => insert(prefix, &name.ident, &name.ident),
+        UseTree::Rename(rename) => insert(prefix, &rename.ident, &rename.rename),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                collect_imports(tree, prefix, module, krate, imports);
+            }
+        }
+        UseTree::Glob(_) => {}
+    }
+}
+
+/// Resolves `crate`, `self` and `super` prefixes of a path used in `module`.
+fn absolute_path(segments: &[String], module: &str, krate: &str) -> String {
+    let mut base: Vec<&str> = Vec::new();
+    let mut rest = segments;
+    while let Some((first, tail)) = rest.split_first() {
+        match first.as_str() {
+            "crate" if base.is_empty() => base.push(krate),
+            "self" if base.is_empty() => base.extend(module.split("::")),
+            "super" => {
+                if base.is_empty() {
+                    base.extend(module.split("::"));
+                }
+                base.pop();
+            }
+            _ => break,
+        }
+        rest = tail;
+    }
+    base.into_iter()
+        .map(str::to_string)
+        .chain(rest.iter().cloned())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn module_path(root: &Path, path: &Path, krate: &str) -> String {
+    let mut segments = vec![krate.to_string()];
+    if let Ok(relative) = path.with_extension("").strip_prefix(root) {
+        segments.extend(
+            relative
+                .iter()
+                .map(|segment| segment.to_string_lossy().to_string()),
+        );
+    }
+    if matches!(segments.last().map(String::as_str), Some("lib" | "mod")) {
+        segments.pop();
+    }
+    segments.join("::")
+}
+
+fn parse_module(path: &Path, module: String, krate: &str) -> Result<(syn::File, Rc<Scope>)> {
+    let contents = fs::read_to_string(path)?;
+    let file = syn::parse_file(&contents)
+        .map_err(|e| format_err!("failed to parse {}: {e}", path.display()))?;
+    let scope = Rc::new(Scope::new(&file, module, krate));
+    Ok((file, scope))
+}
+
+struct TypeDef {
+    item: Item,
+    scope: Rc<Scope>,
+    /// Whether `Serialize` is implemented by hand.
+    custom_serialize: bool,
+}
+
+impl TypeDef {
+    fn ident(&self) -> String {
+        match &self.item {
+            Item::Struct(item) => item.ident.to_string(),
+            Item::Enum(item) => item.ident.to_string(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct OpenRpcGenerator {
+    /// Structs and enums by absolute path.
+    types: HashMap<String, TypeDef>,
+    paths_by_name: HashMap<String, Vec<String>>,
+    /// Component names of the types added to `schemas`.
+    names: HashMap<String, String>,
+    schemas: Map<String, Value>,
+    /// Schemas of the type parameters of the generic type being walked.
+    generics: HashMap<String, Value>,
+    /// Trait and method being described, for warnings.
+    context: String,
+}
+
+impl OpenRpcGenerator {
+    fn load_crate(&mut self, root: &Path, krate: &str) -> Result<()> {
+        let mut paths = Vec::new();
+        visit_files(root, &mut |entry: &DirEntry| {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                paths.push(path);
+            }
+        })?;
+        // Keep the component names stable
+        paths.sort();
+        for path in paths {
+            println!("cargo:rerun-if-changed={}", path.display());
+            self.load_file(&path, module_path(root, &path, krate), krate)?;
+        }
+        Ok(())
+    }
+
+    fn load_file(&mut self, path: &Path, module: String, krate: &str) -> Result<()> {
+        let (file, scope) = parse_module(path, module, krate)?;
+        let custom_serialize = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Impl(item) => Some(item),
+                _ => None,
+            })
+            .filter(|item| {
+                item.trait_.as_ref().is_some_and(|(_, path, _)| {
+                    path.segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "Serialize")
+                })
+            })
+            .map(|item| item.self_ty.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+
+        for item in file.items {
+            let ident = match &item {
+                Item::Struct(item) => item.ident.to_string(),
+                Item::Enum(item) => item.ident.to_string(),
+                _ => continue,
+            };
+            let path = format!("{}::{ident}", scope.module);
+            self.paths_by_name
+                .entry(ident.clone())
+                .or_default()
+                .push(path.clone());
+            self.types.insert(
+                path,
+                TypeDef {
+                    item,
+                    scope: scope.clone(),
+                    custom_serialize: custom_serialize.contains(&ident),
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, path: &syn::Path, scope: &Scope) -> Option<String> {
+        let segments = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>();
+        let (first, rest) = segments.split_first()?;
+        let absolute = match scope.imports.get(first) {
+            Some(import) => std::iter::once(import.clone())
+                .chain(rest.iter().cloned())
+                .collect::<Vec<_>>()
+                .join("::"),
+            None if rest.is_empty() => format!("{}::{first}", scope.module),
+            None => absolute_path(&segments, &scope.module, "crate"),
+        };
+        if self.types.contains_key(&absolute) {
+            return Some(absolute);
+        }
+        // Re-exports and glob imports, fall back to the first type with that name
+        self.paths_by_name
+            .get(segments.last()?)
+            .and_then(|paths| paths.first())
+            .cloned()
+    }
+
+    fn methods(&mut self, file: &syn::File, scope: &Rc<Scope>) -> Vec<Value> {
+        let mut methods = Vec::new();
+        for item in &file.items {
+            let Item::Trait(rpc_trait) = item else {
+                continue;
+            };
+            let trait_name = rpc_trait.ident.to_string();
+            let Some(namespace) = rpc_trait
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("rpc"))
+                .and_then(|attr| capture(attr, r#"namespace\s*=\s*"(\w+)""#))
+            else {
+                continue;
+            };
+
+            for trait_item in &rpc_trait.items {
+                let TraitItem::Fn(method) = trait_item else {
+                    continue;
+                };
+                self.context = format!("{trait_name}::{}", method.sig.ident);
+                for attr in &method.attrs {
+                    if attr.path().is_ident("method") {
+                        let Some(name) = capture(attr, r#"\bname\s*=\s*"(\w+)""#) else {
+                            continue;
+                        };
+                        let mut object =
+                            self.method_object(format!("{namespace}_{name}"), method, scope);
+                        let result = match &method.sig.output {
+                            ReturnType::Type(_, ty) => match generic_args(ty).first() {
+                                Some(ty) => self.schema(ty, scope),
+                                None => json!({}),
+                            },
+                            ReturnType::Default => json!({ "type": "null" }),
+                        };
+                        object["result"] = json!({ "name": "result", "schema": result });
+                        let aliases = capture(attr, r"\baliases\s*=\s*\[([^\]]*)\]")
+                            .map(|aliases| {
+                                aliases
+                                    .split(',')
+                                    .map(|alias| alias.trim().trim_matches('"').to_string())
+                                    .filter(|alias| !alias.is_empty())
+                                    .collect::<Vec<_>>()
+                            })
+                            .unwrap_or_default();
+                        let aliased = aliases
+                            .into_iter()
+                            .map(|alias| {
+                                let mut object = object.clone();
+                                object["name"] = json!(alias);
+                                object
+                            })
+                            .collect::<Vec<_>>();
+                        methods.push(object);
+                        methods.extend(aliased);
+                    } else if attr.path().is_ident("subscription") {
+                        let (Some(name), Some(notification), Some(unsubscribe)) = (
+                            capture(attr, r#"\bname\s*=\s*"(\w+)""#),
+                            capture(attr, r#"=>\s*"(\w+)""#),
+                            capture(attr, r#"\bunsubscribe\s*=\s*"(\w+)""#),
+                        ) else {
+                            continue;
+                        };
+                        let item = capture(attr, r"\bitem\s*=\s*([^,]+)")
+                            .and_then(|item| syn::parse_str::<Type>(&item).ok())
+                            .map_or(json!({}), |ty| self.schema(&ty, scope));
+
+                        let mut object =
+                            self.method_object(format!("{namespace}_{name}"), method, scope);
+                        object["result"] = json!({
+                            "name": "subscriptionId",
+                            "schema": { "type": "string" },
+                        });
+                        object["x-notification"] = json!({
+                            "method": format!("{namespace}_{notification}"),
+                            "result": { "name": "result", "schema": item },
+                        });
+                        methods.push(object);
+                        methods.push(json!({
+                            "name": format!("{namespace}_{unsubscribe}"),
+                            "summary": format!("Cancels a subscription of `{namespace}_{name}`."),
+                            "params": [{
+                                "name": "subscriptionId",
+                                "required": true,
+                                "schema": { "type": "string" },
+                            }],
+                            "result": { "name": "result", "schema": { "type": "boolean" } },
+                        }));
+                    }
+                }
+            }
+        }
+        methods
+    }
+
+    fn method_object(&mut self, name: String, method: &TraitItemFn, scope: &Rc<Scope>) -> Value {
+        let params = method
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(arg) => match &*arg.pat {
+                    Pat::Ident(pat) => Some((pat.ident.unraw().to_string(), &*arg.ty)),
+                    _ => None,
+                },
+                FnArg::Receiver(_) => None,
+            })
+            .map(|(name, ty)| {
+                let (ty, optional) = option_inner(ty);
+                json!({ "name": name, "required": !optional, "schema": self.schema(ty, scope) })
+            })
+            .collect::<Vec<_>>();
+
+        let mut object = json!({ "name": name, "params": params });
+        if let Some(doc) = docs(&method.attrs) {
+            if let Some(summary) = doc.lines().next() {
+                object["summary"] = json!(summary);
+            }
+            if doc.contains('\n') {
+                object["description"] = json!(doc);
+            }
+        }
+        object
+    }
+
+    fn schema(&mut self, ty: &Type, scope: &Rc<Scope>) -> Value {
+        match ty {
+            Type::Reference(ty) => self.schema(&ty.elem, scope),
+            Type::Paren(ty) => self.schema(&ty.elem, scope),
+            Type::Group(ty) => self.schema(&ty.elem, scope),
+            Type::Tuple(ty) if ty.elems.is_empty() => json!({ "type": "null" }),
+            Type::Tuple(ty) => {
+                let items = ty
+                    .elems
+                    .iter()
+                    .map(|ty| self.schema(ty, scope))
+                    .collect::<Vec<_>>();
+                json!({ "type": "array", "items": items })
+            }
+            Type::Array(ty) => self.sequence_schema(&ty.elem, scope),
+            Type::Slice(ty) => self.sequence_schema(&ty.elem, scope),
+            Type::Path(path) => {
+                let Some(last) = path.path.segments.last() else {
+                    return json!({});
+                };
+                let name = last.ident.to_string();
+                if path.path.segments.len() == 1 {
+                    if let Some(schema) = self.generics.get(&name) {
+                        return schema.clone();
+                    }
+                }
+                match (name.as_str(), generic_args(ty).as_slice()) {
+                    ("Option", [inner]) => {
+                        json!({ "oneOf": [self.schema(inner, scope), { "type": "null" }] })
+                    }
+                    ("Box" | "Arc" | "Rc", [inner]) => self.schema(inner, scope),
+                    ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => {
+                        self.sequence_schema(inner, scope)
+                    }
+                    ("HashMap" | "BTreeMap", [_, value]) => {
+                        json!({ "type": "object", "additionalProperties": self.schema(value, scope) })
+                    }
+                    (_, args) => match primitive_schema(&name) {
+                        Some(schema) => schema,
+                        None => match self.resolve(&path.path, scope) {
+                            Some(path) if !args.is_empty() => {
+                                let args = args.iter().map(|ty| self.schema(ty, scope)).collect();
+                                self.generic_schema(&path, args)
+                            }
+                            Some(path) => self.component(&path),
+                            None => self.external_component(&name),
+                        },
+                    },
+                }
+            }
+            _ => json!({}),
+        }
+    }
+
+    fn sequence_schema(&mut self, elem: &Type, scope: &Rc<Scope>) -> Value {
+        // Byte vectors are serialized as hex strings
+        if elem.to_token_stream().to_string() == "u8" {
+            return primitive_schema("Bytes").unwrap_or_default();
+        }
+        json!({ "type": "array", "items": self.schema(elem, scope) })
+    }
+
+    /// Returns a reference to the component schema of the type at `path`, adding it first.
+    fn component(&mut self, path: &str) -> Value {
+        if let Some(name) = self.names.get(path) {
+            return json!({ "$ref": format!("#/components/schemas/{name}") });
+        }
+        // Taken out of the map while its fields are walked
+        let Some(def) = self.types.remove(path) else {
+            return json!({});
+        };
+        let ident = def.ident();
+
+        let mut name = ident.clone();
+        if self.names.values().any(|n| *n == name) {
+            // Prefix names used by several types with their module, i.e. `BlockTransactionTrace`
+            let module = def.scope.module.rsplit("::").next().unwrap_or_default();
+            name = format!("{}{ident}", module.to_upper_camel_case());
+        }
+        self.names.insert(path.to_string(), name.clone());
+
+        let schema = self.item_schema(&def);
+        self.schemas.insert(name.clone(), schema);
+        self.types.insert(path.to_string(), def);
+        json!({ "$ref": format!("#/components/schemas/{name}") })
+    }
+
+    /// Returns the schema of the generic type at `path` with its type parameters replaced by
+    /// `args`. It is inlined, as it differs for each instantiation.
+    fn generic_schema(&mut self, path: &str, args: Vec<Value>) -> Value {
+        let params = match self.types.get(path).map(|def| &def.item) {
+            Some(Item::Struct(item)) => &item.generics,
+            Some(Item::Enum(item)) => &item.generics,
+            _ => return json!({}),
+        }
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect::<Vec<_>>();
+        if params.is_empty() {
+            return self.component(path);
+        }
+
+        let Some(def) = self.types.remove(path) else {
+            return json!({});
+        };
+        let generics =
+            std::mem::replace(&mut self.generics, params.into_iter().zip(args).collect());
+        let schema = self.item_schema(&def);
+        self.generics = generics;
+        self.types.insert(path.to_string(), def);
+        schema
+    }
+
+    fn item_schema(&mut self, def: &TypeDef) -> Value {
+        let ident = def.ident();
+        let attrs = match &def.item {
+            Item::Struct(item) => &item.attrs,
+            Item::Enum(item) => &item.attrs,
+            _ => unreachable!(),
+        };
+        let mut schema = match OPENRPC_SCHEMA_OVERRIDES.iter().find(|(n, _)| *n == ident) {
+            Some((_, schema)) => self.override_schema(&ident, schema),
+            None => {
+                let args = attr_args(attrs, "serde");
+                match &def.item {
+                    Item::Struct(item) => self.fields_schema(&item.fields, &args, &def.scope),
+                    // Hand written `Serialize` implementations output the variant content
+                    Item::Enum(item) if def.custom_serialize => {
+                        self.untagged_schema(item, true, &def.scope)
+                    }
+                    Item::Enum(item) => self.enum_schema(item, &args, &def.scope),
+                    _ => unreachable!(),
+                }
+            }
+        };
+        if let (Value::Object(schema), Some(doc)) = (&mut schema, docs(attrs)) {
+            schema.insert("description".to_string(), json!(doc));
+        }
+        schema
+    }
+
+    /// Returns a reference to the schema override of a type defined in an external crate.
+    fn external_component(&mut self, name: &str) -> Value {
+        let Some((_, schema)) = OPENRPC_SCHEMA_OVERRIDES.iter().find(|(n, _)| *n == name) else {
+            println!(
+                "cargo:warning=OpenRPC: no schema for type {name} used by {}",
+                self.context
+            );
+            return json!({ "title": name });
+        };
+        if !self.schemas.contains_key(name) {
+            let schema = self.override_schema(name, schema);
+            self.schemas.insert(name.to_string(), schema);
+        }
+        json!({ "$ref": format!("#/components/schemas/{name}") })
+    }
+
+    fn override_schema(&self, name: &str, schema: &str) -> Value {
+        serde_json::from_str(schema).unwrap_or_else(|e| {
+            println!(
+                "cargo:warning=OpenRPC: invalid schema override of {name} used by {}: {e}",
+                self.context
+            );
+            json!({ "title": name })
+        })
+    }
+
+    fn fields_schema(
+        &mut self,
+        fields: &Fields,
+        container: &[(String, Option<String>)],
+        scope: &Rc<Scope>,
+    ) -> Value {
+        let fields = match fields {
+            Fields::Unit => return json!({ "type": "null" }),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                return self.schema(&fields.unnamed[0].ty, scope)
+            }
+            Fields::Unnamed(fields) => {
+                let items = fields
+                    .unnamed
+                    .iter()
+                    .map(|field| self.schema(&field.ty, scope))
+                    .collect::<Vec<_>>();
+                return json!({ "type": "array", "items": items });
+            }
+            Fields::Named(fields) => fields,
+        };
+
+        let rename_all = arg_value(container, "rename_all");
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut flattened = Vec::new();
+        for field in &fields.named {
+            let args = attr_args(&field.attrs, "serde");
+            if has_arg(&args, "skip")
+                || has_arg(&args, "skip_serializing") && has_arg(&args, "skip_deserializing")
+            {
+                continue;
+            }
+            let (ty, optional) = option_inner(&field.ty);
+            if has_arg(&args, "flatten") {
+                flattened.push(self.schema(ty, scope));
+                continue;
+            }
+
+            let name = match arg_value(&args, "rename") {
+                Some(name) => name.to_string(),
+                None => rename(
+                    &field.ident.as_ref().unwrap().unraw().to_string(),
+                    rename_all,
+                ),
+            };
+            let mut schema = self.schema(ty, scope);
+            let one_or_many = attr_args(&field.attrs, "serde_as")
+                .iter()
+                .any(|(_, value)| value.as_deref().is_some_and(|v| v.contains("OneOrMany")));
+            if one_or_many {
+                if let Some(item) = schema.get("items").cloned() {
+                    schema = json!({ "oneOf": [item, schema] });
+                }
+            }
+            let skipped_if_none = has_arg(&args, "skip_serializing_if");
+            if optional && !skipped_if_none {
+                schema = json!({ "oneOf": [schema, { "type": "null" }] });
+            }
+            if let (Value::Object(schema), Some(doc)) = (&mut schema, docs(&field.attrs)) {
+                schema.insert("description".to_string(), json!(doc));
+            }
+            if !optional
+                && !skipped_if_none
+                && !has_arg(&args, "default")
+                && !has_arg(container, "default")
+            {
+                required.push(name.clone());
+            }
+            properties.insert(name, schema);
+        }
+
+        let mut object = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            object["required"] = json!(required);
+        }
+        if flattened.is_empty() {
+            if has_arg(container, "deny_unknown_fields") {
+                object["additionalProperties"] = json!(false);
+            }
+            return object;
+        }
+        flattened.push(object);
+        json!({ "allOf": flattened })
+    }
+
+    fn enum_schema(
+        &mut self,
+        item: &ItemEnum,
+        args: &[(String, Option<String>)],
+        scope: &Rc<Scope>,
+    ) -> Value {
+        if has_arg(args, "untagged") {
+            return self.untagged_schema(item, false, scope);
+        }
+        let rename_all = arg_value(args, "rename_all");
+        let variant_name =
+            |variant: &syn::Variant| match arg_value(&attr_args(&variant.attrs, "serde"), "rename")
+            {
+                Some(name) => name.to_string(),
+                None => rename(&variant.ident.to_string(), rename_all),
+            };
+        if item
+            .variants
+            .iter()
+            .all(|variant| matches!(variant.fields, Fields::Unit))
+        {
+            let names = item.variants.iter().map(variant_name).collect::<Vec<_>>();
+            return json!({ "type": "string", "enum": names });
+        }
+
+        let (tag, content) = (arg_value(args, "tag"), arg_value(args, "content"));
+        let mut variants = Vec::new();
+        for variant in &item.variants {
+            let name = variant_name(variant);
+            let unit = matches!(variant.fields, Fields::Unit);
+            let payload =
+                self.fields_schema(&variant.fields, &attr_args(&variant.attrs, "serde"), scope);
+            let mut schema = match (tag, content) {
+                (Some(tag), Some(content)) => json!({
+                    "type": "object",
+                    "properties": { tag: { "const": name }, content: payload },
+                    "required": [tag],
+                }),
+                (Some(tag), None) => {
+                    let tag = json!({
+                        "type": "object",
+                        "properties": { tag: { "const": name } },
+                        "required": [tag],
+                    });
+                    if unit {
+                        tag
+                    } else {
+                        json!({ "allOf": [tag, payload] })
+                    }
+                }
+                (None, _) if unit => json!({ "const": name }),
+                (None, _) => json!({
+                    "type": "object",
+                    "properties": { &name: payload },
+                    "required": [name],
+                    "additionalProperties": false,
+                }),
+            };
+            if let (Value::Object(schema), Some(doc)) = (&mut schema, docs(&variant.attrs)) {
+                schema.insert("description".to_string(), json!(doc));
+            }
+            variants.push(schema);
+        }
+        json!({ "oneOf": variants })
+    }
+
+    fn untagged_schema(&mut self, item: &ItemEnum, skip_unit: bool, scope: &Rc<Scope>) -> Value {
+        let mut variants = Vec::new();
+        for variant in &item.variants {
+            if skip_unit && matches!(variant.fields, Fields::Unit) {
+                continue;
+            }
+            let payload =
+                self.fields_schema(&variant.fields, &attr_args(&variant.attrs, "serde"), scope);
+            let mut schema = json!({ "title": variant.ident.to_string(), "allOf": [payload] });
+            if let Some(doc) = docs(&variant.attrs) {
+                schema["description"] = json!(doc);
+            }
+            variants.push(schema);
+        }
+        json!({ "oneOf": variants })
+    }
+}
+
+fn primitive_schema(name: &str) -> Option<Value> {
+    let hex = |title: &str, pattern: &str| json!({ "title": title, "type": "string", "pattern": pattern });
+    let schema = match name {
+        "bool" => json!({ "type": "boolean" }),
+        "String" | "str" | "char" => json!({ "type": "string" }),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" => json!({ "type": "integer" }),
+        "f32" | "f64" => json!({ "type": "number" }),
+        "Value" | "JsonValue" => json!({}),
+        "H64" => hex("bytes8", "^0x[0-9a-fA-F]{16}$"),
+        "H160" => hex("address", "^0x[0-9a-fA-F]{40}$"),
+        "H256" => hex("hash32", "^0x[0-9a-fA-F]{64}$"),
+        "Bloom" | "H2048" => hex("bytes256", "^0x[0-9a-fA-F]{512}$"),
+        "U64" | "U128" | "U256" => hex("uint", "^0x([1-9a-fA-F][0-9a-fA-F]*|0)$"),
+        "Bytes" => hex("bytes", "^0x([0-9a-fA-F]{2})*$"),
+        _ => return None,
+    };
+    Some(schema)
+}
+
+/// Type arguments of the last segment of a type path, i.e. `T` of `RpcResult<T>`.
+fn generic_args(ty: &Type) -> Vec<&Type> {
+    let Type::Path(ty) = ty else {
+        return Vec::new();
+    };
+    match ty.path.segments.last().map(|segment| &segment.arguments) {
+        Some(PathArguments::AngleBracketed(args)) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn option_inner(ty: &Type) -> (&Type, bool) {
+    if let Type::Path(path) = ty {
+        if path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option")
+        {
+            if let [inner] = generic_args(ty).as_slice() {
+                return (inner, true);
+            }
+        }
+    }
+    (ty, false)
+}
+
+fn docs(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn capture(attr: &Attribute, pattern: &str) -> Option<String> {
+    let tokens = attr.meta.to_token_stream().to_string();
+    let captures = Regex::new(pattern).unwrap().captures(&tokens)?;
+    Some(captures.get(1)?.as_str().trim().to_string())
+}
+
+/// Flattens the arguments of `#[name(...)]` attributes, i.e. `#[serde(rename = "type")]`.
+fn attr_args(attrs: &[Attribute], name: &str) -> Vec<(String, Option<String>)> {
+    let re = Regex::new(r#"(\w+)\s*(?:=\s*"((?:[^"\\]|\\.)*)")?"#).unwrap();
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(name))
+        .filter_map(|attr| match &attr.meta {
+            Meta::List(list) => Some(list.tokens.to_string()),
+            _ => None,
+        })
+        .flat_map(|tokens| {
+            re.captures_iter(&tokens)
+                .map(|c| (c[1].to_string(), c.get(2).map(|v| v.as_str().to_string())))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn has_arg(args: &[(String, Option<String>)], key: &str) -> bool {
+    args.iter().any(|(k, _)| k == key)
+}
+
+fn arg_value<'a>(args: &'a [(String, Option<String>)], key: &str) -> Option<&'a str> {
+    args.iter()
+        .find(|(k, _)| k == key)
+        .and_then(|(_, value)| value.as_deref())
+}
+
+/// Applies a serde `rename_all` rule to a field or variant name.
+fn rename(ident: &str, rule: Option<&str>) -> String {
+    match rule {
+        Some("lowercase") => ident.to_lowercase(),
+        Some("UPPERCASE") => ident.to_uppercase(),
+        Some("camelCase") => ident.to_lower_camel_case(),
+        Some("PascalCase") => ident.to_upper_camel_case(),
+        Some("snake_case") => ident.to_snake_case(),
+        Some("SCREAMING_SNAKE_CASE") => ident.to_shouty_snake_case(),
+        Some("kebab-case") => ident.to_kebab_case(),
+        _ => ident.to_string(),
+    }
+}
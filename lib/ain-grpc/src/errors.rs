@@ -2,13 +2,14 @@ use ain_evm::EVMError;
 use ethereum_types::H256;
 use jsonrpsee::{
     core::{to_json_raw_value, Error},
-    types::error::{CallError, ErrorObject},
+    types::error::{CallError, ErrorObject, SERVER_IS_BUSY_CODE},
 };
 
 pub enum RPCError {
     AccountError,
     BlockNotFound,
     DebugNotEnabled,
+    DevNotEnabled,
     Error(Box<dyn std::error::Error>),
     EvmError(EVMError),
     GasCapTooLow(u64),
@@ -21,6 +22,7 @@ pub enum RPCError {
     NonceCacheError,
     ReceiptNotFound(H256),
     RevertError(String, String),
+    ServerBusy(&'static str),
     StateRootNotFound,
     TraceNotEnabled,
     TracingParamError([u8; 16]),
@@ -37,6 +39,9 @@ impl From<RPCError> for Error {
             RPCError::AccountError => to_custom_err("error getting account"),
             RPCError::BlockNotFound => to_custom_err("header not found"),
             RPCError::DebugNotEnabled => to_custom_err("debug_* RPCs have not been enabled"),
+            RPCError::DevNotEnabled => {
+                to_custom_err("evm_* dev RPCs are only available on regtest")
+            }
             RPCError::Error(e) => Error::Custom(format!("{:?}", e.to_string())),
             RPCError::EvmError(e) => {
                 Error::Custom(format!("error calling EVM : {:?}", e.to_string()))
@@ -67,6 +72,11 @@ impl From<RPCError> for Error {
                 let raw_value = to_json_raw_value(&data).ok();
                 Error::Call(CallError::Custom(ErrorObject::owned(3, msg, raw_value)))
             }
+            RPCError::ServerBusy(pool) => Error::Call(CallError::Custom(ErrorObject::owned(
+                SERVER_IS_BUSY_CODE,
+                format!("server busy, too many queued {pool} requests, try again later"),
+                None::<()>,
+            ))),
             RPCError::StateRootNotFound => to_custom_err("state root not found"),
             RPCError::TraceNotEnabled => to_custom_err("debug_trace* RPCs have not been enabled"),
             RPCError::TracingParamError(hash) => Error::Custom(format!(
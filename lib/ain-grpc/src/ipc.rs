@@ -0,0 +1,374 @@
+//! JSON-RPC over a unix domain socket.
+//!
+//! Messages are JSON values written back to back on the stream; responses and
+//! subscription notifications are each terminated by a newline. Requests are dispatched
+//! straight to the merged [`Methods`], so the socket serves the same methods as the HTTP
+//! and websocket servers, without their network facing middleware.
+
+use std::{
+    fs::{self, DirBuilder, Permissions},
+    io,
+    os::unix::fs::{DirBuilderExt, PermissionsExt},
+    path::Path,
+};
+
+use futures::{channel::mpsc::UnboundedReceiver, StreamExt};
+use jsonrpsee::core::server::rpc_module::Methods;
+use log::{debug, warn};
+use serde::de::IgnoredAny;
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{UnixListener, UnixStream},
+    sync::{
+        mpsc::{self, UnboundedSender},
+        oneshot,
+    },
+};
+
+/// Maximum size of a single request, matching the HTTP server.
+const MAX_REQUEST_SIZE: usize = 10 * 1024 * 1024;
+
+const PARSE_ERROR_CODE: i32 = -32700;
+const INVALID_REQUEST_CODE: i32 = -32600;
+const OVERSIZED_REQUEST_CODE: i32 = -32701;
+
+/// Splits a stream into complete JSON requests.
+///
+/// Values are framed by tracking the nesting depth, so each byte is scanned once no
+/// matter how many reads a request is split across. Complete values are then checked by
+/// serde.
+#[derive(Default)]
+pub struct MessageBuffer {
+    buf: Vec<u8>,
+    /// Start of the pending bytes, the ones before have been returned as messages.
+    start: usize,
+    /// End of the bytes scanned so far.
+    scanned: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl MessageBuffer {
+    pub fn extend(&mut self, data: &[u8]) {
+        if self.start > 0 {
+            self.buf.drain(..self.start);
+            self.scanned -= self.start;
+            self.start = 0;
+        }
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Number of bytes not yet returned as messages.
+    pub fn pending(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    /// Returns the next complete JSON value, or `None` while it is still incomplete.
+    /// Errors when the stream can never become valid JSON.
+    pub fn next_message(&mut self) -> serde_json::Result<Option<String>> {
+        while self.scanned < self.buf.len() {
+            let byte = self.buf[self.scanned];
+            self.scanned += 1;
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' if self.depth > 0 => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        let message = &self.buf[self.start..self.scanned];
+                        serde_json::from_slice::<IgnoredAny>(message)?;
+                        let message = String::from_utf8_lossy(message).trim().to_string();
+                        self.start = self.scanned;
+                        return Ok(Some(message));
+                    }
+                }
+                b'"' if self.depth > 0 => self.in_string = true,
+                b' ' | b'\t' | b'\r' | b'\n' if self.depth == 0 => self.start = self.scanned,
+                _ if self.depth == 0 => {
+                    return Err(serde::de::Error::custom("expected a JSON object or array"))
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn error_message(id: Value, code: i32, message: &str) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message },
+        "id": id,
+    })
+    .to_string()
+}
+
+/// Binds a socket at `path` that only the node's user can connect to, replacing any
+/// socket left behind by an unclean shutdown.
+///
+/// The socket is bound inside a private directory and only moved to `path` once its
+/// permissions are restricted, so it is never reachable with the umask's defaults.
+pub fn bind(path: &Path) -> io::Result<UnixListener> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing file name"))?;
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let dir = parent.join(format!(".ipc.{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    DirBuilder::new().mode(0o700).create(&dir)?;
+
+    let bound = dir.join(name);
+    let listener = UnixListener::bind(&bound).and_then(|listener| {
+        fs::set_permissions(&bound, Permissions::from_mode(0o600))?;
+        fs::rename(&bound, path)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_dir_all(&dir);
+    listener
+}
+
+/// Accepts connections on `listener` until the task is aborted.
+pub async fn serve(listener: UnixListener, methods: Methods) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(stream, methods.clone()));
+            }
+            Err(e) => warn!("IPC accept error: {e}"),
+        }
+    }
+}
+
+async fn handle_connection(stream: UnixStream, methods: Methods) {
+    let (mut reader, mut writer) = stream.into_split();
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+
+    let write_task = tokio::spawn(async move {
+        let mut closing = false;
+        loop {
+            tokio::select! {
+                message = rx.recv() => {
+                    let Some(mut message) = message else {
+                        break;
+                    };
+                    message.push('\n');
+                    if writer.write_all(message.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+                // Subscriptions hold senders forever, so close the channel explicitly.
+                // Messages already queued are still written.
+                _ = &mut shutdown_rx, if !closing => {
+                    closing = true;
+                    rx.close();
+                }
+            }
+        }
+    });
+
+    let mut messages = MessageBuffer::default();
+    let mut chunk = vec![0; 64 * 1024];
+    'read: loop {
+        loop {
+            match messages.next_message() {
+                Ok(Some(request)) => {
+                    tokio::spawn(handle_request(methods.clone(), request, tx.clone()));
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    // The stream cannot be resynchronised after malformed JSON
+                    debug!("IPC parse error: {e}");
+                    let _ = tx.send(error_message(Value::Null, PARSE_ERROR_CODE, "Parse error"));
+                    break 'read;
+                }
+            }
+        }
+        if messages.pending() > MAX_REQUEST_SIZE {
+            let _ = tx.send(error_message(
+                Value::Null,
+                OVERSIZED_REQUEST_CODE,
+                "Request is too big",
+            ));
+            break;
+        }
+        match reader.read(&mut chunk).await {
+            Ok(0) => break,
+            Ok(n) => messages.extend(&chunk[..n]),
+            Err(e) => {
+                debug!("IPC read error: {e}");
+                break;
+            }
+        }
+    }
+
+    // Flush the pending responses. Spawned requests and subscriptions stop once they
+    // notice the closed channel.
+    drop(shutdown_tx);
+    let _ = write_task.await;
+}
+
+async fn handle_request(methods: Methods, request: String, tx: UnboundedSender<String>) {
+    if !request.starts_with('[') {
+        let (response, notifications) = call(&methods, &request).await;
+        if tx.send(response).is_ok() {
+            if let Some(notifications) = notifications {
+                forward_notifications(notifications, tx).await;
+            }
+        }
+        return;
+    }
+
+    let calls = match serde_json::from_str::<Vec<Value>>(&request) {
+        Ok(calls) if !calls.is_empty() => calls,
+        _ => {
+            let _ = tx.send(error_message(
+                Value::Null,
+                INVALID_REQUEST_CODE,
+                "Invalid request",
+            ));
+            return;
+        }
+    };
+
+    let mut responses = Vec::with_capacity(calls.len());
+    let mut subscriptions = Vec::new();
+    for call_request in calls {
+        let (response, notifications) = call(&methods, &call_request.to_string()).await;
+        responses.push(response);
+        subscriptions.extend(notifications);
+    }
+    if tx.send(format!("[{}]", responses.join(","))).is_ok() {
+        for notifications in subscriptions {
+            tokio::spawn(forward_notifications(notifications, tx.clone()));
+        }
+    }
+}
+
+/// Executes a single call, returning the response and the notifications of the
+/// subscription it opened, if any.
+async fn call(methods: &Methods, request: &str) -> (String, Option<UnboundedReceiver<String>>) {
+    match methods.raw_json_request(request).await {
+        Ok((response, notifications)) => {
+            let notifications = response.success.then_some(notifications);
+            (response.result, notifications)
+        }
+        Err(e) => {
+            debug!("IPC invalid request: {e}");
+            let id = serde_json::from_str::<Value>(request)
+                .ok()
+                .and_then(|request| request.get("id").cloned())
+                .unwrap_or(Value::Null);
+            (
+                error_message(id, INVALID_REQUEST_CODE, "Invalid request"),
+                None,
+            )
+        }
+    }
+}
+
+/// Forwards subscription notifications until the subscription ends or the connection is
+/// closed. Dropping `notifications` closes the subscription sink.
+async fn forward_notifications(
+    mut notifications: UnboundedReceiver<String>,
+    tx: UnboundedSender<String>,
+) {
+    loop {
+        tokio::select! {
+            notification = notifications.next() => match notification {
+                Some(notification) => {
+                    if tx.send(notification).is_err() {
+                        break;
+                    }
+                }
+                None => break,
+            },
+            _ = tx.closed() => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn bind_restricts_permissions() {
+        let dir = std::env::temp_dir().join(format!("ain-grpc-ipc-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("metachain.ipc");
+        // A stale socket is replaced
+        drop(bind(&path).unwrap());
+        let _listener = bind(&path).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        UnixStream::connect(&path).await.unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn next_message(messages: &mut MessageBuffer) -> Option<String> {
+        messages.next_message().unwrap()
+    }
+
+    #[test]
+    fn message_buffer_splits_stream() {
+        let mut messages = MessageBuffer::default();
+        messages.extend(
+            br#" {"id":1} [{"id":2}]
+{"id":"#,
+        );
+        assert_eq!(next_message(&mut messages).as_deref(), Some(r#"{"id":1}"#));
+        assert_eq!(
+            next_message(&mut messages).as_deref(),
+            Some(r#"[{"id":2}]"#)
+        );
+        assert_eq!(next_message(&mut messages), None);
+        messages.extend(b"3}\n");
+        assert_eq!(next_message(&mut messages).as_deref(), Some(r#"{"id":3}"#));
+        assert_eq!(next_message(&mut messages), None);
+        assert_eq!(messages.pending(), 0);
+    }
+
+    #[test]
+    fn message_buffer_reads_byte_by_byte() {
+        let request = r#"{"params":["}]\"{[",{"a":[]}],"id":1}"#;
+        let mut messages = MessageBuffer::default();
+        for byte in request.as_bytes() {
+            assert_eq!(next_message(&mut messages), None);
+            messages.extend(&[*byte]);
+        }
+        assert_eq!(next_message(&mut messages).as_deref(), Some(request));
+        assert_eq!(messages.pending(), 0);
+    }
+
+    #[test]
+    fn message_buffer_rejects_malformed_json() {
+        let mut messages = MessageBuffer::default();
+        messages.extend(b"{\"id\":1}}");
+        assert!(next_message(&mut messages).is_some());
+        assert!(messages.next_message().is_err());
+
+        let mut messages = MessageBuffer::default();
+        messages.extend(b"{\"id\" 1}");
+        assert!(messages.next_message().is_err());
+
+        let mut messages = MessageBuffer::default();
+        messages.extend(b"1");
+        assert!(messages.next_message().is_err());
+    }
+}
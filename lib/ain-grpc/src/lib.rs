@@ -8,8 +8,13 @@ pub mod codegen;
 mod errors;
 mod filters;
 mod impls;
+#[cfg(unix)]
+pub mod ipc;
 pub mod logging;
 mod logs;
+mod metrics;
+mod middleware;
+mod pool;
 mod receipt;
 pub mod rpc;
 mod subscription;
@@ -17,31 +22,48 @@ mod sync;
 mod trace;
 mod transaction;
 mod transaction_request;
+mod typed_data;
 mod utils;
 
 #[cfg(test)]
 mod tests;
 
 use std::{
+    convert::Infallible,
     net::SocketAddr,
     path::PathBuf,
     sync::{atomic::Ordering, Arc},
 };
 
-use ain_evm::services::{IS_SERVICES_INIT_CALL, SERVICES};
+use ain_evm::services::{Services, IS_SERVICES_INIT_CALL, SERVICES};
 use anyhow::{format_err, Result};
-use hyper::{header::HeaderValue, Method};
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Server,
+};
 use jsonrpsee::core::server::rpc_module::Methods;
 use jsonrpsee_server::ServerBuilder;
-use log::info;
-use logging::CppLogTarget;
-use tower_http::cors::CorsLayer;
+use log::{info, warn};
+use metrics::RpcMetrics;
+use middleware::{
+    access::{websocket_methods, AccessConfig, AccessControlLayer, ClientAddrLogger},
+    auth::{AuthConfig, AuthLayer},
+    health::HealthLayer,
+    origin::{OriginLayer, OriginPolicy},
+};
 
 use crate::{
     rpc::{
+        admin::{MetachainAdminRPCModule, MetachainAdminRPCServer},
         debug::{MetachainDebugRPCModule, MetachainDebugRPCServer},
+        dev::{MetachainDevRPCModule, MetachainDevRPCServer},
+        discover::{MetachainDiscoverRPCModule, MetachainDiscoverRPCServer},
         eth::{MetachainRPCModule, MetachainRPCServer},
+        metachain::{MetachainIndexRPCModule, MetachainIndexRPCServer},
         net::{MetachainNetRPCModule, MetachainNetRPCServer},
+        ots::{MetachainOtsRPCModule, MetachainOtsRPCServer},
+        personal::{MetachainPersonalRPCModule, MetachainPersonalRPCServer},
+        txpool::{MetachainTxPoolRPCModule, MetachainTxPoolRPCServer},
         web3::{MetachainWeb3RPCModule, MetachainWeb3RPCServer},
     },
     subscription::{
@@ -58,12 +80,7 @@ use crate::{
 pub fn preinit() {}
 
 pub fn init_logging() {
-    env_logger::Builder::from_env(
-        env_logger::Env::default().default_filter_or(log::Level::Info.as_str()),
-    )
-    .format(logging::cpp_log_target_format)
-    .target(env_logger::Target::Pipe(Box::new(CppLogTarget::new(false))))
-    .init();
+    logging::init();
     info!("Init rs logging");
 }
 
@@ -72,6 +89,44 @@ pub fn init_services() {
     let _ = &*SERVICES;
 }
 
+/// Methods of the eth, net and web3 namespaces and `rpc_discover`, served by every transport.
+fn eth_rpc_methods(runtime: &Services) -> Result<Methods> {
+    let mut methods: Methods = Methods::new();
+    methods.merge(MetachainRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    methods.merge(MetachainNetRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    methods.merge(MetachainWeb3RPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    methods.merge(MetachainDiscoverRPCModule.into_rpc())?;
+    Ok(methods)
+}
+
+/// Methods served by the JSON-RPC server, shared with the IPC transport. Admin methods are
+/// only included when `admin` is set, and never served by the websocket server.
+fn json_rpc_methods(runtime: &Services, admin: bool) -> Result<Methods> {
+    let mut methods = eth_rpc_methods(runtime)?;
+    methods.merge(MetachainDebugRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    methods.merge(MetachainPersonalRPCModule.into_rpc())?;
+    methods.merge(MetachainTxPoolRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    methods.merge(MetachainDevRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    methods.merge(MetachainOtsRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    methods.merge(MetachainIndexRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    if !admin {
+        return Ok(methods);
+    }
+
+    let mut modules = methods
+        .method_names()
+        .filter_map(|name| {
+            name.split_once('_')
+                .map(|(namespace, _)| namespace.to_string())
+        })
+        .chain([String::from("admin")])
+        .collect::<Vec<_>>();
+    modules.sort();
+    modules.dedup();
+    methods.merge(MetachainAdminRPCModule::new(Arc::clone(&runtime.evm), modules).into_rpc())?;
+    Ok(methods)
+}
+
 pub fn init_network_json_rpc_service(addr: String) -> Result<()> {
     info!("Init rs rpc services");
 
@@ -80,24 +135,27 @@ pub fn init_network_json_rpc_service(addr: String) -> Result<()> {
     let max_response_size = ain_cpp_imports::get_max_response_byte_size();
     let runtime = &SERVICES;
 
-    let middleware = if !ain_cpp_imports::get_cors_allowed_origin().is_empty() {
-        let origin = ain_cpp_imports::get_cors_allowed_origin();
-        info!("Allowed origins: {}", origin);
-        let cors = CorsLayer::new()
-            .allow_methods([Method::POST, Method::GET, Method::OPTIONS])
-            .allow_origin(origin.parse::<HeaderValue>()?)
-            .allow_headers([hyper::header::CONTENT_TYPE, hyper::header::AUTHORIZATION])
-            .allow_credentials(origin != "*");
-
-        tower::ServiceBuilder::new().layer(cors)
-    } else {
-        tower::ServiceBuilder::new().layer(CorsLayer::new())
-    };
+    let origins = OriginPolicy::from_args()?;
+    if origins.is_enabled() {
+        info!("Allowed origins: {}", origins);
+    }
+    let access = AccessConfig::from_args()?;
+    let methods = json_rpc_methods(runtime, access.admin)?;
+
+    // Access control must stay the innermost layer, see `middleware::access`.
+    let middleware = tower::ServiceBuilder::new()
+        .layer(origins.cors_layer())
+        .layer(OriginLayer::new(origins))
+        .layer(HealthLayer::new(Arc::clone(&runtime.evm)))
+        .layer(AuthLayer::new(AuthConfig::from_args()?))
+        .layer(AccessControlLayer::new(access));
 
     let handle = runtime.tokio_runtime.clone();
     let server = runtime.tokio_runtime.block_on(
         ServerBuilder::default()
+            .http_only()
             .set_middleware(middleware)
+            .set_logger((RpcMetrics::new(methods.method_names()), ClientAddrLogger))
             .max_connections(max_connections)
             .max_response_body_size(max_response_size)
             .custom_tokio_runtime(handle)
@@ -108,12 +166,6 @@ pub fn init_network_json_rpc_service(addr: String) -> Result<()> {
     info!("Starting JSON Eth RPC server at {}", local_addr);
     ain_cpp_imports::print_port_usage(2, local_addr.port());
 
-    let mut methods: Methods = Methods::new();
-    methods.merge(MetachainRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
-    methods.merge(MetachainDebugRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
-    methods.merge(MetachainNetRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
-    methods.merge(MetachainWeb3RPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
-
     runtime.json_rpc_handles.lock().push(server.start(methods)?);
     Ok(())
 }
@@ -136,9 +188,38 @@ pub fn init_network_subscriptions_service(addr: String) -> Result<()> {
     let max_response_size = ain_cpp_imports::get_max_response_byte_size();
     let runtime = &SERVICES;
 
+    // Web3 providers expect to query the node on the same connection as their
+    // subscriptions.
+    let debug_enabled = ain_cpp_imports::is_eth_subscription_debug_enabled();
+    let mut methods = eth_rpc_methods(runtime)?;
+    if debug_enabled {
+        methods.merge(MetachainDebugRPCModule::new(Arc::clone(&runtime.evm)).into_rpc())?;
+    }
+
+    // Calls over an established websocket are not inspected, so debug methods require
+    // authenticating the connection itself. Otherwise protected methods are not served.
+    let auth = AuthConfig::from_args()?;
+    let mut methods = websocket_methods(
+        methods,
+        AccessConfig::from_args()?,
+        auth.as_ref().filter(|_| !debug_enabled),
+    )?;
+    methods.merge(
+        MetachainPubSubModule::new(Arc::clone(&runtime.evm), runtime.tokio_runtime.clone())
+            .into_rpc(),
+    )?;
+    let auth = auth.filter(|_| debug_enabled);
+    // Browsers do not apply CORS to websockets, so the origin policy is enforced here.
+    let middleware = tower::ServiceBuilder::new()
+        .layer(OriginLayer::new(OriginPolicy::from_args()?))
+        .layer(AuthLayer::new(auth));
+
     let handle = runtime.tokio_runtime.clone();
     let server = runtime.tokio_runtime.block_on(
         ServerBuilder::default()
+            .ws_only()
+            .set_middleware(middleware)
+            .set_logger(RpcMetrics::new(methods.method_names()))
             .max_subscriptions_per_connection(max_connections)
             .max_response_body_size(max_response_size)
             .custom_tokio_runtime(handle)
@@ -150,19 +231,71 @@ pub fn init_network_subscriptions_service(addr: String) -> Result<()> {
     info!("Starting ws server at {}", local_addr);
     ain_cpp_imports::print_port_usage(3, local_addr.port());
 
-    let mut methods: Methods = Methods::new();
+    runtime
+        .websocket_handles
+        .lock()
+        .push(server.start(methods)?);
+    Ok(())
+}
+
+pub fn init_network_metrics_service(addr: String) -> Result<()> {
+    info!("Init rs metrics service");
+
+    let addr = addr.as_str().parse::<SocketAddr>()?;
+    let runtime = &SERVICES;
+
+    let server = {
+        let _guard = runtime.tokio_runtime.enter();
+        Server::try_bind(&addr)?.serve(make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(metrics::serve))
+        }))
+    };
+
+    let local_addr = server.local_addr();
+    info!("Starting metrics server at {}", local_addr);
+    ain_cpp_imports::print_port_usage(4, local_addr.port());
+
+    let handle = runtime.tokio_runtime.spawn(async move {
+        if let Err(e) = server.await {
+            warn!("Metrics server error: {e}");
+        }
+    });
+    runtime.metrics_handles.lock().push(handle);
+    Ok(())
+}
+
+#[cfg(unix)]
+pub fn init_network_ipc_service(path: String) -> Result<()> {
+    info!("Init rs ipc service");
+
+    let path = PathBuf::from(path);
+    let runtime = &SERVICES;
+
+    // The datadir lock guarantees no other node is serving a socket left at `path`
+    let listener = {
+        let _guard = runtime.tokio_runtime.enter();
+        ipc::bind(&path)
+            .map_err(|e| format_err!("Error binding IPC socket {}: {e}", path.display()))?
+    };
+
+    // The socket is only reachable by the node's user, so admin methods are always served
+    let mut methods = json_rpc_methods(runtime, true)?;
     methods.merge(
         MetachainPubSubModule::new(Arc::clone(&runtime.evm), runtime.tokio_runtime.clone())
             .into_rpc(),
     )?;
 
-    runtime
-        .websocket_handles
-        .lock()
-        .push(server.start(methods)?);
+    info!("Starting IPC server at {}", path.display());
+    let handle = runtime.tokio_runtime.spawn(ipc::serve(listener, methods));
+    runtime.ipc_handles.lock().push(handle);
     Ok(())
 }
 
+#[cfg(not(unix))]
+pub fn init_network_ipc_service(_path: String) -> Result<()> {
+    Err(format_err!("IPC is only supported on unix platforms"))
+}
+
 fn is_services_init_called() -> bool {
     IS_SERVICES_INIT_CALL.load(Ordering::SeqCst)
 }
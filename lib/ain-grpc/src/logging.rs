@@ -1,4 +1,16 @@
-use std::io::Write;
+use std::{collections::BTreeMap, io::Write, str::FromStr};
+
+use anyhow::{format_err, Result};
+use lazy_static::lazy_static;
+use log::{LevelFilter, Log, Metadata, Record};
+use parking_lot::{Mutex, RwLock};
+
+lazy_static! {
+    static ref LOGGER: ReloadableLogger = ReloadableLogger {
+        logger: RwLock::new(build_logger(&BTreeMap::new())),
+        levels: Mutex::new(BTreeMap::new()),
+    };
+}
 
 #[derive(Debug, Default)]
 pub struct CppLogTarget {
@@ -45,3 +57,97 @@ impl std::io::Write for CppLogTarget {
         Ok(())
     }
 }
+
+/// Builds the env_logger logger of `RUST_LOG`, defaulting to info, with `levels` applied on
+/// top.
+fn build_logger(levels: &BTreeMap<Option<String>, LevelFilter>) -> env_logger::Logger {
+    let mut builder = env_logger::Builder::from_env(
+        env_logger::Env::default().default_filter_or(log::Level::Info.as_str()),
+    );
+    builder
+        .format(cpp_log_target_format)
+        .target(env_logger::Target::Pipe(Box::new(CppLogTarget::new(false))));
+    for (target, level) in levels {
+        builder.filter(target.as_deref(), *level);
+    }
+    builder.build()
+}
+
+/// Forwards records to an env_logger logger that is rebuilt when log levels are changed at
+/// runtime, as env_logger filters cannot be modified once built.
+struct ReloadableLogger {
+    logger: RwLock<env_logger::Logger>,
+    levels: Mutex<BTreeMap<Option<String>, LevelFilter>>,
+}
+
+impl Log for ReloadableLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.logger.read().enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        self.logger.read().log(record);
+    }
+
+    fn flush(&self) {
+        self.logger.read().flush();
+    }
+}
+
+pub fn init() {
+    log::set_logger(&*LOGGER).expect("Logger already initialized");
+    log::set_max_level(LOGGER.logger.read().filter());
+}
+
+/// Sets the log level of `target` and its submodules, overriding `RUST_LOG`. A `None` target
+/// sets the level of targets without a level of their own.
+pub fn set_log_level(target: Option<&str>, level: &str) -> Result<()> {
+    let level =
+        LevelFilter::from_str(level).map_err(|_| format_err!("Invalid log level {level}"))?;
+    if let Some(target) = target {
+        if target.is_empty()
+            || target.contains([',', '=', '/'])
+            || target.contains(char::is_whitespace)
+        {
+            return Err(format_err!("Invalid log target {target}"));
+        }
+    }
+
+    let mut levels = LOGGER.levels.lock();
+    levels.insert(target.map(String::from), level);
+
+    let logger = build_logger(&levels);
+    log::set_max_level(logger.filter());
+    *LOGGER.logger.write() = logger;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_enabled(logger: &env_logger::Logger, target: &str, level: log::Level) -> bool {
+        logger.enabled(&Metadata::builder().target(target).level(level).build())
+    }
+
+    #[test]
+    fn should_apply_levels_per_target() {
+        let levels = BTreeMap::from([
+            (None, LevelFilter::Warn),
+            (Some(String::from("ain_evm")), LevelFilter::Debug),
+        ]);
+        let logger = build_logger(&levels);
+        assert!(is_enabled(&logger, "ain_evm::core", log::Level::Debug));
+        assert!(!is_enabled(&logger, "ain_evm", log::Level::Trace));
+        assert!(!is_enabled(&logger, "ain_grpc", log::Level::Info));
+        assert!(is_enabled(&logger, "ain_grpc", log::Level::Warn));
+        assert_eq!(logger.filter(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn should_reject_invalid_levels() {
+        assert!(set_log_level(None, "verbose").is_err());
+        assert!(set_log_level(Some("ain_evm=debug"), "info").is_err());
+        assert!(set_log_level(Some(""), "info").is_err());
+    }
+}
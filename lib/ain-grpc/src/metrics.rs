@@ -0,0 +1,168 @@
+//! Prometheus metrics of the network services, and the `/metrics` endpoint serving all
+//! registered metrics.
+
+use std::{collections::HashSet, convert::Infallible, net::SocketAddr, sync::Arc, time::Instant};
+
+use ain_evm::services::SERVICES;
+use hyper::{header::CONTENT_TYPE, Body, Method, Request, Response, StatusCode};
+use jsonrpsee_server::logger::{HttpRequest, Logger, MethodKind, Params, TransportProtocol};
+use lazy_static::lazy_static;
+use log::warn;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, Encoder,
+    HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, TextEncoder,
+};
+
+lazy_static! {
+    static ref RPC_REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "rpc_request_duration_seconds",
+        "Latency of JSON-RPC method calls",
+        &["method", "transport"]
+    )
+    .unwrap();
+    static ref RPC_REQUEST_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "rpc_request_errors_total",
+        "Number of JSON-RPC method calls that returned an error",
+        &["method", "transport"]
+    )
+    .unwrap();
+    static ref WS_ACTIVE_SUBSCRIPTIONS: IntGaugeVec = register_int_gauge_vec!(
+        "ws_active_subscriptions",
+        "Number of active websocket subscriptions",
+        &["kind"]
+    )
+    .unwrap();
+    static ref RPC_WORKER_QUEUE_LENGTH: IntGaugeVec = register_int_gauge_vec!(
+        "rpc_worker_queue_length",
+        "Number of JSON-RPC requests waiting for a worker thread",
+        &["pool"]
+    )
+    .unwrap();
+}
+
+/// Records the latency and errors of each JSON-RPC method call. Calls of unknown methods
+/// are grouped under a single label to bound the metrics cardinality.
+#[derive(Clone)]
+pub struct RpcMetrics {
+    methods: Arc<HashSet<String>>,
+}
+
+impl RpcMetrics {
+    pub fn new<'a>(methods: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            methods: Arc::new(methods.into_iter().map(ToString::to_string).collect()),
+        }
+    }
+
+    fn method_label<'a>(&self, method_name: &'a str) -> &'a str {
+        if self.methods.contains(method_name) {
+            method_name
+        } else {
+            "unknown"
+        }
+    }
+}
+
+fn transport_label(transport: TransportProtocol) -> &'static str {
+    match transport {
+        TransportProtocol::Http => "http",
+        TransportProtocol::WebSocket => "ws",
+    }
+}
+
+impl Logger for RpcMetrics {
+    type Instant = Instant;
+
+    fn on_connect(&self, _: SocketAddr, _: &HttpRequest, _: TransportProtocol) {}
+
+    fn on_request(&self, _: TransportProtocol) -> Self::Instant {
+        Instant::now()
+    }
+
+    fn on_call(&self, _: &str, _: Params, _: MethodKind, _: TransportProtocol) {}
+
+    fn on_result(
+        &self,
+        method_name: &str,
+        success: bool,
+        started_at: Self::Instant,
+        transport: TransportProtocol,
+    ) {
+        let labels = [self.method_label(method_name), transport_label(transport)];
+        RPC_REQUEST_DURATION
+            .with_label_values(&labels)
+            .observe(started_at.elapsed().as_secs_f64());
+        if !success {
+            RPC_REQUEST_ERRORS.with_label_values(&labels).inc();
+        }
+    }
+
+    fn on_response(&self, _: &str, _: Self::Instant, _: TransportProtocol) {}
+
+    fn on_disconnect(&self, _: SocketAddr, _: TransportProtocol) {}
+}
+
+/// Counts a websocket subscription as active for as long as the guard is alive.
+pub struct SubscriptionGuard(IntGauge);
+
+impl SubscriptionGuard {
+    pub fn new(kind: &str) -> Self {
+        let gauge = WS_ACTIVE_SUBSCRIPTIONS.with_label_values(&[kind]);
+        gauge.inc();
+        Self(gauge)
+    }
+}
+
+impl Drop for SubscriptionGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+/// Counts a request as queued in a worker pool for as long as the guard is alive.
+pub struct QueueGuard(IntGauge);
+
+impl QueueGuard {
+    pub fn new(pool: &str) -> Self {
+        let gauge = RPC_WORKER_QUEUE_LENGTH.with_label_values(&[pool]);
+        gauge.inc();
+        Self(gauge)
+    }
+}
+
+impl Drop for QueueGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+/// Serves the registered metrics in the Prometheus text format at `GET /metrics`.
+pub async fn serve(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET || req.uri().path() != "/metrics" {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NOT_FOUND;
+        return Ok(response);
+    }
+
+    if let Err(e) = ain_evm::metrics::update_db_stats(&SERVICES.evm.storage) {
+        warn!("Failed to collect db stats: {e}");
+    }
+
+    let encoder = TextEncoder::new();
+    let mut buffer = Vec::new();
+    let response = match encoder.encode(&prometheus::gather(), &mut buffer) {
+        Ok(()) => {
+            let mut response = Response::new(Body::from(buffer));
+            if let Ok(content_type) = encoder.format_type().parse() {
+                response.headers_mut().insert(CONTENT_TYPE, content_type);
+            }
+            response
+        }
+        Err(e) => {
+            let mut response = Response::new(Body::from(e.to_string()));
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            response
+        }
+    };
+    Ok(response)
+}
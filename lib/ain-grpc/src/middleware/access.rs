@@ -0,0 +1,739 @@
+//! Access control of the JSON-RPC HTTP server: method allow and deny lists, token-bucket rate
+//! limiting per client IP and method class, and caps on concurrent requests per method. Admin
+//! methods, when enabled, are only served to loopback clients or to requests authenticated by
+//! `Auth`.
+//!
+//! jsonrpsee only hands the client address to its `Logger`, which it calls synchronously from
+//! the inner service's `call`. `ClientAddrLogger` stores the address in a thread local that
+//! `AccessControl` reads right after calling the inner service. The inner service does no work
+//! until its future is polled, so rejected requests are dropped unprocessed. This requires
+//! `AccessControlLayer` to be the innermost layer of the middleware stack.
+//!
+//! Calls over an established websocket are not inspected, so websocket upgrades are refused and
+//! `websocket_methods` applies the policy to the method set of the websocket server instead.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use anyhow::format_err;
+use hyper::{
+    header::{RETRY_AFTER, UPGRADE},
+    Body, Method, Request, Response, StatusCode,
+};
+use jsonrpsee::{
+    core::{server::rpc_module::Methods, traits::ToRpcParams, Error, JsonRawValue},
+    types::error::{CallError, ErrorObject, METHOD_NOT_FOUND_CODE},
+    RpcModule,
+};
+use jsonrpsee_server::logger::{HttpRequest, Logger, MethodKind, Params, TransportProtocol};
+use parking_lot::Mutex;
+use serde_json::Value;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::{Layer, Service};
+
+use super::{
+    auth::{AuthConfig, Authenticated},
+    is_admin_method, parse_calls, parse_patterns, payload_too_large_response, read_body,
+    rpc_error_response, BoxError, MethodPattern, LIMIT_EXCEEDED_CODE, UNAUTHORIZED_CODE,
+};
+
+/// Number of tracked clients above which idle rate limit buckets are dropped.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+const BUCKET_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Methods that run EVM execution or scan the storage.
+const HEAVY_METHODS: [&str; 7] = [
+    "debug_*",
+    "ots_*",
+    "eth_call",
+    "eth_estimateGas",
+    "eth_createAccessList",
+    "eth_getLogs",
+    "eth_getFilterLogs",
+];
+
+/// Methods signing with the node's keys, never served over websockets.
+const WALLET_METHODS: [&str; 2] = ["eth_sign*", "eth_sendTransaction"];
+
+thread_local! {
+    static CLIENT_ADDR: Cell<Option<IpAddr>> = const { Cell::new(None) };
+}
+
+fn parse_concurrency_limits(list: &str) -> anyhow::Result<Vec<(MethodPattern, usize)>> {
+    list.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (pattern, limit) = entry.split_once('=').ok_or_else(|| {
+                format_err!("Invalid concurrency limit {entry}, expected method=limit")
+            })?;
+            let limit = limit
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|limit| *limit > 0)
+                .ok_or_else(|| format_err!("Invalid concurrency limit {entry}"))?;
+            Ok((MethodPattern::new(pattern), limit))
+        })
+        .collect()
+}
+
+/// Rate limited classes of methods. Every call counts towards `All`, expensive calls count
+/// towards `Heavy` as well.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum MethodClass {
+    All,
+    Heavy,
+}
+
+#[derive(Debug, Default)]
+pub struct AccessConfig {
+    /// Methods served exclusively when not empty.
+    pub allowed: Vec<MethodPattern>,
+    pub denied: Vec<MethodPattern>,
+    /// Requests per second per client IP, 0 disables the limit.
+    pub rate_limit: u32,
+    /// Expensive requests per second per client IP, 0 disables the limit.
+    pub heavy_rate_limit: u32,
+    pub concurrency_limits: Vec<(MethodPattern, usize)>,
+    /// Whether admin methods are served, to loopback or authenticated clients only.
+    pub admin: bool,
+}
+
+impl AccessConfig {
+    pub fn from_args() -> anyhow::Result<Self> {
+        Ok(Self {
+            allowed: parse_patterns(&ain_cpp_imports::get_eth_rpc_allowed_methods()),
+            denied: parse_patterns(&ain_cpp_imports::get_eth_rpc_denied_methods()),
+            rate_limit: ain_cpp_imports::get_eth_rpc_rate_limit(),
+            heavy_rate_limit: ain_cpp_imports::get_eth_rpc_heavy_rate_limit(),
+            concurrency_limits: parse_concurrency_limits(
+                &ain_cpp_imports::get_eth_rpc_concurrency_limits(),
+            )?,
+            admin: ain_cpp_imports::is_eth_rpc_admin_enabled(),
+        })
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.allowed.is_empty()
+            || !self.denied.is_empty()
+            || self.rate_limit > 0
+            || self.heavy_rate_limit > 0
+            || !self.concurrency_limits.is_empty()
+            || self.admin
+    }
+
+    fn is_allowed(&self, method: &str) -> bool {
+        (self.allowed.is_empty() || self.allowed.iter().any(|p| p.matches(method)))
+            && !self.denied.iter().any(|p| p.matches(method))
+    }
+
+    fn rate_limit(&self, class: MethodClass) -> u32 {
+        match class {
+            MethodClass::All => self.rate_limit,
+            MethodClass::Heavy => self.heavy_rate_limit,
+        }
+    }
+}
+
+fn is_heavy(method: &str) -> bool {
+    HEAVY_METHODS
+        .iter()
+        .any(|pattern| MethodPattern::new(pattern).matches(method))
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            updated: now,
+        }
+    }
+
+    /// Refills the bucket at `rate` tokens per second, holding at most a second worth of tokens.
+    fn refill(&mut self, rate: f64, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(rate);
+        self.updated = now;
+    }
+}
+
+#[derive(Debug)]
+struct RateLimitError {
+    class: MethodClass,
+    limit: u32,
+}
+
+pub struct AccessControlState {
+    config: AccessConfig,
+    buckets: Mutex<HashMap<(IpAddr, MethodClass), TokenBucket>>,
+    semaphores: Vec<(MethodPattern, Arc<Semaphore>)>,
+}
+
+impl AccessControlState {
+    pub fn new(config: AccessConfig) -> Self {
+        let semaphores = config
+            .concurrency_limits
+            .iter()
+            .map(|(pattern, limit)| (pattern.clone(), Arc::new(Semaphore::new(*limit))))
+            .collect();
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+            semaphores,
+        }
+    }
+
+    fn check_rate(&self, ip: IpAddr, methods: &[&str], now: Instant) -> Result<(), RateLimitError> {
+        // Requests without any parsable call still cost a token.
+        let all = methods.len().max(1);
+        let heavy = methods.iter().filter(|method| is_heavy(method)).count();
+
+        let mut buckets = self.buckets.lock();
+        if buckets.len() > MAX_TRACKED_CLIENTS {
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated) < BUCKET_IDLE_TIMEOUT);
+        }
+        let costs = [(MethodClass::All, all), (MethodClass::Heavy, heavy)]
+            .into_iter()
+            .filter(|(class, count)| self.config.rate_limit(*class) > 0 && *count > 0)
+            .collect::<Vec<_>>();
+
+        // A request is served when every bucket it draws from holds a token. Batches may take
+        // more tokens than available, leaving the bucket in debt until it is refilled.
+        for (class, _) in &costs {
+            let limit = self.config.rate_limit(*class);
+            let rate = f64::from(limit);
+            let bucket = buckets
+                .entry((ip, *class))
+                .or_insert_with(|| TokenBucket::new(rate, now));
+            bucket.refill(rate, now);
+            if bucket.tokens < 1.0 {
+                return Err(RateLimitError {
+                    class: *class,
+                    limit,
+                });
+            }
+        }
+        for (class, count) in costs {
+            if let Some(bucket) = buckets.get_mut(&(ip, class)) {
+                bucket.tokens -= count as f64;
+            }
+        }
+        Ok(())
+    }
+
+    /// Acquires a permit for every call subject to a concurrency limit, or returns the pattern
+    /// of the exhausted limit.
+    fn acquire_permits(
+        &self,
+        methods: &[&str],
+    ) -> Result<Vec<OwnedSemaphorePermit>, &MethodPattern> {
+        let mut permits = Vec::new();
+        for method in methods {
+            if let Some((pattern, semaphore)) = self
+                .semaphores
+                .iter()
+                .find(|(pattern, _)| pattern.matches(method))
+            {
+                let permit = Arc::clone(semaphore)
+                    .try_acquire_owned()
+                    .map_err(|_| pattern)?;
+                permits.push(permit);
+            }
+        }
+        Ok(permits)
+    }
+}
+
+fn limit_exceeded_response(id: Value, message: String) -> Response<Body> {
+    let mut response = rpc_error_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        id,
+        LIMIT_EXCEEDED_CODE,
+        message,
+    );
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, hyper::header::HeaderValue::from_static("1"));
+    response
+}
+
+/// Params of a forwarded call, passed on as is.
+struct RawParams(Option<Box<JsonRawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<JsonRawValue>>, Error> {
+        Ok(self.0)
+    }
+}
+
+/// Restricts `methods` to the ones served over websockets, leaving out the methods signing
+/// with the node's keys, the ones `config` does not allow and, when connections are not
+/// authenticated, the ones protected by `auth`. The others are forwarded under their
+/// concurrency limits. Websocket calls cannot be attributed to a client IP, so expensive
+/// methods are left out as well when a rate limit applies to them.
+///
+/// Subscriptions are not forwarded and must be merged into the returned methods.
+pub fn websocket_methods(
+    methods: Methods,
+    config: AccessConfig,
+    auth: Option<&AuthConfig>,
+) -> Result<Methods, Error> {
+    let served = methods
+        .method_names()
+        .filter(|method| {
+            !WALLET_METHODS
+                .iter()
+                .any(|pattern| MethodPattern::new(pattern).matches(method))
+                && config.is_allowed(method)
+                && !(is_heavy(method) && (config.rate_limit > 0 || config.heavy_rate_limit > 0))
+                && !auth.is_some_and(|auth| auth.is_protected(method))
+        })
+        .collect::<Vec<_>>();
+
+    let state = Arc::new(AccessControlState::new(config));
+    let mut module = RpcModule::new(methods);
+    for method in served {
+        let state = Arc::clone(&state);
+        module.register_async_method(method, move |params, methods| {
+            let state = Arc::clone(&state);
+            async move {
+                let permits = state.acquire_permits(&[method]).map_err(|pattern| {
+                    CallError::Custom(ErrorObject::owned(
+                        LIMIT_EXCEEDED_CODE,
+                        format!("Too many concurrent {pattern} requests, try again later"),
+                        None::<()>,
+                    ))
+                })?;
+                let params = params.parse::<Option<Box<JsonRawValue>>>()?;
+                let result = methods
+                    .call::<_, Box<JsonRawValue>>(method, RawParams(params))
+                    .await;
+                drop(permits);
+                result
+            }
+        })?;
+    }
+    Ok(module.into())
+}
+
+/// Records the client address of each HTTP request for `AccessControl`.
+#[derive(Clone, Debug)]
+pub struct ClientAddrLogger;
+
+impl Logger for ClientAddrLogger {
+    type Instant = ();
+
+    fn on_connect(&self, remote_addr: SocketAddr, _: &HttpRequest, _: TransportProtocol) {
+        CLIENT_ADDR.with(|addr| addr.set(Some(remote_addr.ip())));
+    }
+
+    fn on_request(&self, _: TransportProtocol) -> Self::Instant {}
+
+    fn on_call(&self, _: &str, _: Params, _: MethodKind, _: TransportProtocol) {}
+
+    fn on_result(&self, _: &str, _: bool, _: Self::Instant, _: TransportProtocol) {}
+
+    fn on_response(&self, _: &str, _: Self::Instant, _: TransportProtocol) {}
+
+    fn on_disconnect(&self, _: SocketAddr, _: TransportProtocol) {}
+}
+
+#[derive(Clone)]
+pub struct AccessControlLayer {
+    state: Arc<AccessControlState>,
+}
+
+impl AccessControlLayer {
+    pub fn new(config: AccessConfig) -> Self {
+        Self {
+            state: Arc::new(AccessControlState::new(config)),
+        }
+    }
+}
+
+impl<S> Layer<S> for AccessControlLayer {
+    type Service = AccessControl<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessControl {
+            inner,
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AccessControl<S> {
+    inner: S,
+    state: Arc<AccessControlState>,
+}
+
+impl<S> Service<Request<Body>> for AccessControl<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // Use the service that was polled ready, leaving a clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let state = Arc::clone(&self.state);
+
+        Box::pin(async move {
+            if request.headers().contains_key(UPGRADE) {
+                return Ok(rpc_error_response(
+                    StatusCode::FORBIDDEN,
+                    Value::Null,
+                    UNAUTHORIZED_CODE,
+                    "Websocket connections are only served on the websocket port",
+                ));
+            }
+            if !state.config.is_enabled() || request.method() != Method::POST {
+                return inner.call(request).await;
+            }
+
+            let (parts, body) = request.into_parts();
+            let Some(body) = read_body(body).await? else {
+                return Ok(payload_too_large_response());
+            };
+            let calls = parse_calls(&body);
+            let methods = calls
+                .iter()
+                .map(|(method, _)| method.as_str())
+                .collect::<Vec<_>>();
+            let first_id = || calls.first().map(|(_, id)| id.clone()).unwrap_or_default();
+
+            if let Some((method, id)) = calls.iter().find(|(m, _)| !state.config.is_allowed(m)) {
+                return Ok(rpc_error_response(
+                    StatusCode::OK,
+                    id.clone(),
+                    METHOD_NOT_FOUND_CODE,
+                    format!("Method {method} is not allowed on this node"),
+                ));
+            }
+
+            let permits = match state.acquire_permits(&methods) {
+                Ok(permits) => permits,
+                Err(pattern) => {
+                    return Ok(limit_exceeded_response(
+                        first_id(),
+                        format!("Too many concurrent {pattern} requests, try again later"),
+                    ))
+                }
+            };
+
+            let authenticated = parts.extensions.get::<Authenticated>().is_some();
+            CLIENT_ADDR.with(|addr| addr.set(None));
+            let response = inner.call(Request::from_parts(parts, Body::from(body)));
+            let client_ip = CLIENT_ADDR.with(Cell::take);
+
+            if !authenticated && !client_ip.is_some_and(|ip| ip.to_canonical().is_loopback()) {
+                if let Some((method, id)) = calls.iter().find(|(m, _)| is_admin_method(m)) {
+                    return Ok(rpc_error_response(
+                        StatusCode::FORBIDDEN,
+                        id.clone(),
+                        UNAUTHORIZED_CODE,
+                        format!("Method {method} is only served to local or authenticated clients"),
+                    ));
+                }
+            }
+            if let Some(ip) = client_ip {
+                if let Err(RateLimitError { class, limit }) =
+                    state.check_rate(ip, &methods, Instant::now())
+                {
+                    let kind = match class {
+                        MethodClass::All => "",
+                        MethodClass::Heavy => "expensive ",
+                    };
+                    return Ok(limit_exceeded_response(
+                        first_id(),
+                        format!("Rate limit of {limit} {kind}requests per second exceeded"),
+                    ));
+                }
+            }
+
+            let response = response.await;
+            drop(permits);
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use hyper::header::CONNECTION;
+    use jsonrpsee_server::ServerBuilder;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+    use crate::middleware::auth::JwtSecret;
+
+    fn upgrade_request() -> Request<Body> {
+        Request::get("/")
+            .header(CONNECTION, "Upgrade")
+            .header(UPGRADE, "websocket")
+            .header("Sec-WebSocket-Version", "13")
+            .header("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn should_match_method_patterns() {
+        let config = AccessConfig {
+            allowed: parse_patterns("eth_*, net_version"),
+            denied: parse_patterns("eth_sendTransaction,eth_sign*"),
+            ..Default::default()
+        };
+        assert!(config.is_allowed("eth_chainId"));
+        assert!(config.is_allowed("net_version"));
+        assert!(!config.is_allowed("net_peerCount"));
+        assert!(!config.is_allowed("debug_traceTransaction"));
+        assert!(!config.is_allowed("eth_sendTransaction"));
+        assert!(!config.is_allowed("eth_signTypedData_v4"));
+        assert!(config.is_allowed("eth_sendRawTransaction"));
+
+        assert!(is_heavy("debug_traceBlockByNumber"));
+        assert!(is_heavy("eth_getLogs"));
+        assert!(!is_heavy("eth_blockNumber"));
+    }
+
+    #[test]
+    fn should_only_inspect_requests_with_a_policy() {
+        assert!(!AccessConfig::default().is_enabled());
+        assert!(AccessConfig {
+            admin: true,
+            ..Default::default()
+        }
+        .is_enabled());
+        assert!(AccessConfig {
+            denied: parse_patterns("eth_sign*"),
+            ..Default::default()
+        }
+        .is_enabled());
+    }
+
+    #[test]
+    fn should_parse_concurrency_limits() {
+        let limits = parse_concurrency_limits("debug_*=4, eth_getLogs=8").unwrap();
+        assert_eq!(
+            limits,
+            vec![
+                (MethodPattern::new("debug_*"), 4),
+                (MethodPattern::new("eth_getLogs"), 8)
+            ]
+        );
+        assert!(parse_concurrency_limits("debug_*").is_err());
+        assert!(parse_concurrency_limits("debug_*=0").is_err());
+    }
+
+    #[test]
+    fn should_parse_single_and_batch_calls() {
+        let single = parse_calls(br#"{"jsonrpc":"2.0","id":1,"method":"eth_chainId"}"#);
+        assert_eq!(single, vec![("eth_chainId".to_string(), Value::from(1))]);
+
+        let batch = parse_calls(
+            br#"[{"id":"a","method":"eth_call"},{"id":2},{"id":3,"method":"debug_traceCall"}]"#,
+        );
+        let methods = batch.iter().map(|(m, _)| m.as_str()).collect::<Vec<_>>();
+        assert_eq!(methods, vec!["eth_call", "debug_traceCall"]);
+
+        assert!(parse_calls(b"not json").is_empty());
+    }
+
+    #[test]
+    fn should_rate_limit_per_ip_and_class() {
+        let state = AccessControlState::new(AccessConfig {
+            rate_limit: 3,
+            heavy_rate_limit: 1,
+            ..Default::default()
+        });
+        let alice = IpAddr::from([10, 0, 0, 1]);
+        let bob = IpAddr::from([10, 0, 0, 2]);
+        let now = Instant::now();
+
+        assert!(state.check_rate(alice, &["eth_call"], now).is_ok());
+        let err = state.check_rate(alice, &["eth_call"], now).unwrap_err();
+        assert_eq!((err.class, err.limit), (MethodClass::Heavy, 1));
+        assert!(state.check_rate(alice, &["eth_chainId"], now).is_ok());
+        assert!(state.check_rate(bob, &["eth_call"], now).is_ok());
+
+        // Refilled after a second
+        let later = now + Duration::from_secs(1);
+        assert!(state.check_rate(alice, &["eth_call"], later).is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_filter_websocket_methods() {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("eth_chainId", |_, _| Ok("0x1"))
+            .unwrap();
+        module
+            .register_method("eth_getBalance", |params, _| {
+                params.one::<String>().map_err(Into::into)
+            })
+            .unwrap();
+        module.register_method("eth_sign", |_, _| Ok("0x")).unwrap();
+        module
+            .register_method("eth_sendTransaction", |_, _| Ok("0x"))
+            .unwrap();
+        module
+            .register_method("debug_traceCall", |_, _| Ok(()))
+            .unwrap();
+        module
+            .register_method("txpool_content", |_, _| Ok(()))
+            .unwrap();
+        module.register_method("eth_call", |_, _| Ok("0x")).unwrap();
+
+        let config = AccessConfig {
+            denied: parse_patterns("txpool_*"),
+            ..Default::default()
+        };
+        let auth = AuthConfig {
+            secret: JwtSecret::from_hex(&"11".repeat(32)).unwrap(),
+            protected: parse_patterns("debug_*"),
+        };
+        let methods: Methods = module.into();
+        let served = websocket_methods(methods.clone(), config, Some(&auth)).unwrap();
+        let mut served = served.method_names().collect::<Vec<_>>();
+        served.sort();
+        assert_eq!(served, vec!["eth_call", "eth_chainId", "eth_getBalance"]);
+
+        // Expensive methods are left out under a rate limit
+        let config = AccessConfig {
+            denied: parse_patterns("txpool_*"),
+            heavy_rate_limit: 10,
+            ..Default::default()
+        };
+        let methods = websocket_methods(methods, config, Some(&auth)).unwrap();
+        let mut served = methods.method_names().collect::<Vec<_>>();
+        served.sort();
+        assert_eq!(served, vec!["eth_chainId", "eth_getBalance"]);
+        let balance: String = methods.call("eth_getBalance", ["0x1"]).await.unwrap();
+        assert_eq!(balance, "0x1");
+        let (response, _) = methods
+            .raw_json_request(r#"{"jsonrpc":"2.0","method":"eth_sign","params":[],"id":1}"#)
+            .await
+            .unwrap();
+        assert!(!response.success);
+        assert!(response.result.contains(&METHOD_NOT_FOUND_CODE.to_string()));
+    }
+
+    #[tokio::test]
+    async fn should_reject_websocket_upgrades() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = {
+            let calls = Arc::clone(&calls);
+            service_fn(move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok::<_, BoxError>(Response::new(Body::empty())) }
+            })
+        };
+        for config in [
+            AccessConfig::default(),
+            AccessConfig {
+                rate_limit: 10,
+                ..Default::default()
+            },
+        ] {
+            let service = AccessControlLayer::new(config).layer(inner.clone());
+            let response = service.oneshot(upgrade_request()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn should_not_serve_admin_methods_over_websockets() {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("admin_flushDb", |_, _| Ok(true))
+            .unwrap();
+        let middleware =
+            tower::ServiceBuilder::new().layer(AccessControlLayer::new(AccessConfig {
+                admin: true,
+                ..Default::default()
+            }));
+        let server = ServerBuilder::default()
+            .http_only()
+            .set_middleware(middleware)
+            .set_logger(ClientAddrLogger)
+            .build("127.0.0.1:0")
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = server.start(module).unwrap();
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let handshake = format!(
+            "GET / HTTP/1.1\r\nHost: {addr}\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\
+             Sec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+        );
+        stream.write_all(handshake.as_bytes()).await.unwrap();
+        let mut response = vec![0; 1024];
+        let len = stream.read(&mut response).await.unwrap();
+        let response = String::from_utf8_lossy(&response[..len]);
+        assert!(response.starts_with("HTTP/1.1 403"), "{response}");
+
+        // Loopback clients are still served admin methods over HTTP
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let body = r#"{"jsonrpc":"2.0","method":"admin_flushDb","params":[],"id":1}"#;
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains(r#""result":true"#), "{response}");
+
+        handle.stop().unwrap();
+    }
+
+    #[test]
+    fn should_cap_concurrent_calls() {
+        let state = AccessControlState::new(AccessConfig {
+            concurrency_limits: parse_concurrency_limits("debug_*=2").unwrap(),
+            ..Default::default()
+        });
+        let permits = state
+            .acquire_permits(&["debug_traceTransaction", "debug_traceCall"])
+            .unwrap();
+        assert_eq!(permits.len(), 2);
+        assert!(state.acquire_permits(&["debug_traceCall"]).is_err());
+        assert!(state.acquire_permits(&["eth_call"]).unwrap().is_empty());
+        drop(permits);
+        assert!(state.acquire_permits(&["debug_traceCall"]).is_ok());
+    }
+}
@@ -0,0 +1,311 @@
+//! Optional JWT authentication of the JSON-RPC HTTP server, following the engine API: HS256
+//! tokens signed with a 32-byte secret shared through a hex encoded file, carrying an `iat`
+//! claim within a minute of the node's clock. Only calls of protected methods require a token,
+//! so public methods stay open to any client. Admin methods are always protected. Websocket
+//! upgrades require a token as the connection is not inspected afterwards.
+
+use std::{
+    fs,
+    future::Future,
+    io::Write,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::format_err;
+use hyper::{
+    header::{HeaderValue, AUTHORIZATION, UPGRADE, WWW_AUTHENTICATE},
+    Body, HeaderMap, Method, Request, Response, StatusCode,
+};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use log::info;
+use rand::RngCore;
+use serde_json::Value;
+use tower::{Layer, Service};
+
+use super::{
+    is_admin_method, parse_calls, parse_patterns, payload_too_large_response, read_body,
+    rpc_error_response, BoxError, MethodPattern, UNAUTHORIZED_CODE,
+};
+
+const JWT_SECRET_LENGTH: usize = 32;
+/// Maximum difference in seconds between the `iat` claim and the node's clock.
+const IAT_LEEWAY: u64 = 60;
+
+#[derive(Deserialize)]
+struct Claims {
+    iat: u64,
+}
+
+/// Request extension marking requests that carried a valid token.
+#[derive(Clone, Copy, Debug)]
+pub struct Authenticated;
+
+pub struct JwtSecret([u8; JWT_SECRET_LENGTH]);
+
+impl JwtSecret {
+    pub fn from_hex(hex: &str) -> anyhow::Result<Self> {
+        let hex = hex.trim();
+        let bytes = hex::decode(hex.strip_prefix("0x").unwrap_or(hex))
+            .map_err(|e| format_err!("Invalid JWT secret: {e}"))?;
+        let secret = bytes.try_into().map_err(|_| {
+            format_err!("Invalid JWT secret, expected {JWT_SECRET_LENGTH} hex encoded bytes")
+        })?;
+        Ok(Self(secret))
+    }
+
+    /// Reads the secret at `path`, generating a random one readable only by the node user
+    /// when the file does not exist.
+    pub fn load_or_generate(path: &Path) -> anyhow::Result<Self> {
+        if path.exists() {
+            let hex = fs::read_to_string(path)
+                .map_err(|e| format_err!("Error reading JWT secret {}: {e}", path.display()))?;
+            return Self::from_hex(&hex);
+        }
+
+        let mut secret = [0u8; JWT_SECRET_LENGTH];
+        rand::thread_rng().fill_bytes(&mut secret);
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(path)
+            .and_then(|mut file| file.write_all(hex::encode(secret).as_bytes()))
+            .map_err(|e| format_err!("Error writing JWT secret {}: {e}", path.display()))?;
+        info!("Generated JWT secret at {}", path.display());
+        Ok(Self(secret))
+    }
+
+    fn validate(&self, token: &str, now: u64) -> Result<(), String> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        // Only `iat` is mandatory, `exp` is still checked when present
+        validation.required_spec_claims.clear();
+        let claims = decode::<Claims>(token, &DecodingKey::from_secret(&self.0), &validation)
+            .map_err(|e| format!("Invalid token: {e}"))?
+            .claims;
+        if claims.iat.abs_diff(now) > IAT_LEEWAY {
+            return Err(format!(
+                "Stale token, iat must be within {IAT_LEEWAY} seconds of the node's time"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates the bearer token of the `Authorization` header.
+    fn authorize(&self, headers: &HeaderMap) -> Result<(), String> {
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or("Missing bearer token")?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or_default();
+        self.validate(token.trim(), now)
+    }
+}
+
+pub struct AuthConfig {
+    pub secret: JwtSecret,
+    /// Methods requiring a token.
+    pub protected: Vec<MethodPattern>,
+}
+
+impl AuthConfig {
+    /// Returns the authentication settings, or `None` when disabled.
+    pub fn from_args() -> anyhow::Result<Option<Self>> {
+        if !ain_cpp_imports::is_eth_rpc_auth_enabled() {
+            return Ok(None);
+        }
+        let path = ain_cpp_imports::get_eth_rpc_jwt_secret_path();
+        let config = Self {
+            secret: JwtSecret::load_or_generate(Path::new(&path))?,
+            protected: parse_patterns(&ain_cpp_imports::get_eth_rpc_auth_methods()),
+        };
+        info!(
+            "ETH-RPC authentication enabled for {}",
+            config
+                .protected
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        Ok(Some(config))
+    }
+
+    pub fn is_protected(&self, method: &str) -> bool {
+        is_admin_method(method) || self.protected.iter().any(|p| p.matches(method))
+    }
+}
+
+fn unauthorized_response(id: Value, message: String) -> Response<Body> {
+    let mut response = rpc_error_response(StatusCode::UNAUTHORIZED, id, UNAUTHORIZED_CODE, message);
+    response
+        .headers_mut()
+        .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+}
+
+#[derive(Clone)]
+pub struct AuthLayer {
+    config: Option<Arc<AuthConfig>>,
+}
+
+impl AuthLayer {
+    pub fn new(config: Option<AuthConfig>) -> Self {
+        Self {
+            config: config.map(Arc::new),
+        }
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = Auth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Auth {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Auth<S> {
+    inner: S,
+    config: Option<Arc<AuthConfig>>,
+}
+
+impl<S> Service<Request<Body>> for Auth<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // Use the service that was polled ready, leaving a clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            let Some(config) = config else {
+                return inner.call(request).await;
+            };
+
+            if request.headers().contains_key(UPGRADE) {
+                if let Err(e) = config.secret.authorize(request.headers()) {
+                    return Ok(unauthorized_response(Value::Null, e));
+                }
+                return inner.call(request).await;
+            }
+            if request.method() != Method::POST {
+                return inner.call(request).await;
+            }
+
+            let (mut parts, body) = request.into_parts();
+            let Some(body) = read_body(body).await? else {
+                return Ok(payload_too_large_response());
+            };
+
+            let calls = parse_calls(&body);
+            if let Some((method, id)) = calls.iter().find(|(m, _)| config.is_protected(m)) {
+                if let Err(e) = config.secret.authorize(&parts.headers) {
+                    return Ok(unauthorized_response(
+                        id.clone(),
+                        format!("Method {method} requires authentication: {e}"),
+                    ));
+                }
+                parts.extensions.insert(Authenticated);
+            }
+
+            inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::json;
+
+    use super::*;
+
+    const SECRET: &str = "0x7365637265747365637265747365637265747365637265747365637265747365";
+
+    fn token(secret: &[u8], claims: Value) -> String {
+        encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(secret),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_parse_hex_secret() {
+        assert!(JwtSecret::from_hex(SECRET).is_ok());
+        assert!(JwtSecret::from_hex(&SECRET[2..]).is_ok());
+        assert!(JwtSecret::from_hex("0x1234").is_err());
+        assert!(JwtSecret::from_hex("not hex").is_err());
+    }
+
+    #[test]
+    fn should_validate_tokens() {
+        let secret = JwtSecret::from_hex(SECRET).unwrap();
+        let now = 1_700_000_000;
+
+        assert!(secret
+            .validate(&token(&secret.0, json!({ "iat": now })), now)
+            .is_ok());
+        assert!(secret
+            .validate(&token(&secret.0, json!({ "iat": now - 30 })), now)
+            .is_ok());
+        // Stale or missing iat
+        assert!(secret
+            .validate(&token(&secret.0, json!({ "iat": now - 120 })), now)
+            .is_err());
+        assert!(secret.validate(&token(&secret.0, json!({})), now).is_err());
+        // Wrong secret or algorithm
+        assert!(secret
+            .validate(&token(&[0u8; 32], json!({ "iat": now })), now)
+            .is_err());
+        let hs512 = encode(
+            &Header::new(Algorithm::HS512),
+            &json!({ "iat": now }),
+            &EncodingKey::from_secret(&secret.0),
+        )
+        .unwrap();
+        assert!(secret.validate(&hs512, now).is_err());
+    }
+
+    #[test]
+    fn should_match_protected_methods() {
+        let config = AuthConfig {
+            secret: JwtSecret::from_hex(SECRET).unwrap(),
+            protected: parse_patterns("debug_*,txpool_*,eth_sign*"),
+        };
+        assert!(config.is_protected("debug_traceTransaction"));
+        assert!(config.is_protected("txpool_content"));
+        assert!(config.is_protected("eth_signTypedData_v4"));
+        assert!(config.is_protected("admin_flushDb"));
+        assert!(!config.is_protected("eth_sendRawTransaction"));
+        assert!(!config.is_protected("eth_chainId"));
+    }
+}
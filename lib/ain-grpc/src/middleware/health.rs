@@ -0,0 +1,237 @@
+//! `GET /health` and `GET /ready` routes of the JSON-RPC HTTP server for load balancers.
+//! `/health` answers as long as the server runs, while `/ready` fails with 503 when the node
+//! is syncing or its latest EVM block is older than the configured age.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use ain_evm::{evm::EVMServices, storage::traits::BlockStorage};
+use hyper::{header::CONTENT_TYPE, Body, Method, Request, Response, StatusCode};
+use tower::{Layer, Service};
+
+use super::BoxError;
+
+#[derive(Debug)]
+struct NodeState {
+    /// Native chain height, -1 when the block index is not loaded yet.
+    current_height: i32,
+    highest_height: i32,
+    /// Number and timestamp of the latest EVM block.
+    latest_block: Option<(u64, u64)>,
+    peer_count: i32,
+    now: u64,
+}
+
+impl NodeState {
+    fn load(evm: &EVMServices) -> Result<Self, String> {
+        let (current_height, highest_height) =
+            ain_cpp_imports::get_sync_status().map_err(|e| e.to_string())?;
+        let latest_block = evm
+            .storage
+            .get_latest_block()
+            .map_err(|e| e.to_string())?
+            .map(|block| (block.header.number.low_u64(), block.header.timestamp));
+        Ok(Self {
+            current_height,
+            highest_height,
+            latest_block,
+            peer_count: ain_cpp_imports::get_num_connections(),
+            now: u64::try_from(ain_cpp_imports::get_current_time()).unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HealthStatus {
+    ready: bool,
+    /// Why the node is not ready.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    block_number: Option<u64>,
+    /// Seconds since the latest EVM block.
+    block_age: Option<u64>,
+    sync_gap: u64,
+    peer_count: i32,
+}
+
+impl HealthStatus {
+    fn new(state: &NodeState, max_block_age: u64) -> Self {
+        let sync_gap = u64::try_from(state.highest_height.saturating_sub(state.current_height))
+            .unwrap_or_default();
+        let block_age = state
+            .latest_block
+            .map(|(_, timestamp)| state.now.saturating_sub(timestamp));
+
+        let reason = match block_age {
+            _ if state.current_height == -1 => Some("Block index not available".to_string()),
+            _ if sync_gap > 0 => Some(format!("Syncing, {sync_gap} blocks behind")),
+            Some(age) if max_block_age > 0 && age > max_block_age => Some(format!(
+                "Latest EVM block is {age} seconds old, above the {max_block_age} seconds limit"
+            )),
+            _ => None,
+        };
+        Self {
+            ready: reason.is_none(),
+            reason,
+            block_number: state.latest_block.map(|(number, _)| number),
+            block_age,
+            sync_gap,
+            peer_count: state.peer_count,
+        }
+    }
+
+    fn unavailable(reason: String) -> Self {
+        Self {
+            reason: Some(reason),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct HealthLayer {
+    evm: Arc<EVMServices>,
+    /// Maximum age in seconds of the latest EVM block, 0 disables the check.
+    max_block_age: u64,
+}
+
+impl HealthLayer {
+    pub fn new(evm: Arc<EVMServices>) -> Self {
+        Self {
+            evm,
+            max_block_age: ain_cpp_imports::get_eth_rpc_ready_max_block_age(),
+        }
+    }
+}
+
+impl<S> Layer<S> for HealthLayer {
+    type Service = Health<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Health {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Health<S> {
+    inner: S,
+    layer: HealthLayer,
+}
+
+impl<S> Service<Request<Body>> for Health<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let readiness = match (request.method(), request.uri().path()) {
+            (&Method::GET, "/health") => false,
+            (&Method::GET, "/ready") => true,
+            _ => return Box::pin(self.inner.call(request)),
+        };
+        let HealthLayer { evm, max_block_age } = self.layer.clone();
+
+        Box::pin(async move {
+            // The FFI calls block on the node's locks, so they run off the runtime threads.
+            let state = tokio::task::spawn_blocking(move || NodeState::load(&evm))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|state| state);
+            let status = match state {
+                Ok(state) => HealthStatus::new(&state, max_block_age),
+                Err(e) => HealthStatus::unavailable(e),
+            };
+            let mut response = Response::new(Body::from(serde_json::to_string(&status)?));
+            if readiness && !status.ready {
+                *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+            }
+            response.headers_mut().insert(
+                CONTENT_TYPE,
+                hyper::header::HeaderValue::from_static("application/json"),
+            );
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synced_state() -> NodeState {
+        NodeState {
+            current_height: 100,
+            highest_height: 100,
+            latest_block: Some((50, 1_000)),
+            peer_count: 8,
+            now: 1_030,
+        }
+    }
+
+    #[test]
+    fn should_be_ready_when_synced() {
+        let status = HealthStatus::new(&synced_state(), 600);
+        assert_eq!(
+            status,
+            HealthStatus {
+                ready: true,
+                reason: None,
+                block_number: Some(50),
+                block_age: Some(30),
+                sync_gap: 0,
+                peer_count: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn should_not_be_ready_while_syncing() {
+        let state = NodeState {
+            highest_height: 120,
+            ..synced_state()
+        };
+        let status = HealthStatus::new(&state, 600);
+        assert!(!status.ready);
+        assert_eq!(status.sync_gap, 20);
+
+        let state = NodeState {
+            current_height: -1,
+            ..synced_state()
+        };
+        assert!(!HealthStatus::new(&state, 600).ready);
+    }
+
+    #[test]
+    fn should_not_be_ready_with_stale_block() {
+        let state = NodeState {
+            now: 2_000,
+            ..synced_state()
+        };
+        assert!(!HealthStatus::new(&state, 600).ready);
+        // Check disabled
+        assert!(HealthStatus::new(&state, 0).ready);
+
+        // No EVM block before the EVM activation
+        let state = NodeState {
+            latest_block: None,
+            ..synced_state()
+        };
+        assert!(HealthStatus::new(&state, 600).ready);
+    }
+}
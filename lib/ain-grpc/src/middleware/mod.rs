@@ -0,0 +1,114 @@
+//! Tower middleware applied to the JSON-RPC HTTP and websocket servers.
+
+pub mod access;
+pub mod auth;
+pub mod health;
+pub mod origin;
+
+use hyper::{body::HttpBody, header::CONTENT_TYPE, Body, Response, StatusCode};
+use serde_json::{json, Value};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Same as the default maximum request body size of the jsonrpsee server.
+const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+const UNAUTHORIZED_CODE: i32 = -32001;
+
+/// A method name, or a method prefix when ending with `*`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodPattern(String);
+
+impl MethodPattern {
+    pub fn new(pattern: &str) -> Self {
+        Self(pattern.trim().to_string())
+    }
+
+    pub fn matches(&self, method: &str) -> bool {
+        match self.0.strip_suffix('*') {
+            Some(prefix) => method.starts_with(prefix),
+            None => self.0 == method,
+        }
+    }
+}
+
+impl std::fmt::Display for MethodPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Methods of the admin namespace, only served to loopback clients or authenticated requests.
+fn is_admin_method(method: &str) -> bool {
+    method.starts_with("admin_")
+}
+
+fn parse_patterns(list: &str) -> Vec<MethodPattern> {
+    list.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(MethodPattern::new)
+        .collect()
+}
+
+/// Builds a JSON-RPC error response answering the request `id`.
+pub fn rpc_error_response(
+    status: StatusCode,
+    id: Value,
+    code: i32,
+    message: impl Into<String>,
+) -> Response<Body> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message.into() },
+        "id": id,
+    });
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+/// Returns the method and id of each call of a single or batch request. Calls without a
+/// method are left for the server to reject.
+fn parse_calls(body: &[u8]) -> Vec<(String, Value)> {
+    let Ok(request) = serde_json::from_slice::<Value>(body) else {
+        return Vec::new();
+    };
+    let calls = match request {
+        Value::Array(calls) => calls,
+        call => vec![call],
+    };
+    calls
+        .into_iter()
+        .filter_map(|mut call| {
+            let method = call.get("method")?.as_str()?.to_string();
+            let id = call.get_mut("id").map(Value::take).unwrap_or_default();
+            Some((method, id))
+        })
+        .collect()
+}
+
+/// Reads the request body, or returns `None` when larger than `MAX_BODY_SIZE`.
+async fn read_body(mut body: Body) -> Result<Option<Vec<u8>>, hyper::Error> {
+    let mut buf = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if buf.len() + chunk.len() > MAX_BODY_SIZE {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(Some(buf))
+}
+
+fn payload_too_large_response() -> Response<Body> {
+    rpc_error_response(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Value::Null,
+        LIMIT_EXCEEDED_CODE,
+        format!("Request body exceeds {MAX_BODY_SIZE} bytes"),
+    )
+}
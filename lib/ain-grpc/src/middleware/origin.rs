@@ -0,0 +1,292 @@
+//! Origin policy of the JSON-RPC HTTP and websocket servers, configured by `-rpcallowcors` as a
+//! comma separated list of origins, `*` for any origin, or subdomain patterns such as
+//! `https://*.example.com`. CORS headers only stop browsers from reading responses, so
+//! requests from a disallowed `Origin` are rejected before reaching the server. Requests
+//! without an `Origin` header, as sent by non-browser clients, are always served.
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use anyhow::format_err;
+use hyper::{
+    header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE, ORIGIN},
+    Body, Method, Request, Response, StatusCode,
+};
+use serde_json::Value;
+use tower::{Layer, Service};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+use super::{rpc_error_response, BoxError, UNAUTHORIZED_CODE};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum OriginPattern {
+    Any,
+    /// Lowercase origin without a trailing slash.
+    Exact(String),
+    /// Scheme, eg: `https://`, and host suffix including the port, eg: `.example.com:8080`.
+    Subdomain {
+        scheme: String,
+        suffix: String,
+    },
+}
+
+impl OriginPattern {
+    fn parse(pattern: &str) -> anyhow::Result<Self> {
+        let pattern = pattern.trim().trim_end_matches('/').to_ascii_lowercase();
+        if pattern == "*" {
+            return Ok(Self::Any);
+        }
+        let invalid =
+            || format_err!("Invalid CORS origin {pattern}, expected scheme://host[:port] or *");
+        let (scheme, host) = pattern.split_once("://").ok_or_else(invalid)?;
+        if scheme.is_empty() || host.is_empty() || host.contains('/') {
+            return Err(invalid());
+        }
+        match host.strip_prefix("*.") {
+            Some(suffix) if !suffix.is_empty() && !suffix.contains('*') => Ok(Self::Subdomain {
+                scheme: format!("{scheme}://"),
+                suffix: format!(".{suffix}"),
+            }),
+            None if !host.contains('*') => Ok(Self::Exact(pattern)),
+            _ => Err(format_err!(
+                "Invalid CORS origin {pattern}, wildcards are only supported as the first label of the host, eg: https://*.example.com"
+            )),
+        }
+    }
+
+    fn matches(&self, origin: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Exact(exact) => exact.eq_ignore_ascii_case(origin),
+            Self::Subdomain { scheme, suffix } => {
+                let origin = origin.to_ascii_lowercase();
+                origin
+                    .strip_prefix(scheme.as_str())
+                    .and_then(|host| host.strip_suffix(suffix.as_str()))
+                    .is_some_and(|subdomain| {
+                        !subdomain.is_empty()
+                            && !subdomain.starts_with('.')
+                            && subdomain
+                                .chars()
+                                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+                    })
+            }
+        }
+    }
+}
+
+impl fmt::Display for OriginPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => f.write_str("*"),
+            Self::Exact(origin) => f.write_str(origin),
+            Self::Subdomain { scheme, suffix } => write!(f, "{scheme}*{suffix}"),
+        }
+    }
+}
+
+/// Allowed origins, checks are disabled when empty.
+#[derive(Clone, Debug, Default)]
+pub struct OriginPolicy(Vec<OriginPattern>);
+
+impl OriginPolicy {
+    pub fn parse(list: &str) -> anyhow::Result<Self> {
+        list.split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(OriginPattern::parse)
+            .collect::<anyhow::Result<_>>()
+            .map(Self)
+    }
+
+    pub fn from_args() -> anyhow::Result<Self> {
+        Self::parse(&ain_cpp_imports::get_cors_allowed_origin())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.0.is_empty()
+    }
+
+    fn allows_any(&self) -> bool {
+        self.0.contains(&OriginPattern::Any)
+    }
+
+    fn is_allowed(&self, origin: &HeaderValue) -> bool {
+        origin
+            .to_str()
+            .is_ok_and(|origin| self.0.iter().any(|pattern| pattern.matches(origin)))
+    }
+
+    /// Returns the CORS headers of the policy. Credentials are allowed unless any origin is.
+    pub fn cors_layer(&self) -> CorsLayer {
+        if !self.is_enabled() {
+            return CorsLayer::new();
+        }
+        let cors = CorsLayer::new()
+            .allow_methods([Method::POST, Method::GET, Method::OPTIONS])
+            .allow_headers([CONTENT_TYPE, AUTHORIZATION]);
+        if self.allows_any() {
+            return cors.allow_origin(Any);
+        }
+        let policy = self.clone();
+        cors.allow_origin(AllowOrigin::predicate(move |origin, _| {
+            policy.is_allowed(origin)
+        }))
+        .allow_credentials(true)
+    }
+}
+
+impl fmt::Display for OriginPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let patterns = self.0.iter().map(ToString::to_string).collect::<Vec<_>>();
+        f.write_str(&patterns.join(","))
+    }
+}
+
+#[derive(Clone)]
+pub struct OriginLayer {
+    policy: Arc<OriginPolicy>,
+}
+
+impl OriginLayer {
+    pub fn new(policy: OriginPolicy) -> Self {
+        Self {
+            policy: Arc::new(policy),
+        }
+    }
+}
+
+impl<S> Layer<S> for OriginLayer {
+    type Service = OriginCheck<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        OriginCheck {
+            inner,
+            policy: Arc::clone(&self.policy),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OriginCheck<S> {
+    inner: S,
+    policy: Arc<OriginPolicy>,
+}
+
+impl<S> Service<Request<Body>> for OriginCheck<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let origin = request.headers().get(ORIGIN);
+        match origin {
+            Some(origin) if self.policy.is_enabled() && !self.policy.is_allowed(origin) => {
+                let message = format!(
+                    "Origin {} is not allowed, allowed origins: {}",
+                    String::from_utf8_lossy(origin.as_bytes()),
+                    self.policy
+                );
+                Box::pin(async move {
+                    Ok(rpc_error_response(
+                        StatusCode::FORBIDDEN,
+                        Value::Null,
+                        UNAUTHORIZED_CODE,
+                        message,
+                    ))
+                })
+            }
+            _ => Box::pin(self.inner.call(request)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+
+    fn is_allowed(policy: &OriginPolicy, origin: &str) -> bool {
+        policy.is_allowed(&HeaderValue::from_str(origin).unwrap())
+    }
+
+    #[test]
+    fn should_parse_origin_lists() {
+        let policy =
+            OriginPolicy::parse("http://127.0.0.1:5000/, https://*.Example.com ,").unwrap();
+        assert_eq!(
+            policy.to_string(),
+            "http://127.0.0.1:5000,https://*.example.com"
+        );
+        assert!(!policy.allows_any());
+        assert!(OriginPolicy::parse("*").unwrap().allows_any());
+        assert!(!OriginPolicy::parse("").unwrap().is_enabled());
+
+        assert!(OriginPolicy::parse("example.com").is_err());
+        assert!(OriginPolicy::parse("https://example.com/path").is_err());
+        assert!(OriginPolicy::parse("https://*").is_err());
+        assert!(OriginPolicy::parse("https://app.*.example.com").is_err());
+    }
+
+    #[test]
+    fn should_match_origins() {
+        let policy = OriginPolicy::parse("http://127.0.0.1:5000,https://*.example.com").unwrap();
+        assert!(is_allowed(&policy, "http://127.0.0.1:5000"));
+        assert!(is_allowed(&policy, "https://app.example.com"));
+        assert!(is_allowed(&policy, "https://a.b.Example.com"));
+
+        assert!(!is_allowed(&policy, "http://127.0.0.1:5001"));
+        assert!(!is_allowed(&policy, "https://example.com"));
+        assert!(!is_allowed(&policy, "http://app.example.com"));
+        assert!(!is_allowed(&policy, "https://app.example.com:8443"));
+        assert!(!is_allowed(&policy, "https://evilexample.com"));
+        assert!(!is_allowed(&policy, "https://evil.com?.example.com"));
+        assert!(!is_allowed(&policy, "null"));
+
+        assert!(is_allowed(&OriginPolicy::parse("*").unwrap(), "null"));
+    }
+
+    async fn call(policy: &str, origin: Option<&str>) -> StatusCode {
+        let service =
+            OriginLayer::new(OriginPolicy::parse(policy).unwrap()).layer(service_fn(|_| async {
+                Ok::<_, BoxError>(Response::new(Body::empty()))
+            }));
+        let mut request = Request::post("/");
+        if let Some(origin) = origin {
+            request = request.header(ORIGIN, origin);
+        }
+        let response = service
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        response.status()
+    }
+
+    #[tokio::test]
+    async fn should_reject_disallowed_origins() {
+        let policy = "https://*.example.com";
+        assert_eq!(
+            call(policy, Some("https://evil.com")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(policy, Some("https://app.example.com")).await,
+            StatusCode::OK
+        );
+        assert_eq!(call(policy, None).await, StatusCode::OK);
+        assert_eq!(call("", Some("https://evil.com")).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,163 @@
+//! Bounded pools of worker threads for RPC handlers running EVM execution or scanning the
+//! storage, so that heavy requests queue up on their own threads instead of stalling the
+//! tokio workers serving cheap ones. Tracing and calls get separate pools, and requests
+//! fail with a server busy error once a pool's queue is full.
+
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc,
+    },
+    thread,
+};
+
+use jsonrpsee::core::RpcResult;
+use lazy_static::lazy_static;
+use log::warn;
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+use crate::{
+    errors::{to_custom_err, RPCError},
+    metrics::QueueGuard,
+};
+
+lazy_static! {
+    /// Pool of `eth_call`, `eth_estimateGas`, `eth_createAccessList`, `debug_feeEstimate` and
+    /// log queries.
+    pub static ref CALL_POOL: BlockingPool = BlockingPool::new(
+        "call",
+        worker_count(ain_cpp_imports::get_eth_rpc_workers(), 1),
+        ain_cpp_imports::get_eth_rpc_worker_queue() as usize,
+    );
+    /// Pool of `debug_trace*` requests and of the Otterscan methods tracing a transaction.
+    pub static ref TRACE_POOL: BlockingPool = BlockingPool::new(
+        "trace",
+        worker_count(ain_cpp_imports::get_eth_rpc_trace_workers(), 2),
+        ain_cpp_imports::get_eth_rpc_worker_queue() as usize,
+    );
+}
+
+/// Returns `configured` workers, or the number of cores divided by `divisor` when 0.
+fn worker_count(configured: u32, divisor: usize) -> usize {
+    match configured {
+        0 => thread::available_parallelism()
+            .map_or(1, |cores| cores.get() / divisor)
+            .max(1),
+        n => n as usize,
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+pub struct BlockingPool {
+    name: &'static str,
+    sender: SyncSender<Job>,
+}
+
+impl BlockingPool {
+    /// Spawns `workers` threads sharing a queue of `queue_size` pending jobs.
+    pub fn new(name: &'static str, workers: usize, queue_size: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Job>(queue_size);
+        let receiver = Arc::new(Mutex::new(receiver));
+        for i in 0..workers.max(1) {
+            let receiver = Arc::clone(&receiver);
+            thread::Builder::new()
+                .name(format!("rpc-{name}-{i}"))
+                .spawn(move || run_worker(name, &receiver))
+                .expect("failed to spawn RPC worker thread");
+        }
+        Self { name, sender }
+    }
+
+    /// Queues `f` for a worker thread, failing with a server busy error when the queue is
+    /// full. The job is queued right away, the returned future waits for its result.
+    pub fn spawn<F, T>(&self, f: F) -> impl Future<Output = RpcResult<T>> + Send + 'static
+    where
+        F: FnOnce() -> RpcResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let queued = QueueGuard::new(self.name);
+        let job: Job = Box::new(move || {
+            drop(queued);
+            // The caller went away while the request was queued
+            if tx.is_closed() {
+                return;
+            }
+            let _ = tx.send(f());
+        });
+        let name = self.name;
+        let sent = self.sender.try_send(job).map_err(|e| match e {
+            TrySendError::Full(_) => RPCError::ServerBusy(name).into(),
+            TrySendError::Disconnected(_) => {
+                to_custom_err(format!("{name} worker pool is stopped"))
+            }
+        });
+
+        async move {
+            sent?;
+            rx.await
+                .map_err(|_| to_custom_err(format!("{name} worker failed")))?
+        }
+    }
+}
+
+fn run_worker(name: &str, receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        // The lock is released as soon as a job is received
+        let job = receiver.lock().recv();
+        let Ok(job) = job else {
+            break;
+        };
+        // Keep the worker alive, the caller gets an error from the dropped sender
+        if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+            warn!("RPC {name} worker panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_run_jobs_on_workers() {
+        let pool = BlockingPool::new("test", 2, 4);
+        let name = pool
+            .spawn(|| Ok(thread::current().name().map(String::from)))
+            .await
+            .unwrap();
+        assert!(name.unwrap().starts_with("rpc-test-"));
+
+        let result = pool.spawn(|| -> RpcResult<()> { panic!("boom") }).await;
+        assert!(result.is_err());
+        // Workers survive panics
+        assert_eq!(pool.spawn(|| Ok(1)).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_reject_jobs_when_queue_is_full() {
+        let pool = BlockingPool::new("busy", 1, 1);
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (started_tx, started_rx) = mpsc::channel::<()>();
+
+        let running = pool.spawn(move || {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+            Ok(())
+        });
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let queued = pool.spawn(|| Ok(()));
+        assert!(pool.spawn(|| Ok(())).await.is_err());
+
+        release_tx.send(()).unwrap();
+        assert!(running.await.is_ok());
+        assert!(queued.await.is_ok());
+    }
+}
@@ -0,0 +1,101 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use ain_evm::{evm::EVMServices, storage::traits::FlushableStorage};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use log::info;
+
+use crate::{
+    errors::{to_custom_err, RPCError},
+    logging,
+    rpc::web3,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    pub client_version: String,
+    pub chain_id: u64,
+    /// Schema version of the EVM block store.
+    pub db_version: u32,
+    /// RPC namespaces served by the node.
+    pub modules: Vec<String>,
+}
+
+/// Node administration, only served to local or authenticated clients.
+#[rpc(server, client, namespace = "admin")]
+pub trait MetachainAdminRPC {
+    /// Sets the log level (off, error, warn, info, debug or trace) of the given target and its
+    /// submodules, or of all targets without a level of their own when omitted.
+    #[method(name = "setLogLevel")]
+    fn set_log_level(&self, level: String, target: Option<String>) -> RpcResult<bool>;
+
+    /// Empties the block, transaction, trace and signed transaction caches. Senders of
+    /// transactions from impersonated accounts are kept, as they cannot be recovered.
+    #[method(name = "clearCaches")]
+    fn clear_caches(&self) -> RpcResult<bool>;
+
+    /// Flushes the memtables of the EVM block store to disk.
+    #[method(name = "flushDb")]
+    fn flush_db(&self) -> RpcResult<bool>;
+
+    /// Returns the RocksDB statistics of the EVM block store.
+    #[method(name = "dbStats")]
+    fn db_stats(&self) -> RpcResult<BTreeMap<String, u64>>;
+
+    /// Returns the client version, chain id, DB version and RPC namespaces of the node.
+    #[method(name = "nodeInfo")]
+    fn node_info(&self) -> RpcResult<NodeInfo>;
+}
+
+pub struct MetachainAdminRPCModule {
+    handler: Arc<EVMServices>,
+    modules: Vec<String>,
+}
+
+impl MetachainAdminRPCModule {
+    #[must_use]
+    pub fn new(handler: Arc<EVMServices>, modules: Vec<String>) -> Self {
+        Self { handler, modules }
+    }
+}
+
+impl MetachainAdminRPCServer for MetachainAdminRPCModule {
+    fn set_log_level(&self, level: String, target: Option<String>) -> RpcResult<bool> {
+        logging::set_log_level(target.as_deref(), &level).map_err(to_custom_err)?;
+        info!(
+            "Log level of {} set to {level}",
+            target.as_deref().unwrap_or("all targets")
+        );
+        Ok(true)
+    }
+
+    fn clear_caches(&self) -> RpcResult<bool> {
+        self.handler.storage.clear_cache();
+        self.handler.tracer.clear_cache();
+        self.handler.tx_cache.clear();
+        info!("Cleared EVM caches");
+        Ok(true)
+    }
+
+    fn flush_db(&self) -> RpcResult<bool> {
+        self.handler.storage.flush().map_err(to_custom_err)?;
+        Ok(true)
+    }
+
+    fn db_stats(&self) -> RpcResult<BTreeMap<String, u64>> {
+        let stats = self.handler.storage.db_stats().map_err(to_custom_err)?;
+        Ok(stats
+            .into_iter()
+            .map(|(property, value)| (property.to_string(), value))
+            .collect())
+    }
+
+    fn node_info(&self) -> RpcResult<NodeInfo> {
+        Ok(NodeInfo {
+            client_version: web3::client_version(),
+            chain_id: ain_cpp_imports::get_chain_id().map_err(RPCError::Error)?,
+            db_version: self.handler.storage.db_version().map_err(to_custom_err)?,
+            modules: self.modules.clone(),
+        })
+    }
+}
@@ -1,11 +1,20 @@
 use std::sync::Arc;
 
-use ain_evm::storage::{traits::BlockStorage, Storage};
+use ain_cpp_imports::get_eth_priv_key;
+use ain_evm::{
+    bytes::Bytes,
+    ecrecover::{public_key_to_address, recover_public_key},
+    storage::{traits::BlockStorage, Storage},
+};
 use ethereum::BlockAny;
-use ethereum_types::U256;
+use ethereum_types::{H160, H256, U256};
 use jsonrpsee::core::RpcResult;
+use libsecp256k1::SecretKey;
 
-use crate::{block::BlockNumber, errors::RPCError};
+use crate::{
+    block::BlockNumber,
+    errors::{to_custom_err, RPCError},
+};
 
 pub fn get_block(storage: &Arc<Storage>, block_number: Option<BlockNumber>) -> RpcResult<BlockAny> {
     match block_number.unwrap_or(BlockNumber::Latest) {
@@ -33,3 +42,65 @@ pub fn get_block(storage: &Arc<Storage>, block_number: Option<BlockNumber>) -> R
     .map_err(RPCError::EvmError)?
     .ok_or(RPCError::BlockNotFound.into())
 }
+
+/// Signs a 32 byte hash with the wallet key of `address`.
+/// Returns the 65 byte `r ‖ s ‖ v` signature with `v` in {27, 28}.
+pub fn sign_hash(address: H160, hash: H256) -> RpcResult<Bytes> {
+    let priv_key = get_eth_priv_key(address.to_fixed_bytes())
+        .map_err(|_| to_custom_err("Invalid private key"))?;
+    let secret_key = SecretKey::parse(&priv_key)
+        .map_err(|e| to_custom_err(format!("Error parsing SecretKey {e}")))?;
+
+    let signing_message = libsecp256k1::Message::parse(hash.as_fixed_bytes());
+    let (signature, recid) = libsecp256k1::sign(&signing_message, &secret_key);
+
+    let mut sig = signature.serialize().to_vec();
+    sig.push(27 + recid.serialize());
+    Ok(Bytes::from(sig))
+}
+
+/// Recovers the signer address of a 65 byte `r ‖ s ‖ v` signature over `hash`.
+/// Accepts `v` both as a recovery id and in the legacy {27, 28} form.
+pub fn recover_signer(hash: H256, signature: &[u8]) -> RpcResult<H160> {
+    if signature.len() != 65 {
+        return Err(to_custom_err("invalid signature length"));
+    }
+    let r = H256::from_slice(&signature[0..32]);
+    let s = H256::from_slice(&signature[32..64]);
+    let recovery_id = match signature[64] {
+        v @ (27 | 28) => v - 27,
+        v @ (0 | 1) => v,
+        v => return Err(to_custom_err(format!("invalid signature recovery id {v}"))),
+    };
+    let pubkey = recover_public_key(&hash, &r, &s, recovery_id)
+        .map_err(|e| to_custom_err(format!("invalid signature: {e:?}")))?;
+    Ok(public_key_to_address(&pubkey))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_recover_signer_of_both_v_forms() {
+        let secret_key = SecretKey::parse(&[0x11; 32]).unwrap();
+        let address = public_key_to_address(&libsecp256k1::PublicKey::from_secret_key(&secret_key));
+        let hash = H256::repeat_byte(0xab);
+        let (signature, recid) = libsecp256k1::sign(
+            &libsecp256k1::Message::parse(hash.as_fixed_bytes()),
+            &secret_key,
+        );
+
+        let mut sig = signature.serialize().to_vec();
+        sig.push(recid.serialize());
+        assert_eq!(recover_signer(hash, &sig).unwrap(), address);
+        sig[64] += 27;
+        assert_eq!(recover_signer(hash, &sig).unwrap(), address);
+
+        for v in [2, 3, 26, 29, 255] {
+            sig[64] = v;
+            assert!(recover_signer(hash, &sig).is_err());
+        }
+        assert!(recover_signer(hash, &sig[..64]).is_err());
+    }
+}
@@ -11,7 +11,7 @@ use ain_evm::{
 
 use ethereum_types::{H160, H256, U256};
 use jsonrpsee::{
-    core::{JsonValue, RpcResult},
+    core::{async_trait, JsonValue, RpcResult},
     proc_macros::rpc,
 };
 use log::debug;
@@ -21,6 +21,7 @@ use crate::{
     block::BlockNumber,
     call_request::{override_to_overlay, CallRequest, CallStateOverride},
     errors::{to_custom_err, RPCError},
+    pool::{CALL_POOL, TRACE_POOL},
     trace::{handle_trace_params, TraceParams},
 };
 
@@ -37,14 +38,14 @@ pub struct FeeEstimate {
 #[rpc(server, client, namespace = "debug")]
 pub trait MetachainDebugRPC {
     #[method(name = "traceTransaction")]
-    fn trace_transaction(
+    async fn trace_transaction(
         &self,
         tx_hash: H256,
         trace_params: Option<TraceParams>,
     ) -> RpcResult<TransactionTrace>;
 
     #[method(name = "traceCall")]
-    fn trace_call(
+    async fn trace_call(
         &self,
         call: CallRequest,
         block_number: BlockNumber,
@@ -53,14 +54,14 @@ pub trait MetachainDebugRPC {
     ) -> RpcResult<TransactionTrace>;
 
     #[method(name = "traceBlockByNumber")]
-    fn trace_block_by_number(
+    async fn trace_block_by_number(
         &self,
         block_number: BlockNumber,
         trace_params: Option<TraceParams>,
     ) -> RpcResult<Vec<JsonValue>>;
 
     #[method(name = "traceBlockByHash")]
-    fn trace_block_by_hash(
+    async fn trace_block_by_hash(
         &self,
         hash: H256,
         trace_params: Option<TraceParams>,
@@ -68,9 +69,10 @@ pub trait MetachainDebugRPC {
 
     // Get transaction fee estimate
     #[method(name = "feeEstimate")]
-    fn fee_estimate(&self, call: CallRequest) -> RpcResult<FeeEstimate>;
+    async fn fee_estimate(&self, call: CallRequest) -> RpcResult<FeeEstimate>;
 }
 
+#[derive(Clone)]
 pub struct MetachainDebugRPCModule {
     handler: Arc<EVMServices>,
 }
@@ -96,11 +98,71 @@ impl MetachainDebugRPCModule {
     }
 }
 
+#[async_trait]
 impl MetachainDebugRPCServer for MetachainDebugRPCModule {
     /// Replays a transaction in the Runtime at a given block height.
     /// In order to succesfully reproduce the result of the original transaction we need a correct
     /// state to replay over.
-    fn trace_transaction(
+    async fn trace_transaction(
+        &self,
+        tx_hash: H256,
+        trace_params: Option<TraceParams>,
+    ) -> RpcResult<TransactionTrace> {
+        let module = self.clone();
+        TRACE_POOL
+            .spawn(move || module.trace_transaction_blocking(tx_hash, trace_params))
+            .await
+    }
+
+    async fn trace_call(
+        &self,
+        call: CallRequest,
+        block_number: BlockNumber,
+        trace_params: Option<TraceParams>,
+        state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+    ) -> RpcResult<TransactionTrace> {
+        let module = self.clone();
+        TRACE_POOL
+            .spawn(move || {
+                module.trace_call_blocking(call, block_number, trace_params, state_overrides)
+            })
+            .await
+    }
+
+    async fn trace_block_by_number(
+        &self,
+        block_number: BlockNumber,
+        trace_params: Option<TraceParams>,
+    ) -> RpcResult<Vec<JsonValue>> {
+        let module = self.clone();
+        TRACE_POOL
+            .spawn(move || module.trace_block_by_number_blocking(block_number, trace_params))
+            .await
+    }
+
+    async fn trace_block_by_hash(
+        &self,
+        hash: H256,
+        trace_params: Option<TraceParams>,
+    ) -> RpcResult<Vec<JsonValue>> {
+        let module = self.clone();
+        TRACE_POOL
+            .spawn(move || module.trace_block_by_hash_blocking(hash, trace_params))
+            .await
+    }
+
+    async fn fee_estimate(&self, call: CallRequest) -> RpcResult<FeeEstimate> {
+        let module = self.clone();
+        CALL_POOL
+            .spawn(move || module.fee_estimate_blocking(call))
+            .await
+    }
+}
+
+impl MetachainDebugRPCModule {
+    // Heavy methods, run on the RPC worker pools
+
+    fn trace_transaction_blocking(
         &self,
         tx_hash: H256,
         trace_params: Option<TraceParams>,
@@ -140,7 +202,7 @@ impl MetachainDebugRPCServer for MetachainDebugRPCModule {
             .map_err(RPCError::EvmError)?)
     }
 
-    fn trace_call(
+    fn trace_call_blocking(
         &self,
         call: CallRequest,
         block_number: BlockNumber,
@@ -190,7 +252,7 @@ impl MetachainDebugRPCServer for MetachainDebugRPCModule {
             .map_err(RPCError::EvmError)?)
     }
 
-    fn trace_block_by_number(
+    fn trace_block_by_number_blocking(
         &self,
         block_number: BlockNumber,
         trace_params: Option<TraceParams>,
@@ -216,7 +278,7 @@ impl MetachainDebugRPCServer for MetachainDebugRPCModule {
         Ok(res)
     }
 
-    fn trace_block_by_hash(
+    fn trace_block_by_hash_blocking(
         &self,
         hash: H256,
         trace_params: Option<TraceParams>,
@@ -247,7 +309,7 @@ impl MetachainDebugRPCServer for MetachainDebugRPCModule {
         Ok(res)
     }
 
-    fn fee_estimate(&self, call: CallRequest) -> RpcResult<FeeEstimate> {
+    fn fee_estimate_blocking(&self, call: CallRequest) -> RpcResult<FeeEstimate> {
         self.is_enabled()?;
 
         debug!(target:"rpc",  "Fee estimate");
@@ -0,0 +1,304 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ain_evm::{bytes::Bytes, evm::EVMServices, storage::traits::BlockStorage};
+use ethereum::TransactionV2;
+use ethereum_types::{H160, H256, U256};
+use evm::backend::Basic;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use log::debug;
+use parking_lot::{Mutex, RwLock};
+
+use crate::{
+    errors::{to_custom_err, RPCError},
+    transaction_request::TransactionMessage,
+};
+
+lazy_static::lazy_static! {
+    // Accounts for which eth_sendTransaction skips signing
+    static ref IMPERSONATED_ACCOUNTS: RwLock<HashSet<H160>> = RwLock::new(HashSet::new());
+}
+
+/// Whether the address is impersonated through `hardhat_impersonateAccount`.
+pub fn is_impersonated(address: &H160) -> bool {
+    ain_cpp_imports::get_network() == "regtest" && IMPERSONATED_ACCOUNTS.read().contains(address)
+}
+
+/// Builds a transaction carrying a placeholder signature for an impersonated sender.
+/// The sender cannot be recovered from it, so it has to be registered in the block store
+/// with `put_impersonated_sender` before being published.
+pub fn impersonated_transaction(
+    from: H160,
+    message: TransactionMessage,
+) -> RpcResult<TransactionV2> {
+    let r = H256::from(from);
+    let s = H256::from_low_u64_be(1);
+
+    match message {
+        TransactionMessage::Legacy(m) => {
+            let v = match m.chain_id {
+                None => 27,
+                Some(chain_id) => 2 * chain_id + 35,
+            };
+            Ok(TransactionV2::Legacy(ethereum::LegacyTransaction {
+                nonce: m.nonce,
+                gas_price: m.gas_price,
+                gas_limit: m.gas_limit,
+                action: m.action,
+                value: m.value,
+                input: m.input,
+                signature: ethereum::TransactionSignature::new(v, r, s)
+                    .ok_or(to_custom_err("Invalid impersonation signature"))?,
+            }))
+        }
+        TransactionMessage::EIP2930(m) => {
+            Ok(TransactionV2::EIP2930(ethereum::EIP2930Transaction {
+                chain_id: m.chain_id,
+                nonce: m.nonce,
+                gas_price: m.gas_price,
+                gas_limit: m.gas_limit,
+                action: m.action,
+                value: m.value,
+                input: m.input,
+                access_list: m.access_list,
+                odd_y_parity: false,
+                r,
+                s,
+            }))
+        }
+        TransactionMessage::EIP1559(m) => {
+            Ok(TransactionV2::EIP1559(ethereum::EIP1559Transaction {
+                chain_id: m.chain_id,
+                nonce: m.nonce,
+                max_priority_fee_per_gas: m.max_priority_fee_per_gas,
+                max_fee_per_gas: m.max_fee_per_gas,
+                gas_limit: m.gas_limit,
+                action: m.action,
+                value: m.value,
+                input: m.input,
+                access_list: m.access_list,
+                odd_y_parity: false,
+                r,
+                s,
+            }))
+        }
+    }
+}
+
+#[rpc(server, client, namespace = "evm")]
+pub trait MetachainDevRPC {
+    /// Snapshots the state of the latest block.
+    /// Returns the snapshot id to be used with `evm_revert`.
+    #[method(name = "snapshot")]
+    fn snapshot(&self) -> RpcResult<U256>;
+
+    /// Reverts the DVM and EVM chains and the EVM state to the given snapshot. The snapshot and
+    /// all snapshots taken after it are discarded.
+    #[method(name = "revert")]
+    fn revert(&self, snapshot_id: U256) -> RpcResult<bool>;
+
+    /// Mines the given number of blocks, one by default.
+    /// Returns the number of blocks minted.
+    #[method(name = "mine", aliases = ["hardhat_mine"])]
+    fn mine(&self, blocks: Option<u32>) -> RpcResult<u32>;
+
+    /// Moves the node time forward by the given number of seconds.
+    /// Returns the total time adjustment in seconds, the node time minus the system time.
+    #[method(name = "increaseTime")]
+    fn increase_time(&self, seconds: i64) -> RpcResult<i64>;
+
+    #[method(name = "setBalance", aliases = ["hardhat_setBalance"])]
+    fn set_balance(&self, address: H160, balance: U256) -> RpcResult<bool>;
+
+    #[method(name = "setCode", aliases = ["hardhat_setCode"])]
+    fn set_code(&self, address: H160, code: Bytes) -> RpcResult<bool>;
+
+    #[method(name = "setStorageAt", aliases = ["hardhat_setStorageAt"])]
+    fn set_storage_at(&self, address: H160, position: U256, value: H256) -> RpcResult<bool>;
+
+    #[method(name = "setNonce", aliases = ["hardhat_setNonce"])]
+    fn set_nonce(&self, address: H160, nonce: U256) -> RpcResult<bool>;
+
+    /// Allows eth_sendTransaction from the given address without its private key.
+    #[method(name = "impersonateAccount", aliases = ["hardhat_impersonateAccount"])]
+    fn impersonate_account(&self, address: H160) -> RpcResult<bool>;
+
+    #[method(
+        name = "stopImpersonatingAccount",
+        aliases = ["hardhat_stopImpersonatingAccount"]
+    )]
+    fn stop_impersonating_account(&self, address: H160) -> RpcResult<bool>;
+}
+
+#[derive(Clone, Copy)]
+struct Snapshot {
+    dvm_height: u32,
+    block_number: U256,
+    state_root: H256,
+}
+
+pub struct MetachainDevRPCModule {
+    handler: Arc<EVMServices>,
+    snapshots: Mutex<BTreeMap<U256, Snapshot>>,
+    next_snapshot_id: Mutex<U256>,
+}
+
+impl MetachainDevRPCModule {
+    #[must_use]
+    pub fn new(handler: Arc<EVMServices>) -> Self {
+        Self {
+            handler,
+            snapshots: Mutex::new(BTreeMap::new()),
+            next_snapshot_id: Mutex::new(U256::one()),
+        }
+    }
+
+    fn is_enabled(&self) -> RpcResult<()> {
+        if ain_cpp_imports::get_network() != "regtest" {
+            return Err(RPCError::DevNotEnabled.into());
+        }
+        Ok(())
+    }
+
+    fn update_account<F>(&self, address: H160, f: F) -> RpcResult<bool>
+    where
+        F: FnOnce(&mut ain_evm::backend::EVMBackend) -> ain_evm::Result<()>,
+    {
+        self.is_enabled()?;
+        let state_root = self
+            .handler
+            .dev_update_latest_state(f)
+            .map_err(RPCError::EvmError)?;
+        debug!(target:"rpc", "[dev] Updated {:#x}, state root: {:#x}", address, state_root);
+        Ok(true)
+    }
+}
+
+impl MetachainDevRPCServer for MetachainDevRPCModule {
+    fn snapshot(&self) -> RpcResult<U256> {
+        self.is_enabled()?;
+        let block = self
+            .handler
+            .storage
+            .get_latest_block()
+            .map_err(RPCError::EvmError)?
+            .ok_or(RPCError::BlockNotFound)?;
+        let (dvm_height, _) = ain_cpp_imports::get_sync_status().map_err(RPCError::Error)?;
+        let dvm_height = u32::try_from(dvm_height).map_err(to_custom_err)?;
+
+        let mut next_id = self.next_snapshot_id.lock();
+        let id = *next_id;
+        *next_id = id.saturating_add(U256::one());
+        self.snapshots.lock().insert(
+            id,
+            Snapshot {
+                dvm_height,
+                block_number: block.header.number,
+                state_root: block.header.state_root,
+            },
+        );
+        Ok(id)
+    }
+
+    fn revert(&self, snapshot_id: U256) -> RpcResult<bool> {
+        self.is_enabled()?;
+        let mut snapshots = self.snapshots.lock();
+        let Some(snapshot) = snapshots.get(&snapshot_id).copied() else {
+            return Ok(false);
+        };
+        snapshots.retain(|id, _| *id < snapshot_id);
+
+        // Disconnecting the DVM blocks disconnects their EVM blocks along
+        if !ain_cpp_imports::rollback_to_block(snapshot.dvm_height) {
+            return Err(to_custom_err(format!(
+                "failed to roll back to block {}",
+                snapshot.dvm_height
+            )));
+        }
+        self.handler
+            .dev_revert_to(snapshot.block_number, snapshot.state_root)
+            .map_err(RPCError::EvmError)?;
+        Ok(true)
+    }
+
+    fn mine(&self, blocks: Option<u32>) -> RpcResult<u32> {
+        self.is_enabled()?;
+        let blocks = blocks.unwrap_or(1);
+        let minted = ain_cpp_imports::mine_blocks(blocks);
+        if minted < blocks {
+            return Err(to_custom_err(format!(
+                "only {minted} out of {blocks} blocks were minted"
+            )));
+        }
+        Ok(minted)
+    }
+
+    fn increase_time(&self, seconds: i64) -> RpcResult<i64> {
+        self.is_enabled()?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(to_custom_err)?
+            .as_secs();
+        let now = i64::try_from(now).map_err(to_custom_err)?;
+        let mock_time = ain_cpp_imports::increase_mock_time(seconds);
+        Ok(mock_time.saturating_sub(now))
+    }
+
+    fn set_balance(&self, address: H160, balance: U256) -> RpcResult<bool> {
+        self.update_account(address, |backend| {
+            let nonce = backend.get_nonce(&address);
+            backend.apply(
+                address,
+                Some(Basic { balance, nonce }),
+                None,
+                Vec::new(),
+                false,
+            )?;
+            Ok(())
+        })
+    }
+
+    fn set_code(&self, address: H160, code: Bytes) -> RpcResult<bool> {
+        self.update_account(address, |backend| {
+            backend.apply(address, None, Some(code.into_vec()), Vec::new(), false)?;
+            Ok(())
+        })
+    }
+
+    fn set_storage_at(&self, address: H160, position: U256, value: H256) -> RpcResult<bool> {
+        let mut key = [0u8; 32];
+        position.to_big_endian(&mut key);
+        self.update_account(address, |backend| {
+            backend.apply(address, None, None, vec![(H256(key), value)], false)?;
+            Ok(())
+        })
+    }
+
+    fn set_nonce(&self, address: H160, nonce: U256) -> RpcResult<bool> {
+        self.update_account(address, |backend| {
+            let balance = backend.get_balance(&address);
+            backend.apply(
+                address,
+                Some(Basic { balance, nonce }),
+                None,
+                Vec::new(),
+                false,
+            )?;
+            Ok(())
+        })
+    }
+
+    fn impersonate_account(&self, address: H160) -> RpcResult<bool> {
+        self.is_enabled()?;
+        IMPERSONATED_ACCOUNTS.write().insert(address);
+        Ok(true)
+    }
+
+    fn stop_impersonating_account(&self, address: H160) -> RpcResult<bool> {
+        self.is_enabled()?;
+        Ok(IMPERSONATED_ACCOUNTS.write().remove(&address))
+    }
+}
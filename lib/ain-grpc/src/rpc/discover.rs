@@ -0,0 +1,52 @@
+use jsonrpsee::{
+    core::{JsonValue, RpcResult},
+    proc_macros::rpc,
+};
+
+use crate::errors::to_custom_err;
+
+/// OpenRPC document of the node's namespaces, generated by the build script
+/// from the RPC traits.
+pub const OPENRPC_DOCUMENT: &str = include_str!(concat!(env!("OUT_DIR"), "/openrpc.json"));
+
+#[rpc(server, client, namespace = "rpc")]
+pub trait MetachainDiscoverRPC {
+    /// Returns the OpenRPC document describing the methods and subscriptions of the node.
+    #[method(name = "discover", aliases = ["rpc.discover"])]
+    fn discover(&self) -> RpcResult<JsonValue>;
+}
+
+pub struct MetachainDiscoverRPCModule;
+
+impl MetachainDiscoverRPCServer for MetachainDiscoverRPCModule {
+    fn discover(&self) -> RpcResult<JsonValue> {
+        serde_json::from_str(OPENRPC_DOCUMENT).map_err(to_custom_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_describe_rpc_methods() {
+        let document: JsonValue = serde_json::from_str(OPENRPC_DOCUMENT).unwrap();
+        let methods = document["methods"].as_array().unwrap();
+        for name in [
+            "eth_call",
+            "eth_subscribe",
+            "debug_traceTransaction",
+            "net_version",
+            "web3_sha3",
+            "rpc_discover",
+        ] {
+            assert!(
+                methods.iter().any(|method| method["name"] == name),
+                "{name} is missing"
+            );
+        }
+        for schema in ["CallRequest", "RpcBlock", "ReceiptResult", "TraceParams"] {
+            assert!(document["components"]["schemas"].get(schema).is_some());
+        }
+    }
+}
@@ -14,11 +14,11 @@ use ethereum::{EnvelopedEncodable, TransactionV2};
 use ethereum_types::{H160, H256, U256};
 use evm::{Config, ExitError, ExitReason};
 use jsonrpsee::{
-    core::{Error, RpcResult},
+    core::{async_trait, Error, RpcResult},
     proc_macros::rpc,
 };
 use libsecp256k1::SecretKey;
-use log::trace;
+use log::{trace, warn};
 
 use crate::{
     block::{BlockNumber, RpcBlock, RpcFeeHistory},
@@ -27,13 +27,18 @@ use crate::{
     errors::{to_custom_err, RPCError},
     filters::{GetFilterChangesResult, NewFilterRequest},
     logs::{GetLogsRequest, LogRequestTopics, LogResult},
+    pool::CALL_POOL,
     receipt::ReceiptResult,
     sync::{SyncInfo, SyncState},
     transaction_request::{TransactionMessage, TransactionRequest},
+    typed_data::{eth_message_hash, TypedData},
     utils::{format_h256, format_u256, try_get_reverted_error_or_default},
 };
 
-use super::common::get_block;
+use super::{
+    common::{get_block, sign_hash},
+    dev::{impersonated_transaction, is_impersonated},
+};
 
 #[rpc(server, client, namespace = "eth")]
 pub trait MetachainRPC {
@@ -44,7 +49,7 @@ pub trait MetachainRPC {
     /// Makes a call to the Ethereum node without creating a transaction on the blockchain.
     /// Returns the output data as a hexadecimal string.
     #[method(name = "call")]
-    fn call(
+    async fn call(
         &self,
         input: CallRequest,
         block_number: Option<BlockNumber>,
@@ -157,7 +162,7 @@ pub trait MetachainRPC {
 
     /// Create access list from a specified transaction call context.
     #[method(name = "createAccessList")]
-    fn create_access_list(
+    async fn create_access_list(
         &self,
         call: CallRequest,
         block_number: Option<BlockNumber>,
@@ -202,6 +207,17 @@ pub trait MetachainRPC {
     #[method(name = "signTransaction")]
     fn sign_transaction(&self, req: TransactionRequest) -> RpcResult<String>;
 
+    /// Signs an EIP-191 personal message with the key of the given address.
+    /// Returns the 65 byte signature.
+    #[method(name = "sign")]
+    fn sign(&self, address: H160, data: Bytes) -> RpcResult<Bytes>;
+
+    /// Signs EIP-712 typed structured data with the key of the given address.
+    /// Accepts the typed data either as a JSON object or a JSON encoded string.
+    /// Returns the 65 byte signature.
+    #[method(name = "signTypedData_v4")]
+    fn sign_typed_data_v4(&self, address: H160, typed_data: serde_json::Value) -> RpcResult<Bytes>;
+
     // ----------------------------------------
     // Send
     // ----------------------------------------
@@ -222,7 +238,7 @@ pub trait MetachainRPC {
 
     /// Estimate gas needed for execution of given contract.
     #[method(name = "estimateGas")]
-    fn estimate_gas(
+    async fn estimate_gas(
         &self,
         input: CallRequest,
         block_number: Option<BlockNumber>,
@@ -265,7 +281,7 @@ pub trait MetachainRPC {
     // ----------------------------------------
 
     #[method(name = "getLogs")]
-    fn get_logs(&self, input: GetLogsRequest) -> RpcResult<Vec<LogResult>>;
+    async fn get_logs(&self, input: GetLogsRequest) -> RpcResult<Vec<LogResult>>;
 
     #[method(name = "newFilter")]
     fn new_filter(&self, input: NewFilterRequest) -> RpcResult<U256>;
@@ -274,18 +290,19 @@ pub trait MetachainRPC {
     fn new_block_filter(&self) -> RpcResult<U256>;
 
     #[method(name = "getFilterChanges")]
-    fn get_filter_changes(&self, filter_id: U256) -> RpcResult<GetFilterChangesResult>;
+    async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<GetFilterChangesResult>;
 
     #[method(name = "uninstallFilter")]
     fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool>;
 
     #[method(name = "getFilterLogs")]
-    fn get_filter_logs(&self, filter_id: U256) -> RpcResult<Vec<LogResult>>;
+    async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<Vec<LogResult>>;
 
     #[method(name = "newPendingTransactionFilter")]
     fn new_pending_transaction_filter(&self) -> RpcResult<U256>;
 }
 
+#[derive(Clone)]
 pub struct MetachainRPCModule {
     handler: Arc<EVMServices>,
 }
@@ -297,60 +314,145 @@ impl MetachainRPCModule {
     pub fn new(handler: Arc<EVMServices>) -> Self {
         Self { handler }
     }
-}
 
-impl MetachainRPCServer for MetachainRPCModule {
-    fn call(
+    /// Fills in the sender, nonce, chain id and gas of a transaction request.
+    fn transaction_message(
         &self,
-        call: CallRequest,
-        block_number: Option<BlockNumber>,
-        state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
-    ) -> RpcResult<Bytes> {
-        trace!(target:"rpc",  "Call, input {:#?}", call);
+        request: TransactionRequest,
+    ) -> RpcResult<(H160, TransactionMessage)> {
+        let from = match request.from {
+            Some(from) => from,
+            None => {
+                let accounts = self.accounts()?;
 
-        let caller = call.from.unwrap_or_default();
-        let byte_data = call.get_data()?;
-        let data = byte_data.0.as_slice();
+                match accounts.first() {
+                    Some(account) => H160::from_str(account.as_str())
+                        .map_err(|_| to_custom_err("Wrong from address"))?,
+                    None => return Err(to_custom_err("from is not available")),
+                }
+            }
+        };
+        trace!(target:"rpc", "[sign_transaction] from: {:?}", from);
 
-        // Get gas
-        let block_gas_limit = ain_cpp_imports::get_attribute_values(None).block_gas_limit;
-        let gas_limit = u64::try_from(call.gas.unwrap_or(U256::from(block_gas_limit)))
-            .map_err(to_custom_err)?;
+        let chain_id = ain_cpp_imports::get_chain_id().map_err(RPCError::Error)?;
+        let Ok(state_root) = self.handler.core.get_latest_state_root() else {
+            return Err(RPCError::StateRootNotFound.into());
+        };
+        let nonce = match request.nonce {
+            Some(nonce) => nonce,
+            None => self
+                .handler
+                .core
+                .get_next_account_nonce(from, state_root)
+                .map_err(RPCError::EvmError)?,
+        };
 
-        let block = get_block(&self.handler.storage, block_number)?;
-        let block_base_fee = block.header.base_fee;
-        let gas_price = call.get_effective_gas_price()?.unwrap_or(block_base_fee);
+        let gas_price = request.gas_price;
+        let gas_limit = match request.gas {
+            Some(gas_limit) => gas_limit,
+            // TODO(): get the gas_limit from block.header
+            // set 21000 (min gas_limit req) by default first
+            None => U256::from(21000),
+        };
+        let max_fee_per_gas = request.max_fee_per_gas;
+        let message: Option<TransactionMessage> = request.into();
+        let message = match message {
+            Some(TransactionMessage::Legacy(mut m)) => {
+                m.nonce = nonce;
+                m.chain_id = Some(chain_id);
+                m.gas_limit = gas_limit;
+                if gas_price.is_none() {
+                    m.gas_price = self.gas_price()?;
+                }
+                TransactionMessage::Legacy(m)
+            }
+            Some(TransactionMessage::EIP2930(mut m)) => {
+                m.nonce = nonce;
+                m.chain_id = chain_id;
+                m.gas_limit = gas_limit;
+                if gas_price.is_none() {
+                    m.gas_price = self.gas_price()?;
+                }
+                TransactionMessage::EIP2930(m)
+            }
+            Some(TransactionMessage::EIP1559(mut m)) => {
+                m.nonce = nonce;
+                m.chain_id = chain_id;
+                m.gas_limit = gas_limit;
+                if max_fee_per_gas.is_none() {
+                    m.max_fee_per_gas = self.gas_price()?;
+                }
+                TransactionMessage::EIP1559(m)
+            }
+            _ => {
+                return Err(RPCError::InvalidTransactionMessage.into());
+            }
+        };
 
-        let TxResponse {
-            data, exit_reason, ..
-        } = self
+        Ok((from, message))
+    }
+
+    /// Publishes a raw transaction to the mempool.
+    fn publish_raw_transaction(tx: &str) -> RpcResult<()> {
+        let raw_tx = tx.strip_prefix("0x").unwrap_or(tx);
+        let hex = hex::decode(raw_tx).map_err(to_custom_err)?;
+
+        let res_string = ain_cpp_imports::publish_eth_transaction(hex).map_err(RPCError::Error)?;
+        if !res_string.is_empty() {
+            trace!(target:"rpc", "[send_raw_transaction] Could not publish raw transaction: {tx} reason: {res_string}");
+            return Err(Error::Custom(format!(
+                "Could not publish raw transaction: {tx} reason: {res_string}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Caches the sender nonce of a published raw transaction and returns its hash.
+    fn track_published_transaction(&self, tx: &str) -> RpcResult<String> {
+        let raw_tx = tx.strip_prefix("0x").unwrap_or(tx);
+        let signed_tx: SignedTx = self
             .handler
             .core
-            .call(
-                EthCallArgs {
-                    caller,
-                    to: call.to,
-                    value: call.value.unwrap_or_default(),
-                    data,
-                    gas_limit,
-                    gas_price,
-                    access_list: call.access_list.unwrap_or_default(),
-                    block_number: block.header.number,
-                },
-                state_overrides.map(override_to_overlay),
-            )
+            .tx_cache
+            .try_get_or_create(raw_tx)
             .map_err(RPCError::EvmError)?;
 
-        match exit_reason {
-            ExitReason::Succeed(_) => Ok(Bytes(data)),
-            ExitReason::Error(e) => Err(Error::Custom(format!("exit error {e:?}"))),
-            ExitReason::Revert(_) => {
-                let revert_msg = try_get_reverted_error_or_default(&data);
-                let encoded_data = format!("0x{}", hex::encode(data));
-                Err(RPCError::RevertError(revert_msg, encoded_data).into())
-            }
-            ExitReason::Fatal(e) => Err(Error::Custom(format!("fatal error {e:?}"))),
+        trace!(target:"rpc",
+            "[send_raw_transaction] signed_tx sender : {:#x}",
+            signed_tx.sender
+        );
+        trace!(target:"rpc",
+            "[send_raw_transaction] signed_tx nonce : {:#x}",
+            signed_tx.nonce()
+        );
+        trace!(target:"rpc",
+            "[send_raw_transaction] transaction hash : {:#x}",
+            signed_tx.hash()
+        );
+
+        if !self
+            .handler
+            .core
+            .store_account_nonce(signed_tx.sender, signed_tx.nonce())
+        {
+            return Err(RPCError::NonceCacheError.into());
         }
+        Ok(format!("{:#x}", signed_tx.hash()))
+    }
+}
+
+#[async_trait]
+impl MetachainRPCServer for MetachainRPCModule {
+    async fn call(
+        &self,
+        call: CallRequest,
+        block_number: Option<BlockNumber>,
+        state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+    ) -> RpcResult<Bytes> {
+        let module = self.clone();
+        CALL_POOL
+            .spawn(move || module.call_blocking(call, block_number, state_overrides))
+            .await
     }
 
     fn accounts(&self) -> RpcResult<Vec<String>> {
@@ -616,85 +718,46 @@ impl MetachainRPCServer for MetachainRPCModule {
 
     fn sign_transaction(&self, request: TransactionRequest) -> RpcResult<String> {
         trace!(target:"rpc", "Signing transaction: {:?}", request);
-
-        let from = match request.from {
-            Some(from) => from,
-            None => {
-                let accounts = self.accounts()?;
-
-                match accounts.first() {
-                    Some(account) => H160::from_str(account.as_str())
-                        .map_err(|_| to_custom_err("Wrong from address"))?,
-                    None => return Err(to_custom_err("from is not available")),
-                }
-            }
-        };
-        trace!(target:"rpc", "[sign_transaction] from: {:?}", from);
-
-        let chain_id = ain_cpp_imports::get_chain_id().map_err(RPCError::Error)?;
-        let Ok(state_root) = self.handler.core.get_latest_state_root() else {
-            return Err(RPCError::StateRootNotFound.into());
-        };
-        let nonce = match request.nonce {
-            Some(nonce) => nonce,
-            None => self
-                .handler
-                .core
-                .get_next_account_nonce(from, state_root)
-                .map_err(RPCError::EvmError)?,
-        };
-
-        let gas_price = request.gas_price;
-        let gas_limit = match request.gas {
-            Some(gas_limit) => gas_limit,
-            // TODO(): get the gas_limit from block.header
-            // set 21000 (min gas_limit req) by default first
-            None => U256::from(21000),
-        };
-        let max_fee_per_gas = request.max_fee_per_gas;
-        let message: Option<TransactionMessage> = request.into();
-        let message = match message {
-            Some(TransactionMessage::Legacy(mut m)) => {
-                m.nonce = nonce;
-                m.chain_id = Some(chain_id);
-                m.gas_limit = gas_limit;
-                if gas_price.is_none() {
-                    m.gas_price = self.gas_price()?;
-                }
-                TransactionMessage::Legacy(m)
-            }
-            Some(TransactionMessage::EIP2930(mut m)) => {
-                m.nonce = nonce;
-                m.chain_id = chain_id;
-                m.gas_limit = gas_limit;
-                if gas_price.is_none() {
-                    m.gas_price = self.gas_price()?;
-                }
-                TransactionMessage::EIP2930(m)
-            }
-            Some(TransactionMessage::EIP1559(mut m)) => {
-                m.nonce = nonce;
-                m.chain_id = chain_id;
-                m.gas_limit = gas_limit;
-                if max_fee_per_gas.is_none() {
-                    m.max_fee_per_gas = self.gas_price()?;
-                }
-                TransactionMessage::EIP1559(m)
-            }
-            _ => {
-                return Err(RPCError::InvalidTransactionMessage.into());
-            }
-        };
-
+        let (from, message) = self.transaction_message(request)?;
         let signed = sign(from, message)?;
         let encoded = hex::encode(signed.encode());
         Ok(encoded)
     }
 
+    fn sign(&self, address: H160, data: Bytes) -> RpcResult<Bytes> {
+        trace!(target:"rpc", "[sign] address: {:#x}", address);
+        sign_hash(address, eth_message_hash(&data.0))
+    }
+
+    fn sign_typed_data_v4(&self, address: H160, typed_data: serde_json::Value) -> RpcResult<Bytes> {
+        trace!(target:"rpc", "[sign_typed_data_v4] address: {:#x}", address);
+        let hash = TypedData::from_value(typed_data)
+            .and_then(|typed_data| typed_data.signing_hash())
+            .map_err(|e| to_custom_err(format!("invalid typed data: {e}")))?;
+        sign_hash(address, hash)
+    }
+
     fn send_transaction(&self, request: TransactionRequest) -> RpcResult<String> {
         trace!(target:"rpc", "Sending transaction: {:?}", request);
-        let signed = self.sign_transaction(request)?;
-        let hash = self.send_raw_transaction(signed.as_str())?;
+        let (from, message) = self.transaction_message(request)?;
+        let hash = if is_impersonated(&from) {
+            // The sender is registered first for the transaction to pass validation
+            let tx = impersonated_transaction(from, message)?;
+            let encoded = hex::encode(tx.encode());
+            let storage = &self.handler.storage;
+            storage
+                .put_impersonated_sender(tx.hash(), from)
+                .map_err(RPCError::EvmError)?;
+            if let Err(e) = Self::publish_raw_transaction(&encoded) {
+                if let Err(err) = storage.delete_impersonated_sender(&tx.hash()) {
+                    warn!(target:"rpc", "[send_transaction] failed to remove impersonated sender: {err}");
+                }
+                return Err(e);
+            }
+            self.track_published_transaction(&encoded)?
+        } else {
+            self.send_raw_transaction(&hex::encode(sign(from, message)?.encode()))?
+        };
 
         trace!(target:"rpc", "[send_transaction] signed: {:?}", hash);
         Ok(hash)
@@ -702,45 +765,8 @@ impl MetachainRPCServer for MetachainRPCModule {
 
     fn send_raw_transaction(&self, tx: &str) -> RpcResult<String> {
         trace!(target:"rpc", "Sending raw transaction: {:?}", tx);
-        let raw_tx = tx.strip_prefix("0x").unwrap_or(tx);
-        let hex = hex::decode(raw_tx).map_err(to_custom_err)?;
-
-        let res_string = ain_cpp_imports::publish_eth_transaction(hex).map_err(RPCError::Error)?;
-        if res_string.is_empty() {
-            let signed_tx: SignedTx = self
-                .handler
-                .core
-                .tx_cache
-                .try_get_or_create(raw_tx)
-                .map_err(RPCError::EvmError)?;
-
-            trace!(target:"rpc",
-                "[send_raw_transaction] signed_tx sender : {:#x}",
-                signed_tx.sender
-            );
-            trace!(target:"rpc",
-                "[send_raw_transaction] signed_tx nonce : {:#x}",
-                signed_tx.nonce()
-            );
-            trace!(target:"rpc",
-                "[send_raw_transaction] transaction hash : {:#x}",
-                signed_tx.hash()
-            );
-
-            if !self
-                .handler
-                .core
-                .store_account_nonce(signed_tx.sender, signed_tx.nonce())
-            {
-                return Err(RPCError::NonceCacheError.into());
-            }
-            Ok(format!("{:#x}", signed_tx.hash()))
-        } else {
-            trace!(target:"rpc", "[send_raw_transaction] Could not publish raw transaction: {tx} reason: {res_string}");
-            Err(Error::Custom(format!(
-                "Could not publish raw transaction: {tx} reason: {res_string}"
-            )))
-        }
+        Self::publish_raw_transaction(tx)?;
+        self.track_published_transaction(tx)
     }
 
     fn get_transaction_count(
@@ -768,22 +794,342 @@ impl MetachainRPCServer for MetachainRPCModule {
     /// startup. Otherwise, the default parameter is set at 15% error ratio.
     ///
     /// Ref: https://github.com/ethereum/go-ethereum/blob/e2778cd59f04f7587c9aa5983282074026ff6684/eth/gasestimator/gasestimator.go
-    fn estimate_gas(
+    async fn estimate_gas(
         &self,
         call: CallRequest,
         block_number: Option<BlockNumber>,
         state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
     ) -> RpcResult<U256> {
-        trace!(target:"rpc",  "Estimate gas, input {:#?}", call);
+        let module = self.clone();
+        CALL_POOL
+            .spawn(move || module.estimate_gas_blocking(call, block_number, state_overrides))
+            .await
+    }
 
-        let caller = call.from.unwrap_or_default();
-        let byte_data = call.get_data()?;
-        let data = byte_data.0.as_slice();
-        let overlay = state_overrides.map(override_to_overlay);
+    fn gas_price(&self) -> RpcResult<U256> {
+        self.handler
+            .block
+            .suggest_legacy_fee()
+            .map_err(to_custom_err)
+    }
 
-        let block_gas_limit = ain_cpp_imports::get_attribute_values(None).block_gas_limit;
-        let call_gas = u64::try_from(call.gas.unwrap_or(U256::from(block_gas_limit)))
-            .map_err(to_custom_err)?;
+    fn get_receipt(&self, hash: H256) -> RpcResult<Option<ReceiptResult>> {
+        self.handler
+            .storage
+            .get_receipt(&hash)
+            .map_err(to_custom_err)?
+            .map_or(Ok(None), |receipt| Ok(Some(ReceiptResult::from(receipt))))
+    }
+
+    fn get_work(&self) -> RpcResult<Vec<String>> {
+        Ok(vec![
+            "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+        ])
+    }
+
+    async fn create_access_list(
+        &self,
+        call: CallRequest,
+        block_number: Option<BlockNumber>,
+    ) -> RpcResult<AccessListResult> {
+        let module = self.clone();
+        CALL_POOL
+            .spawn(move || module.create_access_list_blocking(call, block_number))
+            .await
+    }
+
+    fn submit_work(&self, _nonce: String, _hash: String, _digest: String) -> RpcResult<bool> {
+        Ok(false)
+    }
+
+    fn submit_hashrate(&self, _hashrate: String, _id: String) -> RpcResult<bool> {
+        Ok(false)
+    }
+
+    fn fee_history(
+        &self,
+        block_count: U256,
+        newest_block: BlockNumber,
+        reward_percentile: Vec<i64>,
+    ) -> RpcResult<RpcFeeHistory> {
+        let highest_block_number = get_block(&self.handler.storage, Some(newest_block))?
+            .header
+            .number;
+        let attrs = ain_cpp_imports::get_attribute_values(None);
+
+        let fee_history = self
+            .handler
+            .block
+            .fee_history(
+                block_count,
+                highest_block_number,
+                reward_percentile,
+                attrs.block_gas_target_factor,
+            )
+            .map_err(RPCError::EvmError)?;
+
+        Ok(RpcFeeHistory::from(fee_history))
+    }
+
+    fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
+        self.handler
+            .block
+            .suggest_priority_fee()
+            .map_err(to_custom_err)
+    }
+
+    fn syncing(&self) -> RpcResult<SyncState> {
+        let (current_native_height, highest_native_block) =
+            ain_cpp_imports::get_sync_status().map_err(RPCError::Error)?;
+
+        if current_native_height == -1 {
+            return Err(to_custom_err("Block index not available"));
+        }
+
+        match current_native_height != highest_native_block {
+            true => {
+                let current_block = self
+                    .handler
+                    .storage
+                    .get_latest_block()
+                    .map_err(to_custom_err)?
+                    .map(|block| block.header.number)
+                    .ok_or(RPCError::BlockNotFound)?;
+
+                let starting_block = self.handler.block.get_starting_block_number();
+
+                let highest_block = current_block + (highest_native_block - current_native_height); // safe since current height cannot be higher than seen height
+                trace!(target:"rpc", "Highest native: {highest_native_block}\nCurrent native: {current_native_height}\nCurrent ETH: {current_block}\nHighest ETH: {highest_block}");
+
+                Ok(SyncState::Syncing(SyncInfo {
+                    starting_block,
+                    current_block,
+                    highest_block,
+                }))
+            }
+            false => Ok(SyncState::Synced(false)),
+        }
+    }
+    fn get_uncle_count_by_block_number(&self) -> RpcResult<U256> {
+        Ok(U256::default())
+    }
+
+    fn get_uncle_count_by_block_hash(&self) -> RpcResult<U256> {
+        Ok(U256::default())
+    }
+
+    fn get_uncle_by_block_number(&self) -> RpcResult<Option<bool>> {
+        Ok(None)
+    }
+
+    fn get_uncle_by_block_hash(&self) -> RpcResult<Option<bool>> {
+        Ok(None)
+    }
+
+    async fn get_logs(&self, input: GetLogsRequest) -> RpcResult<Vec<LogResult>> {
+        let module = self.clone();
+        CALL_POOL
+            .spawn(move || module.get_logs_blocking(input))
+            .await
+    }
+
+    fn new_filter(&self, input: NewFilterRequest) -> RpcResult<U256> {
+        let from_block = if input.from_block.is_some() {
+            if let Some(BlockNumber::Num(block_num)) = input.from_block {
+                // Allow future block number to be specified
+                Some(U256::from(block_num))
+            } else {
+                Some(
+                    get_block(&self.handler.storage, input.from_block)?
+                        .header
+                        .number,
+                )
+            }
+        } else {
+            None
+        };
+        let to_block = if input.to_block.is_some() {
+            if let Some(BlockNumber::Num(block_num)) = input.to_block {
+                // Allow future block number to be specified
+                Some(U256::from(block_num))
+            } else {
+                Some(
+                    get_block(&self.handler.storage, input.to_block)?
+                        .header
+                        .number,
+                )
+            }
+        } else {
+            None
+        };
+        let topics = input.topics.map(|topics| match topics {
+            LogRequestTopics::VecOfHashes(inputs) => {
+                inputs.iter().flatten().map(|input| vec![*input]).collect()
+            }
+            LogRequestTopics::VecOfHashVecs(inputs) => inputs
+                .iter()
+                .map(|hashes| hashes.iter().flatten().copied().collect())
+                .collect(),
+        });
+        let curr_block = get_block(&self.handler.storage, Some(BlockNumber::Latest))?
+            .header
+            .number;
+        let mut criteria = FilterCriteria {
+            from_block,
+            to_block,
+            addresses: input.address,
+            topics,
+            ..Default::default()
+        };
+        criteria
+            .verify_criteria(curr_block)
+            .map_err(RPCError::EvmError)?;
+        Ok(self.handler.filters.create_log_filter(criteria).into())
+    }
+
+    fn new_block_filter(&self) -> RpcResult<U256> {
+        Ok(self
+            .handler
+            .filters
+            .create_block_filter()
+            .map_err(RPCError::EvmError)?
+            .into())
+    }
+
+    fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
+        Ok(self.handler.filters.create_tx_filter().into())
+    }
+
+    async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<GetFilterChangesResult> {
+        let module = self.clone();
+        CALL_POOL
+            .spawn(move || module.get_filter_changes_blocking(filter_id))
+            .await
+    }
+
+    fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
+        let filter_id = usize::try_from(filter_id).map_err(to_custom_err)?;
+        Ok(self.handler.filters.delete_filter(filter_id))
+    }
+
+    async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<Vec<LogResult>> {
+        let module = self.clone();
+        CALL_POOL
+            .spawn(move || module.get_filter_logs_blocking(filter_id))
+            .await
+    }
+}
+
+impl MetachainRPCModule {
+    // Heavy methods, run on the RPC worker pools
+
+    fn call_blocking(
+        &self,
+        call: CallRequest,
+        block_number: Option<BlockNumber>,
+        state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+    ) -> RpcResult<Bytes> {
+        trace!(target:"rpc",  "Call, input {:#?}", call);
+
+        let caller = call.from.unwrap_or_default();
+        let byte_data = call.get_data()?;
+        let data = byte_data.0.as_slice();
+
+        // Get gas
+        let block_gas_limit = ain_cpp_imports::get_attribute_values(None).block_gas_limit;
+        let gas_limit = u64::try_from(call.gas.unwrap_or(U256::from(block_gas_limit)))
+            .map_err(to_custom_err)?;
+
+        let block = get_block(&self.handler.storage, block_number)?;
+        let block_base_fee = block.header.base_fee;
+        let gas_price = call.get_effective_gas_price()?.unwrap_or(block_base_fee);
+
+        let TxResponse {
+            data, exit_reason, ..
+        } = self
+            .handler
+            .core
+            .call(
+                EthCallArgs {
+                    caller,
+                    to: call.to,
+                    value: call.value.unwrap_or_default(),
+                    data,
+                    gas_limit,
+                    gas_price,
+                    access_list: call.access_list.unwrap_or_default(),
+                    block_number: block.header.number,
+                },
+                state_overrides.map(override_to_overlay),
+            )
+            .map_err(RPCError::EvmError)?;
+
+        match exit_reason {
+            ExitReason::Succeed(_) => Ok(Bytes(data)),
+            ExitReason::Error(e) => Err(Error::Custom(format!("exit error {e:?}"))),
+            ExitReason::Revert(_) => {
+                let revert_msg = try_get_reverted_error_or_default(&data);
+                let encoded_data = format!("0x{}", hex::encode(data));
+                Err(RPCError::RevertError(revert_msg, encoded_data).into())
+            }
+            ExitReason::Fatal(e) => Err(Error::Custom(format!("fatal error {e:?}"))),
+        }
+    }
+
+    fn create_access_list_blocking(
+        &self,
+        call: CallRequest,
+        block_number: Option<BlockNumber>,
+    ) -> RpcResult<AccessListResult> {
+        let caller = call.from.unwrap_or_default();
+        let byte_data = call.get_data()?;
+        let data = byte_data.0.as_slice();
+
+        // Get gas
+        let block_gas_limit = ain_cpp_imports::get_attribute_values(None).block_gas_limit;
+        let gas_limit = u64::try_from(call.gas.unwrap_or(U256::from(block_gas_limit)))
+            .map_err(to_custom_err)?;
+
+        let block = get_block(&self.handler.storage, block_number)?;
+        let block_base_fee = block.header.base_fee;
+        let gas_price = call.get_effective_gas_price()?.unwrap_or(block_base_fee);
+
+        let res = self
+            .handler
+            .tracer
+            .create_access_list(EthCallArgs {
+                caller,
+                to: call.to,
+                value: call.value.unwrap_or_default(),
+                data,
+                gas_limit,
+                gas_price,
+                access_list: call.access_list.unwrap_or_default(),
+                block_number: block.header.number,
+            })
+            .map_err(RPCError::EvmError)?
+            .into();
+        Ok(res)
+    }
+
+    fn estimate_gas_blocking(
+        &self,
+        call: CallRequest,
+        block_number: Option<BlockNumber>,
+        state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+    ) -> RpcResult<U256> {
+        trace!(target:"rpc",  "Estimate gas, input {:#?}", call);
+
+        let caller = call.from.unwrap_or_default();
+        let byte_data = call.get_data()?;
+        let data = byte_data.0.as_slice();
+        let overlay = state_overrides.map(override_to_overlay);
+
+        let block_gas_limit = ain_cpp_imports::get_attribute_values(None).block_gas_limit;
+        let call_gas = u64::try_from(call.gas.unwrap_or(U256::from(block_gas_limit)))
+            .map_err(to_custom_err)?;
 
         // Determine the highest gas limit can be used during the estimation.
         let mut hi = block_gas_limit;
@@ -946,154 +1292,7 @@ impl MetachainRPCServer for MetachainRPCModule {
         Ok(U256::from(hi))
     }
 
-    fn gas_price(&self) -> RpcResult<U256> {
-        self.handler
-            .block
-            .suggest_legacy_fee()
-            .map_err(to_custom_err)
-    }
-
-    fn get_receipt(&self, hash: H256) -> RpcResult<Option<ReceiptResult>> {
-        self.handler
-            .storage
-            .get_receipt(&hash)
-            .map_err(to_custom_err)?
-            .map_or(Ok(None), |receipt| Ok(Some(ReceiptResult::from(receipt))))
-    }
-
-    fn get_work(&self) -> RpcResult<Vec<String>> {
-        Ok(vec![
-            "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
-            "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
-            "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
-        ])
-    }
-
-    fn create_access_list(
-        &self,
-        call: CallRequest,
-        block_number: Option<BlockNumber>,
-    ) -> RpcResult<AccessListResult> {
-        let caller = call.from.unwrap_or_default();
-        let byte_data = call.get_data()?;
-        let data = byte_data.0.as_slice();
-
-        // Get gas
-        let block_gas_limit = ain_cpp_imports::get_attribute_values(None).block_gas_limit;
-        let gas_limit = u64::try_from(call.gas.unwrap_or(U256::from(block_gas_limit)))
-            .map_err(to_custom_err)?;
-
-        let block = get_block(&self.handler.storage, block_number)?;
-        let block_base_fee = block.header.base_fee;
-        let gas_price = call.get_effective_gas_price()?.unwrap_or(block_base_fee);
-
-        let res = self
-            .handler
-            .tracer
-            .create_access_list(EthCallArgs {
-                caller,
-                to: call.to,
-                value: call.value.unwrap_or_default(),
-                data,
-                gas_limit,
-                gas_price,
-                access_list: call.access_list.unwrap_or_default(),
-                block_number: block.header.number,
-            })
-            .map_err(RPCError::EvmError)?
-            .into();
-        Ok(res)
-    }
-
-    fn submit_work(&self, _nonce: String, _hash: String, _digest: String) -> RpcResult<bool> {
-        Ok(false)
-    }
-
-    fn submit_hashrate(&self, _hashrate: String, _id: String) -> RpcResult<bool> {
-        Ok(false)
-    }
-
-    fn fee_history(
-        &self,
-        block_count: U256,
-        newest_block: BlockNumber,
-        reward_percentile: Vec<i64>,
-    ) -> RpcResult<RpcFeeHistory> {
-        let highest_block_number = get_block(&self.handler.storage, Some(newest_block))?
-            .header
-            .number;
-        let attrs = ain_cpp_imports::get_attribute_values(None);
-
-        let fee_history = self
-            .handler
-            .block
-            .fee_history(
-                block_count,
-                highest_block_number,
-                reward_percentile,
-                attrs.block_gas_target_factor,
-            )
-            .map_err(RPCError::EvmError)?;
-
-        Ok(RpcFeeHistory::from(fee_history))
-    }
-
-    fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
-        self.handler
-            .block
-            .suggest_priority_fee()
-            .map_err(to_custom_err)
-    }
-
-    fn syncing(&self) -> RpcResult<SyncState> {
-        let (current_native_height, highest_native_block) =
-            ain_cpp_imports::get_sync_status().map_err(RPCError::Error)?;
-
-        if current_native_height == -1 {
-            return Err(to_custom_err("Block index not available"));
-        }
-
-        match current_native_height != highest_native_block {
-            true => {
-                let current_block = self
-                    .handler
-                    .storage
-                    .get_latest_block()
-                    .map_err(to_custom_err)?
-                    .map(|block| block.header.number)
-                    .ok_or(RPCError::BlockNotFound)?;
-
-                let starting_block = self.handler.block.get_starting_block_number();
-
-                let highest_block = current_block + (highest_native_block - current_native_height); // safe since current height cannot be higher than seen height
-                trace!(target:"rpc", "Highest native: {highest_native_block}\nCurrent native: {current_native_height}\nCurrent ETH: {current_block}\nHighest ETH: {highest_block}");
-
-                Ok(SyncState::Syncing(SyncInfo {
-                    starting_block,
-                    current_block,
-                    highest_block,
-                }))
-            }
-            false => Ok(SyncState::Synced(false)),
-        }
-    }
-    fn get_uncle_count_by_block_number(&self) -> RpcResult<U256> {
-        Ok(U256::default())
-    }
-
-    fn get_uncle_count_by_block_hash(&self) -> RpcResult<U256> {
-        Ok(U256::default())
-    }
-
-    fn get_uncle_by_block_number(&self) -> RpcResult<Option<bool>> {
-        Ok(None)
-    }
-
-    fn get_uncle_by_block_hash(&self) -> RpcResult<Option<bool>> {
-        Ok(None)
-    }
-
-    fn get_logs(&self, input: GetLogsRequest) -> RpcResult<Vec<LogResult>> {
+    fn get_logs_blocking(&self, input: GetLogsRequest) -> RpcResult<Vec<LogResult>> {
         let from_block = if input.from_block.is_some() {
             if let Some(BlockNumber::Num(block_num)) = input.from_block {
                 // Allow future block number to be specified
@@ -1152,74 +1351,7 @@ impl MetachainRPCServer for MetachainRPCModule {
         Ok(logs.into_iter().map(|log| log.into()).collect())
     }
 
-    fn new_filter(&self, input: NewFilterRequest) -> RpcResult<U256> {
-        let from_block = if input.from_block.is_some() {
-            if let Some(BlockNumber::Num(block_num)) = input.from_block {
-                // Allow future block number to be specified
-                Some(U256::from(block_num))
-            } else {
-                Some(
-                    get_block(&self.handler.storage, input.from_block)?
-                        .header
-                        .number,
-                )
-            }
-        } else {
-            None
-        };
-        let to_block = if input.to_block.is_some() {
-            if let Some(BlockNumber::Num(block_num)) = input.to_block {
-                // Allow future block number to be specified
-                Some(U256::from(block_num))
-            } else {
-                Some(
-                    get_block(&self.handler.storage, input.to_block)?
-                        .header
-                        .number,
-                )
-            }
-        } else {
-            None
-        };
-        let topics = input.topics.map(|topics| match topics {
-            LogRequestTopics::VecOfHashes(inputs) => {
-                inputs.iter().flatten().map(|input| vec![*input]).collect()
-            }
-            LogRequestTopics::VecOfHashVecs(inputs) => inputs
-                .iter()
-                .map(|hashes| hashes.iter().flatten().copied().collect())
-                .collect(),
-        });
-        let curr_block = get_block(&self.handler.storage, Some(BlockNumber::Latest))?
-            .header
-            .number;
-        let mut criteria = FilterCriteria {
-            from_block,
-            to_block,
-            addresses: input.address,
-            topics,
-            ..Default::default()
-        };
-        criteria
-            .verify_criteria(curr_block)
-            .map_err(RPCError::EvmError)?;
-        Ok(self.handler.filters.create_log_filter(criteria).into())
-    }
-
-    fn new_block_filter(&self) -> RpcResult<U256> {
-        Ok(self
-            .handler
-            .filters
-            .create_block_filter()
-            .map_err(RPCError::EvmError)?
-            .into())
-    }
-
-    fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
-        Ok(self.handler.filters.create_tx_filter().into())
-    }
-
-    fn get_filter_changes(&self, filter_id: U256) -> RpcResult<GetFilterChangesResult> {
+    fn get_filter_changes_blocking(&self, filter_id: U256) -> RpcResult<GetFilterChangesResult> {
         let filter_id = usize::try_from(filter_id).map_err(to_custom_err)?;
         let curr_block = get_block(&self.handler.storage, Some(BlockNumber::Latest))?
             .header
@@ -1233,12 +1365,7 @@ impl MetachainRPCServer for MetachainRPCModule {
         Ok(res)
     }
 
-    fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
-        let filter_id = usize::try_from(filter_id).map_err(to_custom_err)?;
-        Ok(self.handler.filters.delete_filter(filter_id))
-    }
-
-    fn get_filter_logs(&self, filter_id: U256) -> RpcResult<Vec<LogResult>> {
+    fn get_filter_logs_blocking(&self, filter_id: U256) -> RpcResult<Vec<LogResult>> {
         let filter_id = usize::try_from(filter_id).map_err(to_custom_err)?;
         let curr_block = get_block(&self.handler.storage, Some(BlockNumber::Latest))?
             .header
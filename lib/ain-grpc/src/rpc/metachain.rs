@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use ain_evm::{
+    evm::EVMServices,
+    storage::{traits::BlockStorage, Direction},
+};
+use ethereum_types::{H160, U256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::{
+    codegen::types::EthTransactionInfo,
+    errors::{to_custom_err, RPCError},
+};
+
+/// Default number of transactions returned by `metachain_getAddressTransactions`.
+const ADDRESS_TRANSACTIONS_DEFAULT_LIMIT: usize = 100;
+/// Maximum number of transactions returned by `metachain_getAddressTransactions`.
+const ADDRESS_TRANSACTIONS_MAX_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactions {
+    pub transactions: Vec<EthTransactionInfo>,
+    /// Cursor to pass in to fetch the next page, `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+#[rpc(server, client, namespace = "metachain")]
+pub trait MetachainIndexRPC {
+    /// Returns the transactions the address appears in, most recent first.
+    /// This includes transactions it sent or received, contracts it created, logs it emitted
+    /// and TransferDomain and DST20 bridge transfers it took part in.
+    #[method(name = "getAddressTransactions")]
+    fn get_address_transactions(
+        &self,
+        address: H160,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<AddressTransactions>;
+}
+
+pub struct MetachainIndexRPCModule {
+    handler: Arc<EVMServices>,
+}
+
+impl MetachainIndexRPCModule {
+    #[must_use]
+    pub fn new(handler: Arc<EVMServices>) -> Self {
+        Self { handler }
+    }
+}
+
+impl MetachainIndexRPCServer for MetachainIndexRPCModule {
+    fn get_address_transactions(
+        &self,
+        address: H160,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<AddressTransactions> {
+        let limit = limit.unwrap_or(ADDRESS_TRANSACTIONS_DEFAULT_LIMIT);
+        if limit == 0 || limit > ADDRESS_TRANSACTIONS_MAX_LIMIT {
+            return Err(to_custom_err(format!(
+                "limit must be between 1 and {ADDRESS_TRANSACTIONS_MAX_LIMIT}"
+            )));
+        }
+        let from = cursor.as_deref().map(parse_cursor).transpose()?;
+
+        // Fetch an extra entry to know where the next page starts
+        let mut entries = self
+            .handler
+            .storage
+            .get_address_transactions(address, from, Direction::Reverse, limit + 1)
+            .map_err(RPCError::EvmError)?;
+        let next_cursor = if entries.len() > limit {
+            entries
+                .pop()
+                .map(|((block_number, tx_index), _)| format_cursor(block_number, tx_index))
+        } else {
+            None
+        };
+
+        let mut transactions = Vec::with_capacity(entries.len());
+        for ((block_number, tx_index), hash) in entries {
+            let block = self
+                .handler
+                .storage
+                .get_block_by_number(&block_number)
+                .map_err(RPCError::EvmError)?
+                .ok_or(RPCError::BlockNotFound)?;
+            let index = usize::try_from(tx_index).map_err(to_custom_err)?;
+            let tx = block
+                .transactions
+                .get(index)
+                .ok_or(RPCError::TxNotFound(hash))?;
+            transactions.push(
+                EthTransactionInfo::try_from_tx_block_and_index(tx, &block, index)
+                    .map_err(to_custom_err)?,
+            );
+        }
+
+        Ok(AddressTransactions {
+            transactions,
+            next_cursor,
+        })
+    }
+}
+
+/// Cursors are formatted as `<block number>-<tx index>` in hex.
+fn format_cursor(block_number: U256, tx_index: u64) -> String {
+    format!("{block_number:#x}-{tx_index:#x}")
+}
+
+fn parse_cursor(cursor: &str) -> RpcResult<(U256, u64)> {
+    let invalid = || to_custom_err(format!("invalid cursor {cursor}"));
+    let (block_number, tx_index) = cursor.split_once('-').ok_or_else(invalid)?;
+    let block_number = block_number
+        .strip_prefix("0x")
+        .and_then(|s| U256::from_str_radix(s, 16).ok())
+        .ok_or_else(invalid)?;
+    let tx_index = tx_index
+        .strip_prefix("0x")
+        .and_then(|s| u64::from_str_radix(s, 16).ok())
+        .ok_or_else(invalid)?;
+    Ok((block_number, tx_index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_round_trip_cursor() {
+        let cursor = format_cursor(U256::from(0x1234), 7);
+        assert_eq!(cursor, "0x1234-0x7");
+        assert_eq!(parse_cursor(&cursor).unwrap(), (U256::from(0x1234), 7));
+        for invalid in [
+            "",
+            "0x1",
+            "1-2",
+            "0x1-",
+            "0xg-0x1",
+            "0x1-0x10000000000000000",
+        ] {
+            assert!(parse_cursor(invalid).is_err());
+        }
+    }
+}
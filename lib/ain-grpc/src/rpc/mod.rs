@@ -1,5 +1,12 @@
+pub mod admin;
 mod common;
 pub mod debug;
+pub mod dev;
+pub mod discover;
 pub mod eth;
+pub mod metachain;
 pub mod net;
+pub mod ots;
+pub mod personal;
+pub mod txpool;
 pub mod web3;
@@ -0,0 +1,710 @@
+use std::sync::Arc;
+
+use ain_evm::{
+    bytes::Bytes,
+    evm::EVMServices,
+    receipt::Receipt,
+    storage::{
+        traits::{BlockStorage, ReceiptStorage, TransactionStorage},
+        Direction,
+    },
+    trace::{
+        formatters::blockscout::{BlockscoutCall, BlockscoutCallInner},
+        types::{
+            single::{Call, TraceType, TracerInput, TransactionTrace},
+            CallResult, CallType, CreateResult,
+        },
+    },
+    transaction::SignedTx,
+};
+use ethereum::BlockAny;
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+};
+
+use super::common::get_block;
+use crate::{
+    block::{BlockNumber, BlockTransactions, RpcBlock, RpcBlockHeader},
+    codegen::types::EthTransactionInfo,
+    errors::{to_custom_err, RPCError},
+    pool::TRACE_POOL,
+    receipt::ReceiptResult,
+};
+
+/// Otterscan API level implemented by the node.
+const OTS_API_LEVEL: u64 = 8;
+
+/// Largest page served by the paginated methods, larger page sizes are capped to it.
+const OTS_MAX_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsInternalOperation {
+    /// 0: transfer, 1: self-destruct, 2: create, 3: create2
+    pub r#type: u8,
+    pub from: H160,
+    pub to: H160,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsTraceEntry {
+    pub r#type: String,
+    pub depth: usize,
+    pub from: H160,
+    pub to: H160,
+    pub value: Option<U256>,
+    pub input: Bytes,
+    pub output: Option<Bytes>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsBlock {
+    #[serde(flatten)]
+    pub header: RpcBlockHeader,
+    pub transaction_count: usize,
+    pub uncles: Vec<H256>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsIssuance {
+    pub block_reward: U256,
+    pub uncle_reward: U256,
+    pub issuance: U256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsBlockDetails {
+    pub block: OtsBlock,
+    pub issuance: OtsIssuance,
+    pub total_fees: U256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsFullBlock {
+    #[serde(flatten)]
+    pub block: RpcBlock,
+    pub transaction_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsBlockTransactions {
+    pub fullblock: OtsFullBlock,
+    pub receipts: Vec<ReceiptResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsReceipt {
+    #[serde(flatten)]
+    pub receipt: ReceiptResult,
+    pub timestamp: U256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsTransactionsWithReceipts {
+    pub txs: Vec<EthTransactionInfo>,
+    pub receipts: Vec<OtsReceipt>,
+    pub first_page: bool,
+    pub last_page: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsContractCreator {
+    pub hash: H256,
+    pub creator: H160,
+}
+
+#[rpc(server, client, namespace = "ots")]
+pub trait MetachainOtsRPC {
+    /// Returns the Otterscan API level implemented by the node.
+    #[method(name = "getApiLevel")]
+    fn get_api_level(&self) -> RpcResult<u64>;
+
+    /// Returns whether the address has code at the given block.
+    #[method(name = "hasCode")]
+    fn has_code(&self, address: H160, block_number: Option<BlockNumber>) -> RpcResult<bool>;
+
+    /// Returns the value transfers, self-destructs and contract creations
+    /// happening inside of a transaction.
+    #[method(name = "getInternalOperations")]
+    async fn get_internal_operations(&self, tx_hash: H256) -> RpcResult<Vec<OtsInternalOperation>>;
+
+    /// Returns the raw revert data of a failed transaction, `0x` otherwise.
+    #[method(name = "getTransactionError")]
+    async fn get_transaction_error(&self, tx_hash: H256) -> RpcResult<Bytes>;
+
+    /// Returns the flattened call tree of a transaction.
+    #[method(name = "traceTransaction")]
+    async fn trace_transaction(&self, tx_hash: H256) -> RpcResult<Vec<OtsTraceEntry>>;
+
+    /// Returns the header, transaction count and fees of a block.
+    #[method(name = "getBlockDetails")]
+    fn get_block_details(&self, block_number: BlockNumber) -> RpcResult<Option<OtsBlockDetails>>;
+
+    /// Returns the header, transaction count and fees of a block.
+    #[method(name = "getBlockDetailsByHash")]
+    fn get_block_details_by_hash(&self, hash: H256) -> RpcResult<Option<OtsBlockDetails>>;
+
+    /// Returns a page of the transactions of a block along with their receipts.
+    /// The page size is capped to 100.
+    #[method(name = "getBlockTransactions")]
+    fn get_block_transactions(
+        &self,
+        block_number: BlockNumber,
+        page_number: usize,
+        page_size: usize,
+    ) -> RpcResult<OtsBlockTransactions>;
+
+    /// Returns the transactions the address appears in before the given block, most recent first.
+    /// A block number of 0 searches from the latest block. The page size is capped to 100.
+    #[method(name = "searchTransactionsBefore")]
+    fn search_transactions_before(
+        &self,
+        address: H160,
+        block_number: U256,
+        page_size: usize,
+    ) -> RpcResult<OtsTransactionsWithReceipts>;
+
+    /// Returns the transactions the address appears in after the given block, most recent first.
+    /// A block number of 0 searches from the genesis block. The page size is capped to 100.
+    #[method(name = "searchTransactionsAfter")]
+    fn search_transactions_after(
+        &self,
+        address: H160,
+        block_number: U256,
+        page_size: usize,
+    ) -> RpcResult<OtsTransactionsWithReceipts>;
+
+    /// Returns the transaction that deployed the contract and its sender.
+    #[method(name = "getContractCreator")]
+    fn get_contract_creator(&self, address: H160) -> RpcResult<Option<OtsContractCreator>>;
+}
+
+#[derive(Clone)]
+pub struct MetachainOtsRPCModule {
+    handler: Arc<EVMServices>,
+}
+
+impl MetachainOtsRPCModule {
+    #[must_use]
+    pub fn new(handler: Arc<EVMServices>) -> Self {
+        Self { handler }
+    }
+
+    fn is_trace_enabled(&self) -> RpcResult<()> {
+        if !ain_cpp_imports::is_eth_debug_trace_rpc_enabled()
+            && !ain_cpp_imports::is_eth_debug_rpc_enabled()
+        {
+            return Err(RPCError::TraceNotEnabled.into());
+        }
+        Ok(())
+    }
+
+    fn get_receipt(&self, tx_hash: H256) -> RpcResult<Receipt> {
+        Ok(self
+            .handler
+            .storage
+            .get_receipt(&tx_hash)
+            .map_err(RPCError::EvmError)?
+            .ok_or(RPCError::ReceiptNotFound(tx_hash))?)
+    }
+
+    fn trace(
+        &self,
+        tx_hash: H256,
+        params: (TracerInput, TraceType),
+    ) -> RpcResult<TransactionTrace> {
+        self.is_trace_enabled()?;
+        let raw_max_memory_usage =
+            usize::try_from(ain_cpp_imports::get_tracing_raw_max_memory_usage_bytes())
+                .map_err(|_| to_custom_err("failed to convert response size limit to usize"))?;
+
+        let receipt = self.get_receipt(tx_hash)?;
+        let tx = self
+            .handler
+            .storage
+            .get_transaction_by_block_hash_and_index(&receipt.block_hash, receipt.tx_index)
+            .map_err(RPCError::EvmError)?
+            .ok_or(RPCError::TxNotFound(tx_hash))?;
+        let signed_tx = SignedTx::try_from(tx).map_err(to_custom_err)?;
+
+        Ok(self
+            .handler
+            .tracer
+            .trace_transaction(
+                &signed_tx,
+                receipt.block_number,
+                params,
+                raw_max_memory_usage,
+            )
+            .map_err(RPCError::EvmError)?)
+    }
+
+    fn call_list(&self, tx_hash: H256) -> RpcResult<Vec<BlockscoutCall>> {
+        let trace = self.trace(tx_hash, (TracerInput::Blockscout, TraceType::CallList))?;
+        let TransactionTrace::CallList(calls) = trace else {
+            return Err(to_custom_err("unexpected trace format"));
+        };
+        Ok(calls
+            .into_iter()
+            .filter_map(|call| match call {
+                Call::Blockscout(call) => Some(*call),
+                Call::CallTracer(_) => None,
+            })
+            .collect())
+    }
+
+    fn block_details(&self, block: BlockAny) -> RpcResult<OtsBlockDetails> {
+        let mut total_fees = U256::zero();
+        for tx in &block.transactions {
+            let receipt = self.get_receipt(tx.hash())?;
+            let gas_used = ethereum::EIP658ReceiptData::from(receipt.receipt).used_gas;
+            total_fees =
+                total_fees.saturating_add(gas_used.saturating_mul(receipt.effective_gas_price));
+        }
+
+        Ok(OtsBlockDetails {
+            block: OtsBlock {
+                header: RpcBlockHeader::from(block.header.clone()),
+                transaction_count: block.transactions.len(),
+                uncles: Vec::new(),
+            },
+            // No block rewards are minted on the EVM chain
+            issuance: OtsIssuance::default(),
+            total_fees,
+        })
+    }
+
+    /// Collects up to `page_size` transactions the address appears in starting from `from`,
+    /// completing the last block so that pages never split a block.
+    /// Returns the transactions and whether the end of the index was reached.
+    fn search_transactions(
+        &self,
+        address: H160,
+        from: Option<(U256, u64)>,
+        direction: Direction,
+        page_size: usize,
+    ) -> RpcResult<(Vec<H256>, bool)> {
+        let page_size = page_size.clamp(1, OTS_MAX_PAGE_SIZE);
+        let storage = &self.handler.storage;
+        let mut entries = storage
+            .get_address_transactions(address, from, direction, page_size)
+            .map_err(RPCError::EvmError)?;
+        let mut exhausted = entries.len() < page_size;
+
+        while !exhausted {
+            let Some(&((last_block, last_index), _)) = entries.last() else {
+                break;
+            };
+            let Some(next) = next_position(last_block, last_index, direction) else {
+                exhausted = true;
+                break;
+            };
+            let chunk = storage
+                .get_address_transactions(address, Some(next), direction, page_size)
+                .map_err(RPCError::EvmError)?;
+            exhausted = chunk.len() < page_size;
+
+            let chunk_len = chunk.len();
+            let same_block = chunk
+                .into_iter()
+                .take_while(|((block, _), _)| *block == last_block)
+                .collect::<Vec<_>>();
+            let crossed_block = same_block.len() < chunk_len;
+            entries.extend(same_block);
+            if crossed_block {
+                exhausted = false;
+                break;
+            }
+        }
+
+        Ok((
+            entries.into_iter().map(|(_, hash)| hash).collect(),
+            exhausted,
+        ))
+    }
+
+    fn transactions_with_receipts(
+        &self,
+        hashes: Vec<H256>,
+    ) -> RpcResult<(Vec<EthTransactionInfo>, Vec<OtsReceipt>)> {
+        let mut txs = Vec::with_capacity(hashes.len());
+        let mut receipts = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let receipt = self.get_receipt(hash)?;
+            let block = self
+                .handler
+                .storage
+                .get_block_by_number(&receipt.block_number)
+                .map_err(RPCError::EvmError)?
+                .ok_or(RPCError::BlockNotFound)?;
+            let tx = block
+                .transactions
+                .get(receipt.tx_index)
+                .ok_or(RPCError::TxNotFound(hash))?;
+
+            txs.push(
+                EthTransactionInfo::try_from_tx_block_and_index(tx, &block, receipt.tx_index)
+                    .map_err(to_custom_err)?,
+            );
+            receipts.push(OtsReceipt {
+                receipt: ReceiptResult::from(receipt),
+                timestamp: U256::from(block.header.timestamp),
+            });
+        }
+        Ok((txs, receipts))
+    }
+}
+
+#[async_trait]
+impl MetachainOtsRPCServer for MetachainOtsRPCModule {
+    fn get_api_level(&self) -> RpcResult<u64> {
+        Ok(OTS_API_LEVEL)
+    }
+
+    fn has_code(&self, address: H160, block_number: Option<BlockNumber>) -> RpcResult<bool> {
+        let block = get_block(&self.handler.storage, block_number)?;
+        let code = self
+            .handler
+            .core
+            .get_code(address, block.header.state_root)
+            .map_err(to_custom_err)?;
+        Ok(code.is_some_and(|code| !code.is_empty()))
+    }
+
+    async fn get_internal_operations(&self, tx_hash: H256) -> RpcResult<Vec<OtsInternalOperation>> {
+        let module = self.clone();
+        TRACE_POOL
+            .spawn(move || module.get_internal_operations_blocking(tx_hash))
+            .await
+    }
+
+    async fn get_transaction_error(&self, tx_hash: H256) -> RpcResult<Bytes> {
+        let module = self.clone();
+        TRACE_POOL
+            .spawn(move || module.get_transaction_error_blocking(tx_hash))
+            .await
+    }
+
+    async fn trace_transaction(&self, tx_hash: H256) -> RpcResult<Vec<OtsTraceEntry>> {
+        let module = self.clone();
+        TRACE_POOL
+            .spawn(move || module.trace_transaction_blocking(tx_hash))
+            .await
+    }
+
+    fn get_block_details(&self, block_number: BlockNumber) -> RpcResult<Option<OtsBlockDetails>> {
+        let block = match block_number {
+            BlockNumber::Num(n) => self
+                .handler
+                .storage
+                .get_block_by_number(&U256::from(n))
+                .map_err(RPCError::EvmError)?,
+            BlockNumber::Hash { hash, .. } => self
+                .handler
+                .storage
+                .get_block_by_hash(&hash)
+                .map_err(RPCError::EvmError)?,
+            _ => Some(get_block(&self.handler.storage, Some(block_number))?),
+        };
+        block.map_or(Ok(None), |block| self.block_details(block).map(Some))
+    }
+
+    fn get_block_details_by_hash(&self, hash: H256) -> RpcResult<Option<OtsBlockDetails>> {
+        self.handler
+            .storage
+            .get_block_by_hash(&hash)
+            .map_err(RPCError::EvmError)?
+            .map_or(Ok(None), |block| self.block_details(block).map(Some))
+    }
+
+    fn get_block_transactions(
+        &self,
+        block_number: BlockNumber,
+        page_number: usize,
+        page_size: usize,
+    ) -> RpcResult<OtsBlockTransactions> {
+        let page_size = page_size.min(OTS_MAX_PAGE_SIZE);
+        let block = get_block(&self.handler.storage, Some(block_number))?;
+        let transaction_count = block.transactions.len();
+        let skip = page_number.saturating_mul(page_size);
+
+        let mut receipts = Vec::new();
+        for tx in block.transactions.iter().skip(skip).take(page_size) {
+            let mut receipt = ReceiptResult::from(self.get_receipt(tx.hash())?);
+            // Logs are fetched separately by the explorer when needed
+            receipt.logs.clear();
+            receipts.push(receipt);
+        }
+
+        let mut rpc_block = RpcBlock::from_block_with_tx(block, true);
+        if let BlockTransactions::Full(txs) = rpc_block.transactions {
+            rpc_block.transactions =
+                BlockTransactions::Full(txs.into_iter().skip(skip).take(page_size).collect());
+        }
+
+        Ok(OtsBlockTransactions {
+            fullblock: OtsFullBlock {
+                block: rpc_block,
+                transaction_count,
+            },
+            receipts,
+        })
+    }
+
+    fn search_transactions_before(
+        &self,
+        address: H160,
+        block_number: U256,
+        page_size: usize,
+    ) -> RpcResult<OtsTransactionsWithReceipts> {
+        let first_page = block_number.is_zero();
+        let from = if first_page {
+            None
+        } else {
+            Some((block_number - 1, u64::MAX))
+        };
+
+        let (hashes, last_page) =
+            self.search_transactions(address, from, Direction::Reverse, page_size)?;
+        let (txs, receipts) = self.transactions_with_receipts(hashes)?;
+        Ok(OtsTransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page,
+            last_page,
+        })
+    }
+
+    fn search_transactions_after(
+        &self,
+        address: H160,
+        block_number: U256,
+        page_size: usize,
+    ) -> RpcResult<OtsTransactionsWithReceipts> {
+        let last_page = block_number.is_zero();
+        let from = if last_page {
+            None
+        } else {
+            Some((block_number.saturating_add(U256::one()), 0))
+        };
+
+        let (mut hashes, first_page) =
+            self.search_transactions(address, from, Direction::Forward, page_size)?;
+        // Results are always returned most recent first
+        hashes.reverse();
+        let (txs, receipts) = self.transactions_with_receipts(hashes)?;
+        Ok(OtsTransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page,
+            last_page,
+        })
+    }
+
+    fn get_contract_creator(&self, address: H160) -> RpcResult<Option<OtsContractCreator>> {
+        Ok(self
+            .handler
+            .storage
+            .get_contract_creator(&address)
+            .map_err(RPCError::EvmError)?
+            .map(|(hash, creator)| OtsContractCreator { hash, creator }))
+    }
+}
+
+impl MetachainOtsRPCModule {
+    // Traced methods, run on the trace worker pool
+
+    fn get_internal_operations_blocking(
+        &self,
+        tx_hash: H256,
+    ) -> RpcResult<Vec<OtsInternalOperation>> {
+        let calls = self.call_list(tx_hash)?;
+
+        let mut operations = Vec::new();
+        for call in calls {
+            let operation = match call.inner {
+                BlockscoutCallInner::Call {
+                    call_type: CallType::Call,
+                    to,
+                    ..
+                } if !call.trace_address.is_empty() && !call.value.is_zero() => {
+                    OtsInternalOperation {
+                        r#type: 0,
+                        from: call.from,
+                        to,
+                        value: call.value,
+                    }
+                }
+                BlockscoutCallInner::SelfDestruct { balance, to } => OtsInternalOperation {
+                    r#type: 1,
+                    from: call.from,
+                    to,
+                    value: balance,
+                },
+                BlockscoutCallInner::Create {
+                    res:
+                        CreateResult::Success {
+                            created_contract_address_hash,
+                            ..
+                        },
+                    create2,
+                    ..
+                } if !call.trace_address.is_empty() => OtsInternalOperation {
+                    r#type: if create2 { 3 } else { 2 },
+                    from: call.from,
+                    to: created_contract_address_hash,
+                    value: call.value,
+                },
+                _ => continue,
+            };
+            operations.push(operation);
+        }
+        Ok(operations)
+    }
+
+    fn get_transaction_error_blocking(&self, tx_hash: H256) -> RpcResult<Bytes> {
+        let receipt = self.get_receipt(tx_hash)?;
+        if ethereum::EIP658ReceiptData::from(receipt.receipt).status_code == 1 {
+            return Ok(Bytes::default());
+        }
+
+        let trace = self.trace(
+            tx_hash,
+            (
+                TracerInput::None,
+                TraceType::Raw {
+                    disable_storage: true,
+                    disable_memory: true,
+                    disable_stack: true,
+                },
+            ),
+        )?;
+        match trace {
+            TransactionTrace::Raw { return_value, .. } => Ok(Bytes::from(return_value)),
+            _ => Err(to_custom_err("unexpected trace format")),
+        }
+    }
+
+    fn trace_transaction_blocking(&self, tx_hash: H256) -> RpcResult<Vec<OtsTraceEntry>> {
+        let calls = self.call_list(tx_hash)?;
+
+        Ok(calls
+            .into_iter()
+            .map(|call| {
+                let depth = call.trace_address.len();
+                match call.inner {
+                    BlockscoutCallInner::Call {
+                        call_type,
+                        to,
+                        input,
+                        res,
+                    } => {
+                        let (r#type, value) = match call_type {
+                            CallType::Call => ("CALL", Some(call.value)),
+                            CallType::CallCode => ("CALLCODE", Some(call.value)),
+                            CallType::DelegateCall => ("DELEGATECALL", None),
+                            CallType::StaticCall => ("STATICCALL", None),
+                        };
+                        let output = match res {
+                            CallResult::Output(output) => Some(Bytes::from(output)),
+                            CallResult::Error(_) => None,
+                        };
+                        OtsTraceEntry {
+                            r#type: r#type.to_string(),
+                            depth,
+                            from: call.from,
+                            to,
+                            value,
+                            input: Bytes::from(input),
+                            output,
+                        }
+                    }
+                    BlockscoutCallInner::Create { init, res, create2 } => {
+                        let (to, output) = match res {
+                            CreateResult::Success {
+                                created_contract_address_hash,
+                                created_contract_code,
+                            } => (
+                                created_contract_address_hash,
+                                Some(Bytes::from(created_contract_code)),
+                            ),
+                            CreateResult::Error { .. } => (H160::zero(), None),
+                        };
+                        OtsTraceEntry {
+                            r#type: if create2 { "CREATE2" } else { "CREATE" }.to_string(),
+                            depth,
+                            from: call.from,
+                            to,
+                            value: Some(call.value),
+                            input: Bytes::from(init),
+                            output,
+                        }
+                    }
+                    BlockscoutCallInner::SelfDestruct { balance, to } => OtsTraceEntry {
+                        r#type: "SELFDESTRUCT".to_string(),
+                        depth,
+                        from: call.from,
+                        to,
+                        value: Some(balance),
+                        input: Bytes::default(),
+                        output: None,
+                    },
+                }
+            })
+            .collect())
+    }
+}
+
+/// Position in the address transactions index following `(block_number, tx_index)`.
+fn next_position(block_number: U256, tx_index: u64, direction: Direction) -> Option<(U256, u64)> {
+    match direction {
+        Direction::Forward => match tx_index.checked_add(1) {
+            Some(tx_index) => Some((block_number, tx_index)),
+            None => block_number.checked_add(U256::one()).map(|b| (b, 0)),
+        },
+        Direction::Reverse => match tx_index.checked_sub(1) {
+            Some(tx_index) => Some((block_number, tx_index)),
+            None => block_number.checked_sub(U256::one()).map(|b| (b, u64::MAX)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_step_to_next_position() {
+        let block = U256::from(5);
+        assert_eq!(
+            next_position(block, 1, Direction::Forward),
+            Some((block, 2))
+        );
+        assert_eq!(
+            next_position(block, u64::MAX, Direction::Forward),
+            Some((U256::from(6), 0))
+        );
+        assert_eq!(
+            next_position(block, 1, Direction::Reverse),
+            Some((block, 0))
+        );
+        assert_eq!(
+            next_position(block, 0, Direction::Reverse),
+            Some((U256::from(4), u64::MAX))
+        );
+        assert_eq!(next_position(U256::zero(), 0, Direction::Reverse), None);
+        assert_eq!(next_position(U256::MAX, u64::MAX, Direction::Forward), None);
+    }
+}
@@ -0,0 +1,33 @@
+use ain_evm::bytes::Bytes;
+use ethereum_types::H160;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use log::trace;
+
+use super::common::{recover_signer, sign_hash};
+use crate::typed_data::eth_message_hash;
+
+#[rpc(server, client, namespace = "personal")]
+pub trait MetachainPersonalRPC {
+    /// Signs an EIP-191 personal message with the key of the given address.
+    /// The passphrase is ignored as the wallet is managed by the node.
+    /// Returns the 65 byte signature.
+    #[method(name = "sign")]
+    fn sign(&self, data: Bytes, address: H160, passphrase: Option<String>) -> RpcResult<Bytes>;
+
+    /// Recovers the address that signed an EIP-191 personal message.
+    #[method(name = "ecRecover")]
+    fn ec_recover(&self, data: Bytes, signature: Bytes) -> RpcResult<H160>;
+}
+
+pub struct MetachainPersonalRPCModule;
+
+impl MetachainPersonalRPCServer for MetachainPersonalRPCModule {
+    fn sign(&self, data: Bytes, address: H160, _passphrase: Option<String>) -> RpcResult<Bytes> {
+        trace!(target:"rpc", "[personal_sign] address: {:#x}", address);
+        sign_hash(address, eth_message_hash(&data.0))
+    }
+
+    fn ec_recover(&self, data: Bytes, signature: Bytes) -> RpcResult<H160> {
+        recover_signer(eth_message_hash(&data.0), &signature.0)
+    }
+}
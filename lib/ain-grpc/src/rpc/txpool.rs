@@ -0,0 +1,300 @@
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+use ain_evm::{evm::EVMServices, transaction::SignedTx};
+use ethereum::TransactionAction;
+use ethereum_types::{H160, U256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{codegen::types::EthTransactionInfo, errors::RPCError};
+
+/// Transaction nonce keying pool transactions, ordered numerically and serialized as a
+/// decimal string like geth.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PoolNonce(pub U256);
+
+impl fmt::Display for PoolNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for PoolNonce {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PoolNonce {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let nonce = String::deserialize(deserializer)?;
+        U256::from_dec_str(&nonce)
+            .map(Self)
+            .map_err(|_| de::Error::custom(format!("invalid nonce {nonce}")))
+    }
+}
+
+/// Pool transactions of a sender keyed by nonce. Transactions sharing a nonce are replacement
+/// candidates, listed in pool order.
+type NonceTxs<T> = BTreeMap<PoolNonce, Vec<T>>;
+
+/// Pool transactions grouped by sender and nonce, split into executable (pending)
+/// and nonce-gapped (queued) transactions.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TxPoolResult<T> {
+    pub pending: BTreeMap<H160, BTreeMap<PoolNonce, Vec<T>>>,
+    pub queued: BTreeMap<H160, BTreeMap<PoolNonce, Vec<T>>>,
+}
+
+/// Pool transactions of a single sender keyed by nonce.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TxPoolFromResult<T> {
+    pub pending: BTreeMap<PoolNonce, Vec<T>>,
+    pub queued: BTreeMap<PoolNonce, Vec<T>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy)]
+pub struct TxPoolStatus {
+    pub pending: U256,
+    pub queued: U256,
+}
+
+#[rpc(server, client, namespace = "txpool")]
+pub trait MetachainTxPoolRPC {
+    /// Returns the pending and queued EVM transactions of the mempool, grouped by sender and nonce.
+    /// Transactions sharing a nonce are all listed.
+    #[method(name = "content")]
+    fn content(&self) -> RpcResult<TxPoolResult<EthTransactionInfo>>;
+
+    /// Returns the pending and queued EVM transactions of the mempool sent by the given address.
+    #[method(name = "contentFrom")]
+    fn content_from(&self, address: H160) -> RpcResult<TxPoolFromResult<EthTransactionInfo>>;
+
+    /// Returns a textual summary of the pending and queued EVM transactions of the mempool.
+    #[method(name = "inspect")]
+    fn inspect(&self) -> RpcResult<TxPoolResult<String>>;
+
+    /// Returns the number of pending and queued EVM transactions of the mempool.
+    #[method(name = "status")]
+    fn status(&self) -> RpcResult<TxPoolStatus>;
+}
+
+pub struct MetachainTxPoolRPCModule {
+    handler: Arc<EVMServices>,
+}
+
+impl MetachainTxPoolRPCModule {
+    #[must_use]
+    pub fn new(handler: Arc<EVMServices>) -> Self {
+        Self { handler }
+    }
+
+    /// Decodes the EVM transactions of the mempool and groups them by sender.
+    fn pool_transactions(&self) -> RpcResult<TxPoolResult<SignedTx>> {
+        let txs = ain_cpp_imports::get_pool_transactions().map_err(RPCError::Error)?;
+
+        let mut by_sender: BTreeMap<H160, BTreeMap<U256, Vec<SignedTx>>> = BTreeMap::new();
+        for tx in txs {
+            let Ok(signed_tx) = self.handler.core.tx_cache.try_get_or_create(&tx.data) else {
+                continue;
+            };
+            by_sender
+                .entry(signed_tx.sender)
+                .or_default()
+                .entry(signed_tx.nonce())
+                .or_default()
+                .push(signed_tx);
+        }
+
+        let state_root = self
+            .handler
+            .core
+            .get_latest_state_root()
+            .map_err(|_| RPCError::StateRootNotFound)?;
+
+        group_by_nonce(by_sender, |sender| {
+            self.handler
+                .core
+                .get_nonce(sender, state_root)
+                .map_err(|e| RPCError::EvmError(e).into())
+        })
+    }
+}
+
+/// Splits the transactions of each sender into pending ones, continuing the sender's account
+/// nonce without gaps, and queued ones. Transactions with nonces already used by the account
+/// are dropped.
+fn group_by_nonce<T, F>(
+    by_sender: BTreeMap<H160, BTreeMap<U256, Vec<T>>>,
+    account_nonce: F,
+) -> RpcResult<TxPoolResult<T>>
+where
+    F: Fn(H160) -> RpcResult<U256>,
+{
+    let mut result = TxPoolResult {
+        pending: BTreeMap::new(),
+        queued: BTreeMap::new(),
+    };
+    for (sender, txs) in by_sender {
+        let mut next_nonce = account_nonce(sender)?;
+        for (nonce, txs) in txs {
+            if nonce < next_nonce {
+                continue;
+            }
+            let group = if nonce == next_nonce {
+                next_nonce = next_nonce.saturating_add(U256::one());
+                &mut result.pending
+            } else {
+                &mut result.queued
+            };
+            group
+                .entry(sender)
+                .or_default()
+                .insert(PoolNonce(nonce), txs);
+        }
+    }
+    Ok(result)
+}
+
+impl MetachainTxPoolRPCServer for MetachainTxPoolRPCModule {
+    fn content(&self) -> RpcResult<TxPoolResult<EthTransactionInfo>> {
+        let pool = self.pool_transactions()?;
+        Ok(TxPoolResult {
+            pending: map_pool(pool.pending, into_pending_info),
+            queued: map_pool(pool.queued, into_pending_info),
+        })
+    }
+
+    fn content_from(&self, address: H160) -> RpcResult<TxPoolFromResult<EthTransactionInfo>> {
+        let mut pool = self.pool_transactions()?;
+        let map_sender =
+            |txs: Option<NonceTxs<SignedTx>>| map_txs(txs.unwrap_or_default(), into_pending_info);
+        Ok(TxPoolFromResult {
+            pending: map_sender(pool.pending.remove(&address)),
+            queued: map_sender(pool.queued.remove(&address)),
+        })
+    }
+
+    fn inspect(&self) -> RpcResult<TxPoolResult<String>> {
+        let pool = self.pool_transactions()?;
+        Ok(TxPoolResult {
+            pending: map_pool(pool.pending, summary),
+            queued: map_pool(pool.queued, summary),
+        })
+    }
+
+    fn status(&self) -> RpcResult<TxPoolStatus> {
+        let pool = self.pool_transactions()?;
+        let count = |group: &BTreeMap<H160, NonceTxs<SignedTx>>| {
+            U256::from(
+                group
+                    .values()
+                    .flat_map(BTreeMap::values)
+                    .map(Vec::len)
+                    .sum::<usize>(),
+            )
+        };
+        Ok(TxPoolStatus {
+            pending: count(&pool.pending),
+            queued: count(&pool.queued),
+        })
+    }
+}
+
+fn map_pool<T, F>(group: BTreeMap<H160, NonceTxs<SignedTx>>, f: F) -> BTreeMap<H160, NonceTxs<T>>
+where
+    F: Fn(SignedTx) -> T,
+{
+    group
+        .into_iter()
+        .map(|(sender, txs)| (sender, map_txs(txs, &f)))
+        .collect()
+}
+
+fn map_txs<T, F>(txs: NonceTxs<SignedTx>, f: F) -> NonceTxs<T>
+where
+    F: Fn(SignedTx) -> T,
+{
+    txs.into_iter()
+        .map(|(nonce, txs)| (nonce, txs.into_iter().map(&f).collect()))
+        .collect()
+}
+
+fn into_pending_info(tx: SignedTx) -> EthTransactionInfo {
+    EthTransactionInfo::from(tx).into_pending_transaction_info()
+}
+
+fn summary(tx: SignedTx) -> String {
+    let to = match tx.action() {
+        TransactionAction::Call(to) => format!("{to:#x}"),
+        TransactionAction::Create => String::from("contract creation"),
+    };
+    format!(
+        "{}: {} wei + {} gas × {} wei",
+        to,
+        tx.value(),
+        tx.gas_limit(),
+        tx.gas_price()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_group_transactions_by_nonce() {
+        let sender = H160::repeat_byte(1);
+        let txs = [2u64, 3, 4, 6, 9, 10]
+            .into_iter()
+            .map(|nonce| (U256::from(nonce), vec![nonce]))
+            .collect::<BTreeMap<_, _>>();
+        let pool = group_by_nonce(BTreeMap::from([(sender, txs)]), |_| Ok(U256::from(3))).unwrap();
+
+        let nonces = |group: &BTreeMap<H160, NonceTxs<u64>>| {
+            group[&sender]
+                .values()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        // Nonce 2 is already used by the account
+        assert_eq!(nonces(&pool.pending), vec![3, 4]);
+        assert_eq!(nonces(&pool.queued), vec![6, 9, 10]);
+    }
+
+    #[test]
+    fn should_keep_transactions_sharing_a_nonce() {
+        let sender = H160::repeat_byte(1);
+        let txs = BTreeMap::from([
+            (U256::from(3), vec!["a", "b"]),
+            (U256::from(4), vec!["c"]),
+            (U256::from(6), vec!["d", "e"]),
+        ]);
+        let pool = group_by_nonce(BTreeMap::from([(sender, txs)]), |_| Ok(U256::from(3))).unwrap();
+
+        assert_eq!(
+            pool.pending[&sender],
+            BTreeMap::from([
+                (PoolNonce(U256::from(3)), vec!["a", "b"]),
+                (PoolNonce(U256::from(4)), vec!["c"]),
+            ])
+        );
+        assert_eq!(
+            pool.queued[&sender],
+            BTreeMap::from([(PoolNonce(U256::from(6)), vec!["d", "e"])])
+        );
+    }
+
+    #[test]
+    fn should_serialize_nonces_in_numeric_order() {
+        let txs = BTreeMap::from([
+            (PoolNonce(U256::from(10)), "b"),
+            (PoolNonce(U256::from(9)), "a"),
+        ]);
+        let json = serde_json::to_string(&txs).unwrap();
+        assert_eq!(json, r#"{"9":"a","10":"b"}"#);
+        let decoded: BTreeMap<PoolNonce, &str> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, txs);
+    }
+}
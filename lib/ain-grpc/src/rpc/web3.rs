@@ -16,6 +16,18 @@ pub trait MetachainWeb3RPC {
     fn sha3(&self, input: Bytes) -> RpcResult<H256>;
 }
 
+/// Returns the client version reported by `web3_clientVersion`.
+pub fn client_version() -> String {
+    let version: String = ain_cpp_imports::get_client_version();
+    let os = std::env::consts::OS;
+    let arch = std::env::consts::ARCH;
+    let rust_version = rustc_version_runtime::version();
+    format!(
+        "DeFiChain/{}/{}-{}/rustc-{}",
+        version, os, arch, rust_version
+    )
+}
+
 pub struct MetachainWeb3RPCModule {
     _handler: Arc<EVMServices>,
 }
@@ -29,14 +41,7 @@ impl MetachainWeb3RPCModule {
 
 impl MetachainWeb3RPCServer for MetachainWeb3RPCModule {
     fn client_version(&self) -> RpcResult<String> {
-        let version: String = ain_cpp_imports::get_client_version();
-        let os = std::env::consts::OS;
-        let arch = std::env::consts::ARCH;
-        let rust_version = rustc_version_runtime::version();
-        Ok(format!(
-            "DeFiChain/{}/{}-{}/rustc-{}",
-            version, os, arch, rust_version
-        ))
+        Ok(client_version())
     }
 
     fn sha3(&self, input: Bytes) -> RpcResult<H256> {
@@ -10,8 +10,11 @@ use jsonrpsee::{proc_macros::rpc, types::SubscriptionEmptyError, SubscriptionSin
 use log::{debug, trace};
 use tokio::runtime::Handle as AsyncHandle;
 
-use crate::subscription::{
-    PubSubResult, Subscription, SubscriptionParams, SubscriptionParamsTopics, SyncStatus,
+use crate::{
+    metrics::SubscriptionGuard,
+    subscription::{
+        PubSubResult, Subscription, SubscriptionParams, SubscriptionParamsTopics, SyncStatus,
+    },
 };
 
 /// Metachain WebSockets interface.
@@ -21,7 +24,7 @@ pub trait MetachainPubSub {
     #[subscription(
     name = "subscribe" => "subscription",
     unsubscribe = "unsubscribe",
-    item = Result,
+    item = PubSubResult,
     )]
     fn subscribe(&self, subscription: Subscription, params: Option<SubscriptionParams>);
 }
@@ -56,7 +59,9 @@ impl MetachainPubSubServer for MetachainPubSubModule {
         let handler = self.handler.clone();
         match subscription {
             Subscription::NewHeads => {
+                let guard = SubscriptionGuard::new("newHeads");
                 let fut = async move {
+                    let _guard = guard;
                     while !sink.is_closed() {
                         if let Notification::Block(hash) = rx.recv().await? {
                             if let Some(block) = handler.storage.get_block_by_hash(&hash)? {
@@ -79,7 +84,9 @@ impl MetachainPubSubServer for MetachainPubSubModule {
                 self.tokio_runtime.spawn(fut);
             }
             Subscription::Logs => {
+                let guard = SubscriptionGuard::new("logs");
                 let fut = async move {
+                    let _guard = guard;
                     while !sink.is_closed() {
                         if let Notification::Block(hash) = rx.recv().await? {
                             if let Some(block) = handler.storage.get_block_by_hash(&hash)? {
@@ -135,7 +142,9 @@ impl MetachainPubSubServer for MetachainPubSubModule {
                 self.tokio_runtime.spawn(fut);
             }
             Subscription::NewPendingTransactions => {
+                let guard = SubscriptionGuard::new("newPendingTransactions");
                 let fut = async move {
+                    let _guard = guard;
                     while !sink.is_closed() {
                         if let Notification::Transaction(hash) = rx.recv().await? {
                             if !sink.send(&PubSubResult::TransactionHash(hash))? {
@@ -149,7 +158,9 @@ impl MetachainPubSubServer for MetachainPubSubModule {
                 self.tokio_runtime.spawn(fut);
             }
             Subscription::Syncing => {
+                let guard = SubscriptionGuard::new("syncing");
                 let fut = async move {
+                    let _guard = guard;
                     let get_sync_status = || -> Result<SyncStatus, anyhow::Error> {
                         let (current, highest) = ain_cpp_imports::get_sync_status()
                             .map_err(|_| format_err!("failed to get sync status"))?;
@@ -0,0 +1,361 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{format_err, Result};
+use ethereum_types::{H160, H256, U256};
+use serde_json::Value;
+use sha3::Digest;
+
+const EIP712_DOMAIN: &str = "EIP712Domain";
+
+/// A single member of an EIP-712 struct type definition.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TypedDataField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+}
+
+/// EIP-712 typed structured data, as passed to `eth_signTypedData_v4`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+impl TypedData {
+    /// Parses typed data from either a JSON object or a JSON encoded string,
+    /// since wallets commonly send the latter.
+    pub fn from_value(value: Value) -> Result<Self> {
+        let typed_data = match value {
+            Value::String(s) => serde_json::from_str(&s)?,
+            v => serde_json::from_value(v)?,
+        };
+        Ok(typed_data)
+    }
+
+    /// Returns the EIP-712 signing hash:
+    /// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+    pub fn signing_hash(&self) -> Result<H256> {
+        let domain_separator = self.hash_struct(EIP712_DOMAIN, &self.domain)?;
+
+        let mut encoded = vec![0x19, 0x01];
+        encoded.extend_from_slice(domain_separator.as_bytes());
+        if self.primary_type != EIP712_DOMAIN {
+            let message_hash = self.hash_struct(&self.primary_type, &self.message)?;
+            encoded.extend_from_slice(message_hash.as_bytes());
+        }
+        Ok(keccak(encoded))
+    }
+
+    fn hash_struct(&self, type_name: &str, data: &Value) -> Result<H256> {
+        let fields = self
+            .types
+            .get(type_name)
+            .ok_or_else(|| format_err!("unknown struct type {type_name}"))?;
+        let data = data
+            .as_object()
+            .ok_or_else(|| format_err!("expected object for struct type {type_name}"))?;
+
+        let mut encoded = keccak(self.encode_type(type_name)?).as_bytes().to_vec();
+        for field in fields {
+            let value = data.get(&field.name).unwrap_or(&Value::Null);
+            encoded.extend_from_slice(self.encode_value(&field.r#type, value)?.as_bytes());
+        }
+        Ok(keccak(encoded))
+    }
+
+    fn encode_type(&self, type_name: &str) -> Result<String> {
+        let mut deps = BTreeSet::new();
+        self.find_dependencies(type_name, &mut deps);
+        deps.remove(type_name);
+
+        std::iter::once(type_name)
+            .chain(deps.iter().map(String::as_str))
+            .map(|name| {
+                let fields = self
+                    .types
+                    .get(name)
+                    .ok_or_else(|| format_err!("unknown struct type {name}"))?;
+                let members = fields
+                    .iter()
+                    .map(|f| format!("{} {}", f.r#type, f.name))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                Ok(format!("{name}({members})"))
+            })
+            .collect()
+    }
+
+    fn find_dependencies(&self, type_name: &str, deps: &mut BTreeSet<String>) {
+        let base = base_type(type_name);
+        if deps.contains(base) {
+            return;
+        }
+        let Some(fields) = self.types.get(base) else {
+            return;
+        };
+        deps.insert(base.to_string());
+        for field in fields {
+            self.find_dependencies(&field.r#type, deps);
+        }
+    }
+
+    fn encode_value(&self, type_name: &str, value: &Value) -> Result<H256> {
+        if let Some(inner) = array_inner_type(type_name) {
+            let items = value
+                .as_array()
+                .ok_or_else(|| format_err!("expected array for type {type_name}"))?;
+            let mut encoded = Vec::with_capacity(items.len() * 32);
+            for item in items {
+                encoded.extend_from_slice(self.encode_value(inner, item)?.as_bytes());
+            }
+            return Ok(keccak(encoded));
+        }
+
+        if self.types.contains_key(type_name) {
+            return self.hash_struct(type_name, value);
+        }
+
+        match type_name {
+            "string" => {
+                let s = value
+                    .as_str()
+                    .ok_or_else(|| format_err!("expected string value"))?;
+                Ok(keccak(s.as_bytes()))
+            }
+            "bytes" => Ok(keccak(parse_hex_bytes(value)?)),
+            "bool" => {
+                let b = value
+                    .as_bool()
+                    .ok_or_else(|| format_err!("expected bool value"))?;
+                Ok(H256::from_low_u64_be(u64::from(b)))
+            }
+            "address" => {
+                let s = value
+                    .as_str()
+                    .ok_or_else(|| format_err!("expected address value"))?;
+                let address: H160 = s.parse()?;
+                Ok(H256::from(address))
+            }
+            t if t.starts_with("bytes") => {
+                let size: usize = t["bytes".len()..].parse()?;
+                let bytes = parse_hex_bytes(value)?;
+                if size == 0 || size > 32 || bytes.len() > size {
+                    return Err(format_err!("invalid value for type {t}"));
+                }
+                let mut word = [0u8; 32];
+                word[..bytes.len()].copy_from_slice(&bytes);
+                Ok(H256(word))
+            }
+            t if t.starts_with("uint") => {
+                let bits = integer_bits(t, "uint")?;
+                let (negative, n) = parse_integer(value)?;
+                if negative && !n.is_zero() {
+                    return Err(format_err!("negative value for type {t}"));
+                }
+                if n.bits() > bits {
+                    return Err(format_err!("value {n} out of range for type {t}"));
+                }
+                Ok(u256_to_h256(n))
+            }
+            t if t.starts_with("int") => {
+                let bits = integer_bits(t, "int")?;
+                let (negative, n) = parse_integer(value)?;
+                // Two's complement range of -2^(bits-1) to 2^(bits-1) - 1
+                let in_range = n.bits() < bits
+                    || (negative && n.bits() == bits && n.trailing_zeros() as usize == bits - 1);
+                if !in_range {
+                    let sign = if negative { "-" } else { "" };
+                    return Err(format_err!("value {sign}{n} out of range for type {t}"));
+                }
+                let n = if negative {
+                    (!n).overflowing_add(U256::one()).0
+                } else {
+                    n
+                };
+                Ok(u256_to_h256(n))
+            }
+            t => Err(format_err!("unsupported type {t}")),
+        }
+    }
+}
+
+/// Returns the EIP-191 personal message hash:
+/// `keccak256("\x19Ethereum Signed Message:\n" ‖ len(message) ‖ message)`.
+pub fn eth_message_hash(message: &[u8]) -> H256 {
+    let mut encoded = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    encoded.extend_from_slice(message);
+    keccak(encoded)
+}
+
+fn keccak<T: AsRef<[u8]>>(data: T) -> H256 {
+    H256::from_slice(sha3::Keccak256::digest(data.as_ref()).as_slice())
+}
+
+fn base_type(type_name: &str) -> &str {
+    type_name.split('[').next().unwrap_or(type_name)
+}
+
+fn array_inner_type(type_name: &str) -> Option<&str> {
+    if !type_name.ends_with(']') {
+        return None;
+    }
+    type_name.rfind('[').map(|idx| &type_name[..idx])
+}
+
+fn parse_hex_bytes(value: &Value) -> Result<Vec<u8>> {
+    let s = value
+        .as_str()
+        .ok_or_else(|| format_err!("expected hex string value"))?;
+    Ok(hex::decode(s.strip_prefix("0x").unwrap_or(s))?)
+}
+
+fn parse_integer(value: &Value) -> Result<(bool, U256)> {
+    match value {
+        Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                Ok((false, U256::from(n)))
+            } else if let Some(n) = n.as_i64() {
+                Ok((true, U256::from(n.unsigned_abs())))
+            } else {
+                Err(format_err!("invalid integer value {n}"))
+            }
+        }
+        Value::String(s) => {
+            let (negative, s) = match s.strip_prefix('-') {
+                Some(s) => (true, s),
+                None => (false, s.as_str()),
+            };
+            let n = match s.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16)?,
+                None => U256::from_dec_str(s)?,
+            };
+            Ok((negative, n))
+        }
+        _ => Err(format_err!("expected integer value")),
+    }
+}
+
+/// Returns the width of a `uintN` or `intN` type, 256 when omitted.
+fn integer_bits(type_name: &str, prefix: &str) -> Result<usize> {
+    let size = &type_name[prefix.len()..];
+    if size.is_empty() {
+        return Ok(256);
+    }
+    size.parse::<usize>()
+        .ok()
+        .filter(|bits| *bits > 0 && *bits <= 256 && bits % 8 == 0)
+        .ok_or_else(|| format_err!("unsupported type {type_name}"))
+}
+
+fn u256_to_h256(n: U256) -> H256 {
+    let mut word = [0u8; 32];
+    n.to_big_endian(&mut word);
+    H256(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eip712_mail_example() {
+        let typed_data = TypedData::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            typed_data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            format!("{:x}", typed_data.signing_hash().unwrap()),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn test_eip712_integer_ranges() {
+        let typed_data = TypedData::from_value(serde_json::json!({
+            "types": { "EIP712Domain": [] },
+            "primaryType": "EIP712Domain",
+            "domain": {},
+            "message": {}
+        }))
+        .unwrap();
+        let encode = |t: &str, v: serde_json::Value| typed_data.encode_value(t, &v);
+
+        assert_eq!(
+            encode("uint8", serde_json::json!(255)).unwrap(),
+            H256::from_low_u64_be(255)
+        );
+        assert!(encode("uint8", serde_json::json!(256)).is_err());
+        assert!(encode("uint8", serde_json::json!(-1)).is_err());
+        assert!(encode(
+            "uint256",
+            serde_json::json!(format!("0x{}", "f".repeat(64)))
+        )
+        .is_ok());
+
+        assert!(encode("int8", serde_json::json!(127)).is_ok());
+        assert!(encode("int8", serde_json::json!(128)).is_err());
+        assert_eq!(
+            encode("int8", serde_json::json!(-128)).unwrap(),
+            u256_to_h256(!U256::from(127))
+        );
+        assert!(encode("int8", serde_json::json!(-129)).is_err());
+        assert!(encode(
+            "int256",
+            serde_json::json!(format!("0x8{}", "0".repeat(63)))
+        )
+        .is_err());
+        assert!(encode(
+            "int256",
+            serde_json::json!(format!("-0x8{}", "0".repeat(63)))
+        )
+        .is_ok());
+
+        assert!(encode("uint7", serde_json::json!(1)).is_err());
+        assert!(encode("int264", serde_json::json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_eth_message_hash() {
+        assert_eq!(
+            format!("{:x}", eth_message_hash(b"Hello World")),
+            "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! Expansion of `#[derive(ConsensusEncoding)]`.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Error, Expr, Fields, Ident, LitStr,
+    Path, Result, Type,
+};
+
+#[derive(Default)]
+struct ContainerAttrs {
+    marker: Option<Expr>,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    varint: bool,
+    skip: bool,
+    optional: bool,
+    with: Option<Path>,
+}
+
+fn consensus_attrs(
+    attrs: &[Attribute],
+    mut f: impl FnMut(syn::meta::ParseNestedMeta) -> Result<()>,
+) -> Result<()> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("consensus"))
+        .try_for_each(|attr| attr.parse_nested_meta(&mut f))
+}
+
+fn container_attrs(attrs: &[Attribute]) -> Result<ContainerAttrs> {
+    let mut out = ContainerAttrs::default();
+    consensus_attrs(attrs, |meta| {
+        if meta.path.is_ident("marker") {
+            out.marker = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported consensus attribute"))
+        }
+    })?;
+    Ok(out)
+}
+
+fn field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs> {
+    let mut out = FieldAttrs::default();
+    let mut span = None;
+    consensus_attrs(attrs, |meta| {
+        if span.replace(meta.path.span()).is_some() {
+            return Err(meta.error("only one consensus attribute is allowed per field"));
+        }
+        if meta.path.is_ident("varint") {
+            out.varint = true;
+        } else if meta.path.is_ident("skip") {
+            out.skip = true;
+        } else if meta.path.is_ident("optional") {
+            out.optional = true;
+        } else if meta.path.is_ident("with") {
+            out.with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+        } else {
+            return Err(meta.error("unsupported consensus attribute"));
+        }
+        Ok(())
+    })?;
+    Ok(out)
+}
+
+/// `#[consensus(other)]` marks the variant decoded for unknown discriminants.
+fn is_other(attrs: &[Attribute]) -> Result<bool> {
+    let mut other = false;
+    consensus_attrs(attrs, |meta| {
+        if meta.path.is_ident("other") {
+            other = true;
+            Ok(())
+        } else {
+            Err(meta.error("unsupported consensus attribute"))
+        }
+    })?;
+    Ok(other)
+}
+
+/// Discriminants are encoded as the `#[repr]` integer type, `u8` by default.
+fn repr_type(attrs: &[Attribute]) -> Result<Ident> {
+    let mut repr = Ident::new("u8", Span::call_site());
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                repr = ident.clone();
+            }
+            Ok(())
+        })?;
+    }
+    Ok(repr)
+}
+
+struct Field<'a> {
+    attrs: FieldAttrs,
+    ty: &'a Type,
+    /// Expression referencing the field when encoding.
+    access: TokenStream,
+    /// Member name when constructing a struct or struct variant.
+    member: Option<&'a Ident>,
+}
+
+fn fields(
+    fields: &Fields,
+    access: impl Fn(usize, &syn::Field) -> TokenStream,
+) -> Result<Vec<Field<'_>>> {
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            Ok(Field {
+                attrs: field_attrs(&field.attrs)?,
+                ty: &field.ty,
+                access: access(i, field),
+                member: field.ident.as_ref(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut optional = false;
+    for field in &fields {
+        if field.attrs.optional {
+            optional = true;
+        } else if optional && !field.attrs.skip {
+            return Err(Error::new(
+                field.ty.span(),
+                "only trailing fields can be #[consensus(optional)]",
+            ));
+        }
+    }
+    Ok(fields)
+}
+
+fn encode_field(field: &Field) -> TokenStream {
+    let access = &field.access;
+    let attrs = &field.attrs;
+    if attrs.skip {
+        quote! {}
+    } else if attrs.varint {
+        quote! {
+            __len += bitcoin::consensus::Encodable::consensus_encode(
+                &ain_dftx::common::VarInt(u64::from(*#access)),
+                __writer,
+            )?;
+        }
+    } else if let Some(with) = &attrs.with {
+        quote! { __len += #with::consensus_encode(#access, __writer)?; }
+    } else if attrs.optional {
+        quote! {
+            if let Some(__value) = #access {
+                __len += bitcoin::consensus::Encodable::consensus_encode(__value, __writer)?;
+            }
+        }
+    } else {
+        quote! { __len += bitcoin::consensus::Encodable::consensus_encode(#access, __writer)?; }
+    }
+}
+
+fn decode_field(field: &Field) -> TokenStream {
+    let ty = &field.ty;
+    let attrs = &field.attrs;
+    if attrs.skip {
+        quote! { ::core::default::Default::default() }
+    } else if attrs.varint {
+        quote! {
+            <#ty as ::core::convert::TryFrom<u64>>::try_from(
+                <ain_dftx::common::VarInt as bitcoin::consensus::Decodable>::consensus_decode(
+                    __reader,
+                )?
+                .0,
+            )
+            .map_err(|_| bitcoin::consensus::encode::Error::ParseFailed("VarInt out of range"))?
+        }
+    } else if let Some(with) = &attrs.with {
+        quote! { #with::consensus_decode(__reader)? }
+    } else if attrs.optional {
+        // Absent when the input ends before the field, as with `Maybe`
+        quote! {
+            match bitcoin::consensus::Decodable::consensus_decode(__reader) {
+                Ok(__value) => Some(__value),
+                Err(bitcoin::consensus::encode::Error::Io(__e))
+                    if __e.kind() == bitcoin::io::ErrorKind::UnexpectedEof =>
+                {
+                    None
+                }
+                Err(__e) => return Err(__e),
+            }
+        }
+    } else {
+        quote! { bitcoin::consensus::Decodable::consensus_decode(__reader)? }
+    }
+}
+
+/// Constructor expression decoding `fields` in order.
+fn construct(path: TokenStream, shape: &Fields, fields: &[Field]) -> TokenStream {
+    let values = fields.iter().map(decode_field);
+    match shape {
+        Fields::Named(_) => {
+            let members = fields.iter().map(|f| &f.member);
+            quote! { #path { #(#members: #values),* } }
+        }
+        Fields::Unnamed(_) => quote! { #path(#(#values),*) },
+        Fields::Unit => path,
+    }
+}
+
+fn struct_codec(shape: &Fields) -> Result<(TokenStream, TokenStream)> {
+    let fields = fields(shape, |i, field| match &field.ident {
+        Some(ident) => quote! { &self.#ident },
+        None => {
+            let index = syn::Index::from(i);
+            quote! { &self.#index }
+        }
+    })?;
+    let encode = fields.iter().map(encode_field);
+    let decode = construct(quote! { Self }, shape, &fields);
+    Ok((quote! { #(#encode)* }, quote! { Ok(#decode) }))
+}
+
+fn enum_codec(name: &Ident, data: &DataEnum, repr: &Ident) -> Result<(TokenStream, TokenStream)> {
+    let mut encode_arms = Vec::new();
+    let mut decode_arms = Vec::new();
+    let mut other = None;
+
+    // Implicit discriminants follow the previous explicit one, as in Rust
+    let mut base = None;
+    let mut offset = 0usize;
+    for variant in &data.variants {
+        if let Some((_, expr)) = &variant.discriminant {
+            base = Some(expr);
+            offset = 0;
+        }
+        let index = Literal::usize_unsuffixed(offset);
+        let tag = match &base {
+            Some(expr) if offset == 0 => quote! { (#expr) as #repr },
+            Some(expr) => quote! { ((#expr) as #repr + #index) },
+            None => quote! { #index as #repr },
+        };
+        offset += 1;
+
+        let ident = &variant.ident;
+        if is_other(&variant.attrs)? {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new(
+                    variant.span(),
+                    "#[consensus(other)] is only supported on unit variants",
+                ));
+            }
+            if other.replace(ident.clone()).is_some() {
+                return Err(Error::new(
+                    variant.span(),
+                    "only one variant can be #[consensus(other)]",
+                ));
+            }
+        }
+
+        let fields = fields(&variant.fields, |i, field| match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => {
+                let ident = format_ident!("__field{}", i);
+                quote! { #ident }
+            }
+        })?;
+        let bindings = fields.iter().map(|f| &f.access);
+        let pattern = match &variant.fields {
+            Fields::Named(_) => quote! { Self::#ident { #(#bindings),* } },
+            Fields::Unnamed(_) => quote! { Self::#ident(#(#bindings),*) },
+            Fields::Unit => quote! { Self::#ident },
+        };
+        let encode = fields.iter().map(encode_field);
+        encode_arms.push(quote! {
+            #pattern => {
+                __len += bitcoin::consensus::Encodable::consensus_encode(&(#tag), __writer)?;
+                #(#encode)*
+            }
+        });
+
+        let decode = construct(quote! { Self::#ident }, &variant.fields, &fields);
+        decode_arms.push(quote! { __tag if __tag == #tag => #decode, });
+    }
+
+    let fallback = match other {
+        Some(ident) => quote! { _ => Self::#ident, },
+        None => quote! {
+            _ => {
+                return Err(bitcoin::consensus::encode::Error::ParseFailed(concat!(
+                    "Unknown ",
+                    stringify!(#name),
+                    " variant"
+                )))
+            }
+        },
+    };
+
+    let encode = quote! {
+        match self {
+            #(#encode_arms)*
+        }
+    };
+    let decode = quote! {
+        let __tag = <#repr as bitcoin::consensus::Decodable>::consensus_decode(__reader)?;
+        Ok(match __tag {
+            #(#decode_arms)*
+            #fallback
+        })
+    };
+    Ok((encode, decode))
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let container = container_attrs(&input.attrs)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (encode, decode) = match &input.data {
+        Data::Struct(data) => struct_codec(&data.fields)?,
+        Data::Enum(data) => enum_codec(name, data, &repr_type(&input.attrs)?)?,
+        Data::Union(_) => {
+            return Err(Error::new(
+                input.span(),
+                "ConsensusEncoding cannot be derived for unions",
+            ))
+        }
+    };
+
+    let (encode_marker, decode_marker) = match &container.marker {
+        Some(marker) => (
+            quote! {
+                __len += bitcoin::consensus::Encodable::consensus_encode(&#marker, __writer)?;
+            },
+            quote! {
+                let mut __marker = #marker;
+                bitcoin::io::Read::read_exact(__reader, &mut __marker)?;
+                if __marker != #marker {
+                    return Err(bitcoin::consensus::encode::Error::ParseFailed("Invalid marker"));
+                }
+            },
+        ),
+        None => (quote! {}, quote! {}),
+    };
+
+    Ok(quote! {
+        impl #impl_generics bitcoin::consensus::Encodable for #name #ty_generics #where_clause {
+            fn consensus_encode<__W: bitcoin::io::Write + ?Sized>(
+                &self,
+                __writer: &mut __W,
+            ) -> ::core::result::Result<usize, bitcoin::io::Error> {
+                #[allow(unused_mut)]
+                let mut __len = 0;
+                #encode_marker
+                #encode
+                Ok(__len)
+            }
+        }
+
+        impl #impl_generics bitcoin::consensus::Decodable for #name #ty_generics #where_clause {
+            fn consensus_decode<__R: bitcoin::io::Read + ?Sized>(
+                __reader: &mut __R,
+            ) -> ::core::result::Result<Self, bitcoin::consensus::encode::Error> {
+                #decode_marker
+                #decode
+            }
+        }
+    })
+}
@@ -1,8 +1,10 @@
 extern crate proc_macro;
 
+mod consensus;
+
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, ItemFn, ReturnType, Type};
+use syn::{parse_macro_input, DeriveInput, ItemFn, ReturnType, Type};
 
 #[proc_macro_attribute]
 pub fn ffi_fallible(_attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -61,34 +63,32 @@ pub fn ffi_fallible(_attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-#[proc_macro_derive(ConsensusEncoding)]
+/// Derives `Encodable` and `Decodable` for custom tx types, encoding fields in declaration order.
+///
+/// Enums are encoded as their discriminant, as the `#[repr]` integer type or `u8` by default,
+/// followed by the variant fields. Unknown discriminants fail to decode unless a unit variant is
+/// marked `#[consensus(other)]`. A `#[consensus(marker = EXPR)]` on the type prefixes it with a
+/// fixed byte array.
+///
+/// Fields accept one of:
+/// - `#[consensus(varint)]`: integer encoded as a bitcoin core `VARINT`
+/// - `#[consensus(skip)]`: not encoded, decoded as `Default::default()`
+/// - `#[consensus(with = "module")]`: encoded with `module::consensus_encode` and
+///   `module::consensus_decode`
+/// - `#[consensus(optional)]`: trailing `Option` only encoded when set, and `None` when the input
+///   ends before it
+#[proc_macro_derive(ConsensusEncoding, attributes(consensus))]
 pub fn consensus_encoding_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
-
-    let fields = if let Data::Struct(data) = input.data {
-        if let Fields::Named(fields) = data.fields {
-            fields
-                .named
-                .into_iter()
-                .map(|f| f.ident)
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        }
-    } else {
-        Vec::new()
-    };
-
-    let field_names = fields.iter().filter_map(|f| f.as_ref()).collect::<Vec<_>>();
-
-    let expanded = quote! {
-        bitcoin::impl_consensus_encoding!(#name, #(#field_names),*);
-    };
-
-    TokenStream::from(expanded)
+    consensus::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
+/// Round-trips every fixture of `tests/data/<name>.txt`. When the test function takes
+/// `(index: usize, dftx: &DfTx)` parameters, its body is run against the decoded message of
+/// each fixture line to assert the expected variant and fields. In that case `#[test]` has to
+/// follow this attribute, as test functions can not take parameters.
 #[proc_macro_attribute]
 pub fn test_dftx_serialization(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
@@ -97,16 +97,25 @@ pub fn test_dftx_serialization(_attr: TokenStream, item: TokenStream) -> TokenSt
 
     let path = format!("./tests/data/{}.txt", &test_name[4..].to_lowercase());
 
+    let fn_attrs = &input_fn.attrs;
     let fn_body = &input_fn.block;
+    let fn_inputs = &input_fn.sig.inputs;
+    let (setup, check) = if fn_inputs.is_empty() {
+        (quote! { #fn_body }, quote! {})
+    } else {
+        (
+            quote! { fn check(#fn_inputs) #fn_body },
+            quote! { check(index, &dftx.dftx); },
+        )
+    };
 
     let output = quote! {
+        #(#fn_attrs)*
         fn #fn_name() {
-            #fn_body
+            #setup
             let s = std::fs::read_to_string(#path).unwrap();
-            for line in s.lines() {
-                if line.starts_with("//") {
-                    continue;
-                }
+            let lines = s.lines().filter(|line| !line.starts_with("//"));
+            for (index, line) in lines.enumerate() {
                 let l = line.split(' ').next().unwrap();
                 let hex = &hex::decode(l).unwrap();
 
@@ -122,6 +131,7 @@ pub fn test_dftx_serialization(_attr: TokenStream, item: TokenStream) -> TokenSt
                 let dftx = bitcoin::consensus::deserialize::<ain_dftx::Stack>(&raw_tx).unwrap();
                 let ser = bitcoin::consensus::serialize::<ain_dftx::Stack>(&dftx);
                 assert_eq!(ser, raw_tx);
+                #check
             }
         }
     };
@@ -44,6 +44,18 @@ pub fn ain_rs_init_network_subscriptions_service(addr: String) -> Result<()> {
     Ok(())
 }
 
+#[ffi_fallible]
+pub fn ain_rs_init_network_metrics_service(addr: String) -> Result<()> {
+    ain_grpc::init_network_metrics_service(addr)?;
+    Ok(())
+}
+
+#[ffi_fallible]
+pub fn ain_rs_init_network_ipc_service(path: String) -> Result<()> {
+    ain_grpc::init_network_ipc_service(path)?;
+    Ok(())
+}
+
 #[ffi_fallible]
 pub fn ain_rs_stop_network_services() -> Result<()> {
     ain_grpc::stop_network_services()?;
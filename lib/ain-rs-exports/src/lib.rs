@@ -50,6 +50,8 @@ pub mod ffi {
             result: &mut CrossBoundaryResult,
             addr: String,
         );
+        fn ain_rs_init_network_metrics_service(result: &mut CrossBoundaryResult, addr: String);
+        fn ain_rs_init_network_ipc_service(result: &mut CrossBoundaryResult, path: String);
         fn ain_rs_stop_network_services(result: &mut CrossBoundaryResult);
     }
 
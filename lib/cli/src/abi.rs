@@ -0,0 +1,420 @@
+use std::fs;
+
+use ethabi::{
+    param_type::Reader,
+    token::{LenientTokenizer, Tokenizer},
+    Contract, Event, Function, Param, ParamType, RawLog, StateMutability, Token,
+};
+use ethereum_types::{H160, H256, U256};
+use serde_json::{json, Map, Value};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct AbiOpt {
+    /// A JSON ABI file, or one of the built-in `dst20` and `transferdomain` ABIs.
+    #[structopt(long)]
+    abi: Option<String>,
+}
+
+impl AbiOpt {
+    pub fn load(&self) -> Result<Option<Contract>, String> {
+        self.abi.as_deref().map(load_abi).transpose()
+    }
+}
+
+/// Loads a built-in ABI by name, or an ABI file. Compiler artifacts holding the ABI under an
+/// `abi` key are accepted as well.
+pub fn load_abi(source: &str) -> Result<Contract, String> {
+    match source.to_ascii_lowercase().as_str() {
+        "dst20" => return Ok(ain_contracts::get_dst20_abi()),
+        "transferdomain" => return Ok(ain_contracts::get_transfer_domain_abi()),
+        _ => {}
+    }
+    let content =
+        fs::read_to_string(source).map_err(|e| format!("Failed to read ABI {source}: {e}"))?;
+    let mut value = serde_json::from_str::<Value>(&content)
+        .map_err(|e| format!("Failed to parse ABI {source}: {e}"))?;
+    if let Some(abi) = value.get_mut("abi") {
+        value = abi.take();
+    }
+    serde_json::from_value(value).map_err(|e| format!("Invalid ABI {source}: {e}"))
+}
+
+/// Splits the leading parenthesized group off `s`, returning its content and the remainder.
+fn split_group(s: &str) -> Result<(&str, &str), String> {
+    if !s.starts_with('(') {
+        return Err(format!("Expected '(' in {s}"));
+    }
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok((&s[1..i], &s[i + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(format!("Unbalanced parentheses in {s}"))
+}
+
+/// Splits comma separated types at the top level, keeping tuples whole.
+fn split_types(types: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in types.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                out.push(types[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    out.push(types[start..].trim());
+    out
+}
+
+/// Reads a type, handling tuples and arrays here as ethabi's reader misreads arrays of tuples
+/// nested in a tuple.
+fn read_kind(kind: &str) -> Result<ParamType, String> {
+    let kind = kind.trim();
+    if let Some(array) = kind.strip_suffix(']') {
+        let open = array
+            .rfind('[')
+            .ok_or_else(|| format!("Invalid type {kind}"))?;
+        let inner = Box::new(read_kind(&array[..open])?);
+        return match &array[open + 1..] {
+            "" => Ok(ParamType::Array(inner)),
+            size => size
+                .parse()
+                .map(|size| ParamType::FixedArray(inner, size))
+                .map_err(|_| format!("Invalid array size in {kind}")),
+        };
+    }
+    if kind.starts_with('(') {
+        return match split_group(kind)? {
+            (types, "") => Ok(ParamType::Tuple(read_kinds(types)?)),
+            _ => Err(format!("Invalid type {kind}")),
+        };
+    }
+    Reader::read(kind).map_err(|e| format!("Invalid type {kind}: {e}"))
+}
+
+fn read_kinds(types: &str) -> Result<Vec<ParamType>, String> {
+    if types.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    split_types(types).into_iter().map(read_kind).collect()
+}
+
+fn read_params(types: &str) -> Result<Vec<Param>, String> {
+    Ok(read_kinds(types)?
+        .into_iter()
+        .map(|kind| Param {
+            name: String::new(),
+            kind,
+            internal_type: None,
+        })
+        .collect())
+}
+
+/// Resolves `function`, either a signature such as `transfer(address,uint256)`, optionally
+/// followed by the return types as in `balanceOf(address)(uint256)`, or a function name of `abi`.
+/// Signatures found in `abi` use its definition, which also provides the return types.
+pub fn resolve_function(function: &str, abi: Option<&Contract>) -> Result<Function, String> {
+    let Some(open) = function.find('(') else {
+        let abi =
+            abi.ok_or_else(|| format!("Function {function} needs a full signature or an ABI"))?;
+        let functions = abi
+            .functions_by_name(function)
+            .map_err(|_| format!("Function {function} not found in ABI"))?;
+        return match functions.as_slice() {
+            [function] => Ok(function.clone()),
+            _ => Err(format!(
+                "Function {function} is overloaded, use its full signature"
+            )),
+        };
+    };
+
+    let name = function[..open].trim();
+    let (inputs, rest) = split_group(&function[open..])?;
+    let inputs = read_params(inputs)?;
+    let outputs = match rest.trim() {
+        "" => Vec::new(),
+        rest => match split_group(rest)? {
+            (outputs, "") => read_params(outputs)?,
+            _ => return Err(format!("Invalid function signature {function}")),
+        },
+    };
+
+    if let Some(function) = abi
+        .and_then(|abi| abi.functions_by_name(name).ok())
+        .and_then(|functions| {
+            functions.iter().find(|f| {
+                f.inputs
+                    .iter()
+                    .map(|p| &p.kind)
+                    .eq(inputs.iter().map(|p| &p.kind))
+            })
+        })
+    {
+        return Ok(function.clone());
+    }
+
+    #[allow(deprecated)] // constant field is deprecated since Solidity 0.5.0
+    Ok(Function {
+        name: name.to_string(),
+        inputs,
+        outputs,
+        constant: None,
+        state_mutability: StateMutability::NonPayable,
+    })
+}
+
+/// ABI-encodes the call of `function` with `args` given as strings, e.g. `[1,2]` for arrays and
+/// `(0x..,1)` for tuples.
+pub fn encode_call(function: &Function, args: &[String]) -> Result<Vec<u8>, String> {
+    if args.len() != function.inputs.len() {
+        return Err(format!(
+            "{} expects {} arguments, got {}",
+            function.signature(),
+            function.inputs.len(),
+            args.len()
+        ));
+    }
+    let tokens = function
+        .inputs
+        .iter()
+        .zip(args)
+        .map(|(param, arg)| {
+            LenientTokenizer::tokenize(&param.kind, arg)
+                .map_err(|e| format!("Invalid {} argument {arg}: {e}", param.kind))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    function
+        .encode_input(&tokens)
+        .map_err(|e| format!("Failed to encode call: {e}"))
+}
+
+pub fn token_to_json(token: &Token) -> Value {
+    match token {
+        Token::Address(address) => json!(format!("{address:#x}")),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
+            json!(format!("0x{}", hex::encode(bytes)))
+        }
+        Token::Int(value) if value.bit(255) => {
+            json!(format!("-{}", (!*value).overflowing_add(U256::one()).0))
+        }
+        Token::Int(value) | Token::Uint(value) => json!(value.to_string()),
+        Token::Bool(value) => json!(value),
+        Token::String(value) => json!(value),
+        Token::FixedArray(tokens) | Token::Array(tokens) | Token::Tuple(tokens) => {
+            Value::Array(tokens.iter().map(token_to_json).collect())
+        }
+    }
+}
+
+/// Values keyed by name when every one is named, as a list otherwise.
+fn named_values<'a>(values: impl Iterator<Item = (&'a str, &'a Token)>) -> Value {
+    let values = values.collect::<Vec<_>>();
+    if !values.is_empty() && values.iter().all(|(name, _)| !name.is_empty()) {
+        Value::Object(
+            values
+                .into_iter()
+                .map(|(name, token)| (name.to_string(), token_to_json(token)))
+                .collect::<Map<_, _>>(),
+        )
+    } else {
+        Value::Array(values.into_iter().map(|(_, t)| token_to_json(t)).collect())
+    }
+}
+
+/// Decodes the return data of `function`. A single unnamed value is returned as is.
+pub fn decode_output(function: &Function, data: &[u8]) -> Result<Value, String> {
+    if function.outputs.is_empty() {
+        return Ok(json!(format!("0x{}", hex::encode(data))));
+    }
+    let tokens = function
+        .decode_output(data)
+        .map_err(|e| format!("Failed to decode output: {e}"))?;
+    Ok(match (function.outputs.as_slice(), tokens.as_slice()) {
+        ([output], [token]) if output.name.is_empty() => token_to_json(token),
+        (outputs, tokens) => {
+            named_values(outputs.iter().map(|p| p.name.as_str()).zip(tokens.iter()))
+        }
+    })
+}
+
+/// Decodes a log against the events of `abi`. Logs of unknown events are returned undecoded.
+pub fn decode_log(abi: &Contract, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Value {
+    let event = topics.first().and_then(|topic| {
+        abi.events()
+            .filter(|event| !event.anonymous)
+            .find(|event| event.signature() == *topic)
+    });
+    let decoded = event.and_then(|event: &Event| {
+        let log = event
+            .parse_log(RawLog {
+                topics: topics.clone(),
+                data: data.clone(),
+            })
+            .ok()?;
+        Some(json!({
+            "address": format!("{address:#x}"),
+            "event": event.name,
+            "params": named_values(log.params.iter().map(|p| (p.name.as_str(), &p.value))),
+        }))
+    });
+    decoded.unwrap_or_else(|| {
+        json!({
+            "address": format!("{address:#x}"),
+            "event": null,
+            "topics": topics,
+            "data": format!("0x{}", hex::encode(data)),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(byte: u8) -> H160 {
+        H160::from_low_u64_be(u64::from(byte))
+    }
+
+    #[test]
+    fn should_split_nested_groups() {
+        assert_eq!(
+            split_group("((address,(uint8,bytes)[])[2],bool)(uint256)").unwrap(),
+            ("(address,(uint8,bytes)[])[2],bool", "(uint256)")
+        );
+        assert_eq!(split_group("()").unwrap(), ("", ""));
+        assert!(split_group("uint256)").is_err());
+        assert!(split_group("((uint256)").is_err());
+    }
+
+    #[test]
+    fn should_resolve_nested_tuples_and_arrays() {
+        let function = resolve_function(
+            "foo((address,uint256[])[],(bool,(int8,bytes32))[2])(int256,string)",
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            function.signature(),
+            "foo((address,uint256[])[],(bool,(int8,bytes32))[2]):(int256,string)"
+        );
+        assert_eq!(
+            function.inputs[1].kind,
+            ParamType::FixedArray(
+                Box::new(ParamType::Tuple(vec![
+                    ParamType::Bool,
+                    ParamType::Tuple(vec![ParamType::Int(8), ParamType::FixedBytes(32)]),
+                ])),
+                2
+            )
+        );
+
+        let function = resolve_function("bar(((address,(uint8,bytes)[])[2],bool))", None).unwrap();
+        assert_eq!(
+            function.signature(),
+            "bar(((address,(uint8,bytes)[])[2],bool))"
+        );
+
+        let dst20 = load_abi("dst20").unwrap();
+        let transfer = resolve_function("transfer(address,uint256)", Some(&dst20)).unwrap();
+        assert_eq!(transfer.outputs[0].kind, ParamType::Bool);
+        assert!(resolve_function("transfer", None).is_err());
+        assert!(resolve_function("foo(uint256[x])", None).is_err());
+        assert!(resolve_function("foo((uint256)x)", None).is_err());
+        assert!(resolve_function("foo(uint256)(bool", None).is_err());
+        assert!(resolve_function("foo(uint256)(bool)x", None).is_err());
+    }
+
+    #[test]
+    fn should_format_negative_ints() {
+        let minus = |value: u64| Token::Int(!U256::from(value) + U256::one());
+        assert_eq!(token_to_json(&minus(1)), json!("-1"));
+        assert_eq!(token_to_json(&minus(42)), json!("-42"));
+        assert_eq!(token_to_json(&Token::Int(U256::from(42))), json!("42"));
+        assert_eq!(
+            token_to_json(&Token::Int(U256::one() << 255)),
+            json!("-57896044618658097711785492504343953926634992332820282019728792003956564819968")
+        );
+        assert_eq!(
+            token_to_json(&Token::Tuple(vec![minus(3), Token::Uint(U256::MAX)])),
+            json!([
+                "-3",
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+            ])
+        );
+    }
+
+    #[test]
+    fn should_decode_output() {
+        let dst20 = load_abi("dst20").unwrap();
+        let balance_of = resolve_function("balanceOf", Some(&dst20)).unwrap();
+        let data = ethabi::encode(&[Token::Uint(U256::from(5))]);
+        assert_eq!(decode_output(&balance_of, &data).unwrap(), json!("5"));
+
+        let function = resolve_function("foo()(int8,string)", None).unwrap();
+        let data = ethabi::encode(&[
+            Token::Int(!U256::zero()),
+            Token::String(String::from("dfi")),
+        ]);
+        assert_eq!(
+            decode_output(&function, &data).unwrap(),
+            json!(["-1", "dfi"])
+        );
+
+        // Raw data without return types
+        let function = resolve_function("foo()", None).unwrap();
+        assert_eq!(decode_output(&function, &[0xab]).unwrap(), json!("0xab"));
+        assert!(decode_output(&balance_of, &[0xab]).is_err());
+    }
+
+    #[test]
+    fn should_decode_dst20_transfer_log() {
+        let dst20 = load_abi("dst20").unwrap();
+        let transfer = dst20.event("Transfer").unwrap();
+        let token = address(0xff);
+        let topics = vec![
+            transfer.signature(),
+            H256::from(address(1)),
+            H256::from(address(2)),
+        ];
+        let data = ethabi::encode(&[Token::Uint(U256::from(7))]);
+        assert_eq!(
+            decode_log(&dst20, token, topics, data.clone()),
+            json!({
+                "address": format!("{token:#x}"),
+                "event": "Transfer",
+                "params": {
+                    "from": format!("{:#x}", address(1)),
+                    "to": format!("{:#x}", address(2)),
+                    "value": "7",
+                },
+            })
+        );
+
+        // Unknown events are returned undecoded
+        let topics = vec![H256::repeat_byte(1)];
+        assert_eq!(
+            decode_log(&dst20, token, topics.clone(), data.clone()),
+            json!({
+                "address": format!("{token:#x}"),
+                "event": null,
+                "topics": topics,
+                "data": format!("0x{}", hex::encode(data)),
+            })
+        );
+    }
+}
@@ -1,11 +1,15 @@
-use ain_grpc::rpc::MetachainRPCClient;
-use jsonrpsee::http_client::HttpClient;
+use ain_grpc::{call_request, rpc::MetachainRPCClient};
+use serde_json::Value;
 
-use crate::{result::RpcResult, MetachainCLI};
+use crate::{
+    abi, dftx, params::BaseChainParams, result::RpcResult, structs::HexData, wallet,
+    wallet::to_custom_err, MetachainCLI,
+};
 
-pub async fn execute_cli_command(
+pub async fn execute_cli_command<C: MetachainRPCClient + Sync>(
     cmd: MetachainCLI,
-    client: &HttpClient,
+    client: &C,
+    params: &BaseChainParams,
 ) -> Result<RpcResult, jsonrpsee::core::Error> {
     let result = match cmd {
         MetachainCLI::Accounts => client.accounts().await?.into(),
@@ -73,6 +77,79 @@ pub async fn execute_cli_command(
             .into(),
         MetachainCLI::EstimateGas { input } => client.estimate_gas((*input).into()).await?.into(),
         MetachainCLI::GasPrice => client.gas_price().await?.into(),
+        MetachainCLI::DecodeDftx { input } => {
+            dftx::decode_dftx(&input).map_err(jsonrpsee::core::Error::Custom)?
+        }
+        MetachainCLI::EncodeDftx { input } => {
+            dftx::encode_dftx(&input).map_err(jsonrpsee::core::Error::Custom)?
+        }
+        MetachainCLI::DecodeBlock { input } => {
+            dftx::decode_raw_block(&input).map_err(jsonrpsee::core::Error::Custom)?
+        }
+        MetachainCLI::Wallet(opt) => wallet::execute_wallet_command(opt, client, params).await?,
+        MetachainCLI::CallFn {
+            to,
+            from,
+            block_number,
+            abi,
+            function,
+            args,
+        } => {
+            let contract = abi.load().map_err(to_custom_err)?;
+            let function =
+                abi::resolve_function(&function, contract.as_ref()).map_err(to_custom_err)?;
+            let data = abi::encode_call(&function, &args).map_err(to_custom_err)?;
+            let request = call_request::CallRequest {
+                from,
+                to: Some(to),
+                data: Some(data.into()),
+                ..Default::default()
+            };
+            let output = client.call(request, block_number, None).await?;
+            abi::decode_output(&function, &output.0)
+                .map_err(to_custom_err)?
+                .into()
+        }
+        MetachainCLI::SendFn {
+            from,
+            keystore,
+            abi,
+            mut input,
+            function,
+            args,
+        } => {
+            if input.to.is_none() {
+                return Err(to_custom_err("--to is required"));
+            }
+            if input.data.is_some() {
+                return Err(to_custom_err("--data cannot be used with a function call"));
+            }
+            let contract = abi.load().map_err(to_custom_err)?;
+            let function =
+                abi::resolve_function(&function, contract.as_ref()).map_err(to_custom_err)?;
+            input.data = Some(HexData(
+                abi::encode_call(&function, &args).map_err(to_custom_err)?,
+            ));
+            keystore
+                .send_transaction(client, params, from, &input)
+                .await?
+                .into()
+        }
+        MetachainCLI::DecodeLogs { abi, hash } => {
+            let contract = abi::load_abi(&abi).map_err(to_custom_err)?;
+            let receipt = client
+                .get_receipt(hash)
+                .await?
+                .ok_or_else(|| to_custom_err(format!("Receipt not found for {hash:#x}")))?;
+            Value::Array(
+                receipt
+                    .logs
+                    .into_iter()
+                    .map(|log| abi::decode_log(&contract, log.address, log.topics, log.data.0))
+                    .collect(),
+            )
+            .into()
+        }
     };
     Ok(result)
 }
@@ -0,0 +1,118 @@
+use ain_dftx::{decode_block, deserialize, serialize, Block, DfTx, Script, Transaction};
+use serde_json::{json, Value};
+
+use crate::result::RpcResult;
+
+const DFTX_MARKER: &[u8] = b"DfTx";
+const OP_RETURN: u8 = 0x6a;
+
+fn decode_hex(input: &str) -> Result<Vec<u8>, String> {
+    hex::decode(input.trim_start_matches("0x")).map_err(|e| format!("Invalid hex: {e}"))
+}
+
+/// Decodes either a custom tx payload, starting with the `DfTx` marker, an `OP_RETURN` output
+/// script or a full raw transaction.
+pub fn decode_dftx(input: &str) -> Result<RpcResult, String> {
+    let bytes = decode_hex(input)?;
+    let dftx = if bytes.starts_with(DFTX_MARKER) {
+        deserialize::<DfTx>(&bytes).map_err(|e| format!("Failed to decode custom tx: {e}"))?
+    } else if bytes.first() == Some(&OP_RETURN) {
+        DfTx::from_script(Script::from_bytes(&bytes), true)
+            .map_err(|e| format!("Failed to decode custom tx: {e}"))?
+            .ok_or_else(|| String::from("Script is not a custom tx"))?
+    } else {
+        let tx = deserialize::<Transaction>(&bytes)
+            .map_err(|e| format!("Failed to decode transaction: {e}"))?;
+        DfTx::from_transaction(&tx, true)
+            .map_err(|e| format!("Failed to decode custom tx: {e}"))?
+            .ok_or_else(|| String::from("Transaction is not a custom tx"))?
+    };
+    let value = serde_json::to_value(dftx).map_err(|e| e.to_string())?;
+    Ok(value.into())
+}
+
+/// Encodes a custom tx given as JSON into its hex payload.
+pub fn encode_dftx(input: &str) -> Result<RpcResult, String> {
+    let dftx =
+        serde_json::from_str::<DfTx>(input).map_err(|e| format!("Invalid custom tx: {e}"))?;
+    Ok(hex::encode(serialize(&dftx)).into())
+}
+
+/// Lists the custom txs of a raw block along with their txid.
+pub fn decode_raw_block(input: &str) -> Result<RpcResult, String> {
+    let bytes = decode_hex(input)?;
+    let block = deserialize::<Block>(&bytes).map_err(|e| format!("Failed to decode block: {e}"))?;
+    let txs = decode_block(&block, true)
+        .map(|(txid, dftx)| json!({ "txid": txid.to_string(), "dftx": dftx }))
+        .collect::<Vec<_>>();
+    Ok(Value::Array(txs).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        let data = std::fs::read_to_string(format!("../ain-dftx/tests/data/{name}.txt")).unwrap();
+        let line = data.lines().find(|line| !line.starts_with("//")).unwrap();
+        line.split(' ').next().unwrap().to_string()
+    }
+
+    fn decode(input: &str) -> Value {
+        match decode_dftx(input).unwrap() {
+            RpcResult::Value(value) => value,
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+
+    #[test]
+    fn should_decode_script_and_payload() {
+        let script = fixture("accounttoaccount");
+        let value = decode(&script);
+        assert_eq!(value["type"], "AccountToAccount");
+        assert_eq!(
+            value["results"]["from"],
+            "76a914078084bb24a623d99521a2d6a53de6c558811de888ac"
+        );
+
+        // The payload without the OP_RETURN push, with and without the 0x prefix
+        let payload = &script[4..];
+        assert_eq!(decode(payload), value);
+        assert_eq!(decode(&format!("0x{payload}")), value);
+    }
+
+    #[test]
+    fn should_decode_transaction() {
+        let script = hex::decode(fixture("poolswap")).unwrap();
+        let mut raw = vec![0x02, 0x00, 0x00, 0x00, 0x01];
+        raw.extend([0u8; 36]);
+        raw.extend([0x00, 0xff, 0xff, 0xff, 0xff, 0x01]);
+        raw.extend([0u8; 8]);
+        raw.push(u8::try_from(script.len()).unwrap());
+        raw.extend(&script);
+        raw.extend([0u8; 4]);
+
+        let value = decode(&hex::encode(raw));
+        assert_eq!(value["type"], "PoolSwap");
+        assert_eq!(value, decode(&hex::encode(&script)));
+    }
+
+    #[test]
+    fn should_round_trip_encode() {
+        let payload = &fixture("poolswap")[6..];
+        let value = decode(payload);
+        match encode_dftx(&value.to_string()).unwrap() {
+            RpcResult::String(hex) => assert_eq!(hex, payload),
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_input() {
+        assert!(decode_dftx("zz").is_err());
+        // OP_RETURN without the DfTx marker
+        assert!(decode_dftx("6a0401020304").is_err());
+        // Truncated payload
+        assert!(decode_dftx(&fixture("accounttoaccount")[..40]).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+//! Client transport over the node's unix domain socket, served with `-ethipc`.
+
+use std::{io, path::Path};
+
+use ain_grpc::ipc::MessageBuffer;
+use jsonrpsee::core::{
+    async_trait,
+    client::{Client, ClientBuilder, ReceivedMessage, TransportReceiverT, TransportSenderT},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        unix::{OwnedReadHalf, OwnedWriteHalf},
+        UnixStream,
+    },
+};
+
+/// Name of the socket in the chain's data directory, matching defid's `-ethipcpath` default.
+pub const DEFAULT_IPC_FILENAME: &str = "metachain.ipc";
+
+struct Sender(OwnedWriteHalf);
+
+#[async_trait]
+impl TransportSenderT for Sender {
+    type Error = io::Error;
+
+    async fn send(&mut self, mut msg: String) -> Result<(), Self::Error> {
+        msg.push('\n');
+        self.0.write_all(msg.as_bytes()).await
+    }
+}
+
+struct Receiver {
+    reader: OwnedReadHalf,
+    messages: MessageBuffer,
+    chunk: Vec<u8>,
+}
+
+#[async_trait]
+impl TransportReceiverT for Receiver {
+    type Error = io::Error;
+
+    async fn receive(&mut self) -> Result<ReceivedMessage, Self::Error> {
+        loop {
+            if let Some(message) = self
+                .messages
+                .next_message()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            {
+                return Ok(ReceivedMessage::Text(message));
+            }
+            let n = self.reader.read(&mut self.chunk).await?;
+            if n == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            self.messages.extend(&self.chunk[..n]);
+        }
+    }
+}
+
+/// Connects to the node's IPC socket at `path`.
+pub async fn connect(path: &Path) -> Result<Client, jsonrpsee::core::Error> {
+    let stream = UnixStream::connect(path).await.map_err(|e| {
+        jsonrpsee::core::Error::Custom(format!(
+            "Failed to connect to IPC socket {}: {e}",
+            path.display()
+        ))
+    })?;
+    let (reader, writer) = stream.into_split();
+    Ok(ClientBuilder::default().build_with_tokio(
+        Sender(writer),
+        Receiver {
+            reader,
+            messages: MessageBuffer::default(),
+            chunk: vec![0; 64 * 1024],
+        },
+    ))
+}
@@ -0,0 +1,379 @@
+//! Encrypted key files in the Web3 Secret Storage (JSON v3) format, as written by geth and most
+//! Ethereum wallets.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use ethereum_types::H160;
+use libsecp256k1::{PublicKey, SecretKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use subtle::ConstantTimeEq;
+
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+const CIPHER: &str = "aes-128-ctr";
+const DKLEN: usize = 32;
+
+/// scrypt cost parameters. New keys use geth's standard parameters.
+#[derive(Clone, Copy, Debug)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        Self {
+            log_n: 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeyFile {
+    pub version: u8,
+    pub id: String,
+    /// Hex address without `0x` prefix. Optional in the format, but always written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(alias = "Crypto")]
+    pub crypto: Crypto,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Crypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    #[serde(flatten)]
+    pub kdf: Kdf,
+    pub mac: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum Kdf {
+    Scrypt {
+        dklen: usize,
+        n: u32,
+        r: u32,
+        p: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        dklen: usize,
+        c: u32,
+        prf: String,
+        salt: String,
+    },
+}
+
+impl Kdf {
+    fn derive_key(&self, password: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Kdf::Scrypt {
+                dklen,
+                n,
+                r,
+                p,
+                salt,
+            } => {
+                if !n.is_power_of_two() || *dklen < DKLEN {
+                    return Err(String::from("Invalid scrypt parameters"));
+                }
+                let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p, *dklen)
+                    .map_err(|e| format!("Invalid scrypt parameters: {e}"))?;
+                let mut key = vec![0u8; *dklen];
+                scrypt::scrypt(password.as_bytes(), &decode_hex(salt)?, &params, &mut key)
+                    .map_err(|e| format!("Failed to derive key: {e}"))?;
+                Ok(key)
+            }
+            Kdf::Pbkdf2 {
+                dklen,
+                c,
+                prf,
+                salt,
+            } => {
+                if prf != "hmac-sha256" {
+                    return Err(format!("Unsupported pbkdf2 prf: {prf}"));
+                }
+                if *dklen < DKLEN {
+                    return Err(String::from("Invalid pbkdf2 parameters"));
+                }
+                let mut key = vec![0u8; *dklen];
+                pbkdf2::pbkdf2_hmac::<Sha256>(
+                    password.as_bytes(),
+                    &decode_hex(salt)?,
+                    *c,
+                    &mut key,
+                );
+                Ok(key)
+            }
+        }
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    hex::decode(s.trim_start_matches("0x")).map_err(|e| format!("Invalid hex in key file: {e}"))
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+/// MAC over the second half of the derived key and the ciphertext.
+fn mac(derived_key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    Keccak256::new()
+        .chain_update(&derived_key[16..32])
+        .chain_update(ciphertext)
+        .finalize()
+        .into()
+}
+
+pub fn generate_key() -> SecretKey {
+    loop {
+        if let Ok(key) = SecretKey::parse(&random_bytes()) {
+            return key;
+        }
+    }
+}
+
+pub fn address(key: &SecretKey) -> H160 {
+    let public = PublicKey::from_secret_key(key).serialize();
+    H160::from_slice(&Keccak256::digest(&public[1..])[12..])
+}
+
+pub fn encrypt(key: &SecretKey, password: &str, params: ScryptParams) -> Result<KeyFile, String> {
+    let kdf = Kdf::Scrypt {
+        dklen: DKLEN,
+        n: 1 << params.log_n,
+        r: params.r,
+        p: params.p,
+        salt: hex::encode(random_bytes::<32>()),
+    };
+    let derived_key = kdf.derive_key(password)?;
+
+    let iv = random_bytes::<16>();
+    let mut ciphertext = key.serialize().to_vec();
+    Aes128Ctr::new(derived_key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+
+    let mut id = random_bytes::<16>();
+    // UUID version 4, variant 1
+    id[6] = (id[6] & 0x0f) | 0x40;
+    id[8] = (id[8] & 0x3f) | 0x80;
+    let id = hex::encode(id);
+
+    Ok(KeyFile {
+        version: 3,
+        id: format!(
+            "{}-{}-{}-{}-{}",
+            &id[..8],
+            &id[8..12],
+            &id[12..16],
+            &id[16..20],
+            &id[20..]
+        ),
+        address: Some(hex::encode(address(key))),
+        crypto: Crypto {
+            cipher: String::from(CIPHER),
+            cipherparams: CipherParams {
+                iv: hex::encode(iv),
+            },
+            mac: hex::encode(mac(&derived_key, &ciphertext)),
+            ciphertext: hex::encode(ciphertext),
+            kdf,
+        },
+    })
+}
+
+pub fn decrypt(file: &KeyFile, password: &str) -> Result<SecretKey, String> {
+    if file.version != 3 {
+        return Err(format!("Unsupported key file version: {}", file.version));
+    }
+    let crypto = &file.crypto;
+    if crypto.cipher != CIPHER {
+        return Err(format!("Unsupported cipher: {}", crypto.cipher));
+    }
+
+    let derived_key = crypto.kdf.derive_key(password)?;
+    let mut ciphertext = decode_hex(&crypto.ciphertext)?;
+    let expected_mac = decode_hex(&crypto.mac)?;
+    // Compared in constant time to not leak how much of the MAC matches
+    if !bool::from(mac(&derived_key, &ciphertext)[..].ct_eq(&expected_mac)) {
+        return Err(String::from("Invalid password"));
+    }
+
+    let iv: [u8; 16] = decode_hex(&crypto.cipherparams.iv)?
+        .try_into()
+        .map_err(|_| String::from("Invalid IV length"))?;
+    Aes128Ctr::new(derived_key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+    SecretKey::parse_slice(&ciphertext).map_err(|e| format!("Invalid private key: {e}"))
+}
+
+/// Directory of key files, one per address.
+pub struct Keystore {
+    dir: PathBuf,
+}
+
+impl Keystore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Encrypts `key` with `password` and writes it to the keystore. Returns the key address.
+    pub fn store(&self, key: &SecretKey, password: &str) -> Result<H160, String> {
+        let address = address(key);
+        if self.find(address)?.is_some() {
+            return Err(format!("Key for {address:#x} already exists"));
+        }
+        let file = encrypt(key, password, ScryptParams::default())?;
+        let json = serde_json::to_vec_pretty(&file).map_err(|e| e.to_string())?;
+
+        create_dir(&self.dir)?;
+        let path = self.dir.join(format!("{address:x}.json"));
+        write_private(&path, &json)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        Ok(address)
+    }
+
+    /// Decrypts the key of `address`.
+    pub fn load(&self, address: H160, password: &str) -> Result<SecretKey, String> {
+        let (_, file) = self
+            .find(address)?
+            .ok_or_else(|| format!("No key for {address:#x} in {}", self.dir.display()))?;
+        decrypt(&file, password)
+    }
+
+    /// Returns the addresses of all key files in the keystore.
+    pub fn accounts(&self) -> Result<Vec<H160>, String> {
+        Ok(self
+            .key_files()?
+            .into_iter()
+            .filter_map(|(_, file)| file_address(&file))
+            .collect())
+    }
+
+    fn find(&self, address: H160) -> Result<Option<(PathBuf, KeyFile)>, String> {
+        Ok(self
+            .key_files()?
+            .into_iter()
+            .find(|(_, file)| file_address(file) == Some(address)))
+    }
+
+    /// Reads every file of the keystore that parses as a key file.
+    fn key_files(&self) -> Result<Vec<(PathBuf, KeyFile)>, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read {}: {e}", self.dir.display())),
+        };
+        let mut files = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter_map(|path| {
+                let file = serde_json::from_slice::<KeyFile>(&fs::read(&path).ok()?).ok()?;
+                Some((path, file))
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(files)
+    }
+}
+
+fn file_address(file: &KeyFile) -> Option<H160> {
+    let bytes = decode_hex(file.address.as_deref()?).ok()?;
+    (bytes.len() == 20).then(|| H160::from_slice(&bytes))
+}
+
+fn create_dir(dir: &Path) -> Result<(), String> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder
+        .create(dir)
+        .map_err(|e| format!("Failed to create {}: {e}", dir.display()))
+}
+
+/// Writes a new file readable by the owner only.
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vector from the Web3 Secret Storage definition
+    const PBKDF2_KEY_FILE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    #[test]
+    fn test_decrypt_pbkdf2() {
+        let file = serde_json::from_str::<KeyFile>(PBKDF2_KEY_FILE).unwrap();
+        let key = decrypt(&file, "testpassword").unwrap();
+        assert_eq!(
+            hex::encode(key.serialize()),
+            "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+        );
+        assert_eq!(
+            decrypt(&file, "wrongpassword").unwrap_err(),
+            "Invalid password"
+        );
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let key = generate_key();
+        let params = ScryptParams {
+            log_n: 10,
+            r: 8,
+            p: 1,
+        };
+        let file = encrypt(&key, "password", params).unwrap();
+        let json = serde_json::to_string(&file).unwrap();
+        let file = serde_json::from_str::<KeyFile>(&json).unwrap();
+
+        assert_eq!(file_address(&file), Some(address(&key)));
+        assert_eq!(decrypt(&file, "password").unwrap(), key);
+        assert!(decrypt(&file, "").is_err());
+    }
+}
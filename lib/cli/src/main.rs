@@ -1,8 +1,16 @@
+mod abi;
 mod command;
+mod dftx;
 mod format;
+#[cfg(unix)]
+mod ipc;
+mod keystore;
 mod params;
 mod result;
 mod structs;
+mod wallet;
+
+use std::path::PathBuf;
 
 use ain_grpc::block::BlockNumber;
 use command::execute_cli_command;
@@ -12,7 +20,7 @@ use jsonrpsee::http_client::HttpClientBuilder;
 use params::{BaseChainParams, Chain};
 use structopt::StructOpt;
 
-use crate::structs::CallRequest;
+use crate::structs::{CallRequest, TransactionRequest};
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "metachain-cli", about = "Metachain JSON-RPC CLI")]
@@ -25,6 +33,11 @@ struct Opt {
     #[structopt(long, default_value = "json")]
     format: Format,
 
+    /// Connect over the node's IPC socket instead of HTTP. Defaults to the socket in the
+    /// chain's data directory when no path is given.
+    #[structopt(long)]
+    ipc: Option<Option<PathBuf>>,
+
     #[structopt(subcommand)]
     cmd: MetachainCLI,
 }
@@ -126,20 +139,85 @@ pub enum MetachainCLI {
     },
     /// Returns the current price per gas in wei.
     GasPrice,
+    /// Decodes a hex custom tx payload, OP_RETURN script or raw transaction into JSON.
+    /// Does not require a node.
+    DecodeDftx { input: String },
+    /// Encodes a JSON custom tx into its hex payload. Does not require a node.
+    EncodeDftx { input: String },
+    /// Lists the custom txs of a hex raw block. Does not require a node.
+    DecodeBlock { input: String },
+    /// Manages keys in a local encrypted keystore and signs transactions with them.
+    Wallet(wallet::WalletOpt),
+    /// Calls a contract function, ABI-encoding the arguments and decoding the return values.
+    CallFn {
+        #[structopt(long, parse(try_from_str))]
+        to: H160,
+        #[structopt(long, parse(try_from_str))]
+        from: Option<H160>,
+        #[structopt(long, parse(try_from_str))]
+        block_number: Option<BlockNumber>,
+        #[structopt(flatten)]
+        abi: abi::AbiOpt,
+        /// Function signature such as `balanceOf(address)(uint256)`, or a function name of the ABI.
+        function: String,
+        #[structopt(allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Signs a contract function call with a keystore key, sends it and returns the transaction hash.
+    SendFn {
+        #[structopt(long, parse(try_from_str))]
+        from: H160,
+        #[structopt(flatten)]
+        keystore: wallet::KeystoreOpt,
+        #[structopt(flatten)]
+        abi: abi::AbiOpt,
+        #[structopt(flatten)]
+        input: Box<TransactionRequest>,
+        /// Function signature such as `transfer(address,uint256)`, or a function name of the ABI.
+        function: String,
+        #[structopt(allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Decodes the logs of a transaction receipt against an ABI.
+    DecodeLogs {
+        /// A JSON ABI file, or one of the built-in `dst20` and `transferdomain` ABIs.
+        #[structopt(long)]
+        abi: String,
+        #[structopt(parse(try_from_str))]
+        hash: H256,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<(), jsonrpsee::core::Error> {
     let opt = Opt::from_args();
 
-    let client = {
-        let chain = opt.chain;
-        let base_chain_params = BaseChainParams::create(&chain);
-        let json_addr = format!("http://127.0.0.1:{}", base_chain_params.eth_rpc_port);
-        HttpClientBuilder::default().build(json_addr)
-    }?;
-
-    let result = execute_cli_command(opt.cmd, &client).await?;
+    let base_chain_params = BaseChainParams::create(&opt.chain);
+    let result = match opt.ipc {
+        #[cfg(unix)]
+        Some(path) => {
+            let path = path.unwrap_or_else(|| {
+                base_chain_params
+                    .data_dir_path()
+                    .join(ipc::DEFAULT_IPC_FILENAME)
+            });
+            let client = ipc::connect(&path).await?;
+            execute_cli_command(opt.cmd, &client, &base_chain_params).await?
+        }
+        #[cfg(not(unix))]
+        Some(_) => {
+            return Err(jsonrpsee::core::Error::Custom(
+                "IPC is only supported on unix platforms".to_string(),
+            ))
+        }
+        None => {
+            let client = {
+                let json_addr = format!("http://127.0.0.1:{}", base_chain_params.eth_rpc_port);
+                HttpClientBuilder::default().build(json_addr)
+            }?;
+            execute_cli_command(opt.cmd, &client, &base_chain_params).await?
+        }
+    };
     match opt.format {
         Format::Rust => println!("{result}"),
         Format::Json => println!("{}", serde_json::to_string(&result)?),
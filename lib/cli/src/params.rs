@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 #[derive(Debug, Default)]
 pub enum Chain {
@@ -99,3 +99,22 @@ impl BaseChainParams {
         }
     }
 }
+
+/// defid's default data directory for the platform.
+fn default_data_dir() -> PathBuf {
+    let home = || PathBuf::from(std::env::var_os("HOME").unwrap_or_default());
+    if cfg!(windows) {
+        PathBuf::from(std::env::var_os("APPDATA").unwrap_or_default()).join("DeFi")
+    } else if cfg!(target_os = "macos") {
+        home().join("Library/Application Support/DeFi")
+    } else {
+        home().join(".defi")
+    }
+}
+
+impl BaseChainParams {
+    /// The data directory of the chain within defid's default data directory.
+    pub fn data_dir_path(&self) -> PathBuf {
+        default_data_dir().join(&self.data_dir)
+    }
+}
@@ -1,6 +1,7 @@
 use ain_grpc::{block::RpcBlock, codegen::types::EthTransactionInfo};
 use ethereum_types::{H256, U256};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -14,6 +15,7 @@ pub enum RpcResult {
     Usize(usize),
     Block(Box<RpcBlock>),
     Option(Option<Box<RpcResult>>),
+    Value(Value),
 }
 
 impl From<String> for RpcResult {
@@ -64,6 +66,12 @@ impl From<RpcBlock> for RpcResult {
     }
 }
 
+impl From<Value> for RpcResult {
+    fn from(value: Value) -> Self {
+        RpcResult::Value(value)
+    }
+}
+
 impl<T: Into<RpcResult> + 'static> From<Option<T>> for RpcResult {
     fn from(value: Option<T>) -> Self {
         RpcResult::Option(value.map(|v| Box::new(v.into())))
@@ -87,6 +95,7 @@ impl Display for RpcResult {
                 Some(inner_value) => write!(f, "Some({inner_value})"),
                 None => write!(f, "None"),
             },
+            RpcResult::Value(value) => write!(f, "{value}"),
         }
     }
 }
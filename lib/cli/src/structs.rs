@@ -3,7 +3,7 @@ use ethereum_types::{H160, U256};
 use structopt::StructOpt;
 
 #[derive(Debug)]
-pub struct HexData(Vec<u8>);
+pub struct HexData(pub Vec<u8>);
 
 pub type AccessList = Vec<AccessListItem>;
 
@@ -94,3 +94,51 @@ impl From<CallRequest> for ain_grpc::call_request::CallRequest {
         }
     }
 }
+
+#[derive(Debug, StructOpt)]
+pub struct TransactionRequest {
+    /// The recipient of the transaction. Deploys a contract when omitted.
+    #[structopt(long, parse(try_from_str = parse_h160))]
+    pub to: Option<H160>,
+
+    /// The amount of DFI (in Wei) sent with the transaction (optional).
+    #[structopt(long, parse(try_from_str = parse_u256))]
+    pub value: Option<U256>,
+
+    /// The input data for the transaction (optional).
+    #[structopt(long, parse(try_from_str = parse_hex_data))]
+    pub data: Option<HexData>,
+
+    /// The gas limit for the transaction (optional, estimated by the node when omitted).
+    #[structopt(long, parse(try_from_str = parse_u256))]
+    pub gas: Option<U256>,
+
+    /// The gas price for legacy and EIP-2930 transactions (optional, fetched from the node when omitted).
+    #[structopt(long, parse(try_from_str = parse_u256))]
+    pub gas_price: Option<U256>,
+
+    /// The maximum fee per gas for EIP-1559 transactions (optional, derived from the latest base fee when omitted).
+    #[structopt(long = "max-fee-per-gas", parse(try_from_str = parse_u256))]
+    pub max_fee_per_gas: Option<U256>,
+
+    /// The priority fee per gas for EIP-1559 transactions (optional, fetched from the node when omitted).
+    #[structopt(long = "max-priority-fee-per-gas", parse(try_from_str = parse_u256))]
+    pub max_priority_fee_per_gas: Option<U256>,
+
+    /// The nonce value for the transaction (optional, fetched from the node when omitted).
+    #[structopt(long, parse(try_from_str = parse_u256))]
+    pub nonce: Option<U256>,
+
+    /// The access list for the transaction (optional, used for EIP-2930 and EIP-1559 transactions).
+    #[structopt(long, parse(try_from_str = parse_access_list))]
+    pub access_list: Option<AccessList>,
+
+    /// The EIP-2718 transaction type: 0 (legacy), 1 (EIP-2930) or 2 (EIP-1559).
+    /// Inferred from the fee fields when omitted, defaulting to EIP-1559.
+    #[structopt(long)]
+    pub transaction_type: Option<u8>,
+
+    /// The chain ID to sign for (optional, fetched from the node when omitted).
+    #[structopt(long)]
+    pub chain_id: Option<u64>,
+}
@@ -0,0 +1,451 @@
+use std::{fs, path::PathBuf};
+
+use ain_evm::transaction::{LegacyUnsignedTransaction, LOWER_H256};
+use ain_grpc::{block::BlockNumber, call_request::CallRequest, rpc::MetachainRPCClient};
+use ethereum::{
+    EIP1559Transaction, EIP1559TransactionMessage, EIP2930Transaction, EIP2930TransactionMessage,
+    EnvelopedEncodable, TransactionAction, TransactionSignature, TransactionV2,
+};
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::core::Error;
+use libsecp256k1::SecretKey;
+use structopt::StructOpt;
+
+use crate::{
+    keystore::{self, Keystore},
+    params::BaseChainParams,
+    result::RpcResult,
+    structs::TransactionRequest,
+};
+
+#[derive(Debug, StructOpt)]
+pub struct KeystoreOpt {
+    /// The keystore directory. Defaults to the `keystore` directory of the chain data directory.
+    #[structopt(long, parse(from_os_str))]
+    keystore: Option<PathBuf>,
+
+    /// Read the keystore password from this file instead of prompting for it.
+    #[structopt(long, parse(from_os_str))]
+    password_file: Option<PathBuf>,
+}
+
+impl KeystoreOpt {
+    fn keystore(&self, params: &BaseChainParams) -> Keystore {
+        Keystore::new(
+            self.keystore
+                .clone()
+                .unwrap_or_else(|| params.data_dir_path().join("keystore")),
+        )
+    }
+
+    fn password(&self, confirm: bool) -> Result<String, Error> {
+        if let Some(path) = &self.password_file {
+            let password = fs::read_to_string(path)
+                .map_err(|e| to_custom_err(format!("Failed to read {}: {e}", path.display())))?;
+            return Ok(password.trim_end_matches(['\r', '\n']).to_string());
+        }
+        let password = rpassword::prompt_password("Password: ").map_err(to_custom_err)?;
+        if confirm
+            && rpassword::prompt_password("Repeat password: ").map_err(to_custom_err)? != password
+        {
+            return Err(to_custom_err("Passwords do not match"));
+        }
+        Ok(password)
+    }
+
+    /// Decrypts the key of `from` and signs `input` with it, filling the fields left out from
+    /// the node.
+    pub async fn sign_transaction<C: MetachainRPCClient + Sync>(
+        &self,
+        client: &C,
+        params: &BaseChainParams,
+        from: H160,
+        input: &TransactionRequest,
+    ) -> Result<TransactionV2, Error> {
+        let key = self
+            .keystore(params)
+            .load(from, &self.password(false)?)
+            .map_err(to_custom_err)?;
+        let message = transaction_message(client, from, input).await?;
+        sign(&key, message)
+    }
+
+    /// Signs `input` like [`Self::sign_transaction`] and sends it. Returns the transaction hash.
+    pub async fn send_transaction<C: MetachainRPCClient + Sync>(
+        &self,
+        client: &C,
+        params: &BaseChainParams,
+        from: H160,
+        input: &TransactionRequest,
+    ) -> Result<String, Error> {
+        let signed = self.sign_transaction(client, params, from, input).await?;
+        client
+            .send_raw_transaction(&hex::encode(signed.encode()))
+            .await
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct WalletOpt {
+    #[structopt(flatten)]
+    keystore: KeystoreOpt,
+
+    #[structopt(subcommand)]
+    cmd: WalletCommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum WalletCommand {
+    /// Generates a new key, stores it encrypted in the keystore and returns its address.
+    New,
+    /// Imports a hex private key into the keystore and returns its address.
+    Import { private_key: String },
+    /// Returns the addresses of the keys in the keystore.
+    List,
+    /// Signs a transaction with a keystore key and returns the raw transaction.
+    SignTransaction {
+        #[structopt(long, parse(try_from_str))]
+        from: H160,
+        #[structopt(flatten)]
+        input: Box<TransactionRequest>,
+    },
+    /// Signs a transaction with a keystore key, sends it and returns the transaction hash.
+    SendTransaction {
+        #[structopt(long, parse(try_from_str))]
+        from: H160,
+        #[structopt(flatten)]
+        input: Box<TransactionRequest>,
+    },
+}
+
+enum TransactionMessage {
+    /// Legacy transactions carry the chain ID in the signature.
+    Legacy(LegacyUnsignedTransaction, u64),
+    EIP2930(EIP2930TransactionMessage),
+    EIP1559(EIP1559TransactionMessage),
+}
+
+pub fn to_custom_err<T: ToString>(e: T) -> Error {
+    Error::Custom(e.to_string())
+}
+
+pub async fn execute_wallet_command<C: MetachainRPCClient + Sync>(
+    opt: WalletOpt,
+    client: &C,
+    params: &BaseChainParams,
+) -> Result<RpcResult, Error> {
+    let keystore = opt.keystore.keystore(params);
+
+    let result = match &opt.cmd {
+        WalletCommand::New => {
+            let password = opt.keystore.password(true)?;
+            let address = keystore
+                .store(&keystore::generate_key(), &password)
+                .map_err(to_custom_err)?;
+            format!("{address:#x}").into()
+        }
+        WalletCommand::Import { private_key } => {
+            let bytes = hex::decode(private_key.trim_start_matches("0x")).map_err(to_custom_err)?;
+            let key = SecretKey::parse_slice(&bytes)
+                .map_err(|e| to_custom_err(format!("Invalid private key: {e}")))?;
+            let password = opt.keystore.password(true)?;
+            let address = keystore.store(&key, &password).map_err(to_custom_err)?;
+            format!("{address:#x}").into()
+        }
+        WalletCommand::List => keystore
+            .accounts()
+            .map_err(to_custom_err)?
+            .into_iter()
+            .map(|address| format!("{address:#x}"))
+            .collect::<Vec<_>>()
+            .into(),
+        WalletCommand::SignTransaction { from, input } => {
+            let signed = opt
+                .keystore
+                .sign_transaction(client, params, *from, input)
+                .await?;
+            format!("0x{}", hex::encode(signed.encode())).into()
+        }
+        WalletCommand::SendTransaction { from, input } => opt
+            .keystore
+            .send_transaction(client, params, *from, input)
+            .await?
+            .into(),
+    };
+    Ok(result)
+}
+
+/// Builds the transaction to sign, filling the chain ID, nonce, fees and gas limit left out of
+/// `input` from the node.
+async fn transaction_message<C: MetachainRPCClient + Sync>(
+    client: &C,
+    from: H160,
+    input: &TransactionRequest,
+) -> Result<TransactionMessage, Error> {
+    let chain_id = match input.chain_id {
+        Some(chain_id) => chain_id,
+        None => {
+            let chain_id = client.chain_id().await?;
+            u64::from_str_radix(chain_id.trim_start_matches("0x"), 16).map_err(to_custom_err)?
+        }
+    };
+    let nonce = match input.nonce {
+        Some(nonce) => nonce,
+        None => {
+            client
+                .get_transaction_count(from, Some(BlockNumber::Pending))
+                .await?
+        }
+    };
+
+    let transaction_type = match input.transaction_type {
+        Some(transaction_type @ 0..=2) => transaction_type,
+        Some(transaction_type) => {
+            return Err(to_custom_err(format!(
+                "Unsupported transaction type: {transaction_type}"
+            )))
+        }
+        None => match (input.gas_price, &input.access_list) {
+            (Some(_), None) => 0,
+            (Some(_), Some(_)) => 1,
+            (None, _) => 2,
+        },
+    };
+
+    let (gas_price, max_fee_per_gas, max_priority_fee_per_gas) = if transaction_type == 2 {
+        let max_priority_fee_per_gas = match input.max_priority_fee_per_gas {
+            Some(fee) => fee,
+            None => client.max_priority_fee_per_gas().await?,
+        };
+        let max_fee_per_gas = match input.max_fee_per_gas {
+            Some(fee) => fee,
+            None => {
+                // Leaves room for the base fee to double before the tx is priced out
+                let base_fee = client
+                    .get_block_by_number(BlockNumber::Latest, Some(false))
+                    .await?
+                    .ok_or_else(|| to_custom_err("Latest block not found"))?
+                    .base_fee_per_gas;
+                base_fee.saturating_mul(U256::from(2)) + max_priority_fee_per_gas
+            }
+        };
+        (None, Some(max_fee_per_gas), Some(max_priority_fee_per_gas))
+    } else {
+        let gas_price = match input.gas_price {
+            Some(gas_price) => gas_price,
+            None => client.gas_price().await?,
+        };
+        (Some(gas_price), None, None)
+    };
+
+    let data = input
+        .data
+        .as_ref()
+        .map(|data| data.0.clone())
+        .unwrap_or_default();
+    let access_list = input.access_list.clone().unwrap_or_default();
+    let gas_limit = match input.gas {
+        Some(gas) => gas,
+        None => {
+            let request = CallRequest {
+                from: Some(from),
+                to: input.to,
+                gas_price,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                value: input.value,
+                data: Some(data.clone().into()),
+                nonce: Some(nonce),
+                access_list: (transaction_type != 0).then(|| access_list.clone()),
+                transaction_type: Some(U256::from(transaction_type)),
+                ..Default::default()
+            };
+            client.estimate_gas(request, None, None).await?
+        }
+    };
+
+    let action = match input.to {
+        Some(to) => TransactionAction::Call(to),
+        None => TransactionAction::Create,
+    };
+    let value = input.value.unwrap_or_default();
+    Ok(match transaction_type {
+        0 => TransactionMessage::Legacy(
+            LegacyUnsignedTransaction {
+                nonce,
+                gas_price: gas_price.unwrap_or_default(),
+                gas_limit,
+                action,
+                value,
+                input: data,
+                // Dummy sig, replaced when signing
+                sig: TransactionSignature::new(27, LOWER_H256, LOWER_H256)
+                    .ok_or_else(|| to_custom_err("Invalid signature"))?,
+            },
+            chain_id,
+        ),
+        1 => TransactionMessage::EIP2930(EIP2930TransactionMessage {
+            chain_id,
+            nonce,
+            gas_price: gas_price.unwrap_or_default(),
+            gas_limit,
+            action,
+            value,
+            input: data,
+            access_list,
+        }),
+        _ => TransactionMessage::EIP1559(EIP1559TransactionMessage {
+            chain_id,
+            nonce,
+            max_priority_fee_per_gas: max_priority_fee_per_gas.unwrap_or_default(),
+            max_fee_per_gas: max_fee_per_gas.unwrap_or_default(),
+            gas_limit,
+            action,
+            value,
+            input: data,
+            access_list,
+        }),
+    })
+}
+
+/// Signs the hash of a typed transaction message, returning the y parity, r and s.
+fn sign_hash(key: &SecretKey, hash: H256) -> Result<(bool, H256, H256), Error> {
+    let message = libsecp256k1::Message::parse_slice(hash.as_bytes())
+        .map_err(|_| to_custom_err("Invalid signing message"))?;
+    let (signature, recid) = libsecp256k1::sign(&message, key);
+    let rs = signature.serialize();
+    Ok((
+        recid.serialize() != 0,
+        H256::from_slice(&rs[0..32]),
+        H256::from_slice(&rs[32..64]),
+    ))
+}
+
+fn sign(key: &SecretKey, message: TransactionMessage) -> Result<TransactionV2, Error> {
+    Ok(match message {
+        TransactionMessage::Legacy(m, chain_id) => {
+            TransactionV2::Legacy(m.sign(&key.serialize(), chain_id).map_err(to_custom_err)?)
+        }
+        TransactionMessage::EIP2930(m) => {
+            let (odd_y_parity, r, s) = sign_hash(key, m.hash())?;
+            TransactionV2::EIP2930(EIP2930Transaction {
+                chain_id: m.chain_id,
+                nonce: m.nonce,
+                gas_price: m.gas_price,
+                gas_limit: m.gas_limit,
+                action: m.action,
+                value: m.value,
+                input: m.input,
+                access_list: m.access_list,
+                odd_y_parity,
+                r,
+                s,
+            })
+        }
+        TransactionMessage::EIP1559(m) => {
+            let (odd_y_parity, r, s) = sign_hash(key, m.hash())?;
+            TransactionV2::EIP1559(EIP1559Transaction {
+                chain_id: m.chain_id,
+                nonce: m.nonce,
+                max_priority_fee_per_gas: m.max_priority_fee_per_gas,
+                max_fee_per_gas: m.max_fee_per_gas,
+                gas_limit: m.gas_limit,
+                action: m.action,
+                value: m.value,
+                input: m.input,
+                access_list: m.access_list,
+                odd_y_parity,
+                r,
+                s,
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ain_evm::transaction::SignedTx;
+    use libsecp256k1::{PublicKey, RecoveryId, Signature};
+
+    use super::*;
+
+    const CHAIN_ID: u64 = 1133;
+
+    fn key() -> SecretKey {
+        SecretKey::parse(&[0x42; 32]).unwrap()
+    }
+
+    fn action() -> TransactionAction {
+        TransactionAction::Call(H160::repeat_byte(1))
+    }
+
+    #[test]
+    fn should_recover_signer_of_signed_hash() {
+        let key = key();
+        let hash = H256::repeat_byte(0xab);
+        let (odd_y_parity, r, s) = sign_hash(&key, hash).unwrap();
+
+        let mut rs = [0u8; 64];
+        rs[..32].copy_from_slice(r.as_bytes());
+        rs[32..].copy_from_slice(s.as_bytes());
+        let public = libsecp256k1::recover(
+            &libsecp256k1::Message::parse(hash.as_fixed_bytes()),
+            &Signature::parse_standard(&rs).unwrap(),
+            &RecoveryId::parse(u8::from(odd_y_parity)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(public, PublicKey::from_secret_key(&key));
+    }
+
+    #[test]
+    fn should_recover_sender_of_signed_transactions() {
+        let key = key();
+        let legacy = TransactionMessage::Legacy(
+            LegacyUnsignedTransaction {
+                nonce: U256::one(),
+                gas_price: U256::from(10_000_000_000u64),
+                gas_limit: U256::from(21_000),
+                action: action(),
+                value: U256::one(),
+                input: Vec::new(),
+                sig: TransactionSignature::new(27, LOWER_H256, LOWER_H256).unwrap(),
+            },
+            CHAIN_ID,
+        );
+        let eip2930 = TransactionMessage::EIP2930(EIP2930TransactionMessage {
+            chain_id: CHAIN_ID,
+            nonce: U256::one(),
+            gas_price: U256::from(10_000_000_000u64),
+            gas_limit: U256::from(21_000),
+            action: action(),
+            value: U256::one(),
+            input: vec![0x01],
+            access_list: Vec::new(),
+        });
+        let eip1559 = TransactionMessage::EIP1559(EIP1559TransactionMessage {
+            chain_id: CHAIN_ID,
+            nonce: U256::one(),
+            max_priority_fee_per_gas: U256::from(1_500_000_000u64),
+            max_fee_per_gas: U256::from(10_000_000_000u64),
+            gas_limit: U256::from(21_000),
+            action: action(),
+            value: U256::one(),
+            input: vec![0x02],
+            access_list: Vec::new(),
+        });
+
+        for message in [legacy, eip2930, eip1559] {
+            let signed = sign(&key, message).unwrap();
+            match &signed {
+                // EIP-155 replay protection
+                TransactionV2::Legacy(tx) => assert_eq!(tx.signature.chain_id(), Some(CHAIN_ID)),
+                TransactionV2::EIP2930(tx) => assert_eq!(tx.chain_id, CHAIN_ID),
+                TransactionV2::EIP1559(tx) => assert_eq!(tx.chain_id, CHAIN_ID),
+            }
+            assert_eq!(
+                SignedTx::try_from(signed).unwrap().sender,
+                keystore::address(&key)
+            );
+        }
+    }
+}
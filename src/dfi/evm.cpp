@@ -19,6 +19,12 @@ ResVal<std::string> CVMDomainGraphView::GetVMDomainBlockEdge(VMDomainEdge type,
     return DeFiErrors::DatabaseKeyNotFound(blockHashKey);
 }
 
+Res CVMDomainGraphView::DelVMDomainBlockEdge(VMDomainEdge type, std::string blockHashKey) {
+    return EraseBy<VMDomainBlockEdge>(std::pair(static_cast<uint8_t>(type), blockHashKey))
+               ? Res::Ok()
+               : DeFiErrors::DatabaseRWFailure(blockHashKey);
+}
+
 Res CVMDomainGraphView::SetVMDomainTxEdge(VMDomainEdge type, std::string txHashKey, std::string txHash) {
     return WriteBy<VMDomainTxEdge>(std::pair(static_cast<uint8_t>(type), txHashKey), txHash)
                ? Res::Ok()
@@ -36,6 +36,7 @@ class CVMDomainGraphView : public virtual CStorageView {
 public:
     Res SetVMDomainBlockEdge(VMDomainEdge type, std::string blockHashKey, std::string blockHash);
     ResVal<std::string> GetVMDomainBlockEdge(VMDomainEdge type, std::string blockHashKey) const;
+    Res DelVMDomainBlockEdge(VMDomainEdge type, std::string blockHashKey);
     void ForEachVMDomainBlockEdges(
         std::function<bool(const std::pair<VMDomainEdge, std::string> &, const std::string &)> callback,
         const std::pair<VMDomainEdge, std::string> &start = {});
@@ -11,7 +11,9 @@
 #include <key_io.h>
 #include <logging.h>
 #include <net.h>
+#include <rpc/server.h>
 #include <util/system.h>
+#include <util/time.h>
 
 #include <algorithm>
 #include <array>
@@ -529,3 +531,152 @@ uint64_t getDF24Height() {
 bool migrateTokensFromEVM(std::size_t mnview_ptr, TokenAmount old_amount, TokenAmount &new_amount) {
     return ExecuteTokenMigrationEVM(mnview_ptr, old_amount, new_amount);
 }
+
+uint32_t mineBlocks(uint32_t count) {
+    const auto operators = gArgs.GetArgs("-masternode_operator");
+    if (operators.empty()) {
+        LogPrintf("mineBlocks: no -masternode_operator set\n");
+        return 0;
+    }
+
+    JSONRPCRequest request;
+    request.strMethod = "generatetoaddress";
+    request.params = UniValue(UniValue::VARR);
+    request.params.push_back(static_cast<int>(count));
+    request.params.push_back(operators.front());
+
+    try {
+        return tableRPC.execute(request).get_int();
+    } catch (const UniValue &objError) {
+        LogPrintf("mineBlocks: %s\n", objError.write());
+    } catch (const std::exception &e) {
+        LogPrintf("mineBlocks: %s\n", e.what());
+    }
+    return 0;
+}
+
+int64_t increaseMockTime(int64_t seconds) {
+    const auto now = GetMockTime() ? GetMockTime() : GetTime();
+    SetMockTime(now + seconds);
+    return now + seconds;
+}
+
+bool rollbackToBlock(uint32_t height) {
+    CBlockIndex *pindex;
+    {
+        LOCK(cs_main);
+        pindex = ::ChainActive()[height + 1];
+    }
+    if (!pindex) {
+        return true;
+    }
+
+    CValidationState state;
+    InvalidateBlock(state, Params(), pindex);
+    if (state.IsValid()) {
+        ActivateBestChain(state, Params());
+    }
+    if (!state.IsValid()) {
+        LogPrintf("rollbackToBlock: %s\n", FormatStateMessage(state));
+        return false;
+    }
+
+    // Clear the failure flags so that the reverted blocks can be mined or reconsidered again,
+    // without reactivating them as the chain with the most work.
+    LOCK(cs_main);
+    ResetBlockFailureFlags(pindex);
+    auto &candidates = ::ChainstateActive().setBlockIndexCandidates;
+    for (auto it = candidates.begin(); it != candidates.end();) {
+        if ((*it)->GetAncestor(pindex->nHeight) == pindex) {
+            it = candidates.erase(it);
+        } else {
+            ++it;
+        }
+    }
+    return true;
+}
+
+bool replaceEVMBlockHash(std::array<uint8_t, 32> oldEvmBlockHash, std::array<uint8_t, 32> newEvmBlockHash) {
+    LOCK(cs_main);
+
+    const auto oldHash = uint256::FromByteArray(oldEvmBlockHash).GetHex();
+    const auto newHash = uint256::FromByteArray(newEvmBlockHash).GetHex();
+    const auto dvmBlockHash = pcustomcsview->GetVMDomainBlockEdge(VMDomainEdge::EVMToDVM, oldHash);
+    if (!dvmBlockHash) {
+        // Not mapped to a DVM block, like the EVM genesis block
+        return true;
+    }
+
+    auto res = pcustomcsview->DelVMDomainBlockEdge(VMDomainEdge::EVMToDVM, oldHash);
+    if (res) {
+        res = pcustomcsview->SetVMDomainBlockEdge(VMDomainEdge::DVMToEVM, *dvmBlockHash, newHash);
+    }
+    if (res) {
+        res = pcustomcsview->SetVMDomainBlockEdge(VMDomainEdge::EVMToDVM, newHash, *dvmBlockHash);
+    }
+    if (!res) {
+        LogPrintf("replaceEVMBlockHash: %s\n", res.msg);
+        return false;
+    }
+    return true;
+}
+
+uint32_t getEthRPCRateLimit() {
+    return gArgs.GetArg("-ethrpcratelimit", DEFAULT_ETH_RPC_RATE_LIMIT);
+}
+
+uint32_t getEthRPCHeavyRateLimit() {
+    return gArgs.GetArg("-ethrpcheavyratelimit", DEFAULT_ETH_RPC_HEAVY_RATE_LIMIT);
+}
+
+rust::string getEthRPCAllowedMethods() {
+    return gArgs.GetArg("-ethrpcallowmethods", "");
+}
+
+rust::string getEthRPCDeniedMethods() {
+    return gArgs.GetArg("-ethrpcdenymethods", "");
+}
+
+rust::string getEthRPCConcurrencyLimits() {
+    return gArgs.GetArg("-ethrpcconcurrencylimits", DEFAULT_ETH_RPC_CONCURRENCY_LIMITS);
+}
+
+bool isEthRPCAuthEnabled() {
+    return gArgs.GetBoolArg("-ethrpcauth", DEFAULT_ETH_RPC_AUTH_ENABLED);
+}
+
+rust::string getEthRPCJwtSecretPath() {
+    return fs::PathToString(AbsPathForConfigVal(fs::PathFromString(gArgs.GetArg("-ethrpcjwtsecret", DEFAULT_ETH_RPC_JWT_SECRET_FILENAME))));
+}
+
+rust::string getEthRPCAuthMethods() {
+    return gArgs.GetArg("-ethrpcauthmethods", DEFAULT_ETH_RPC_AUTH_METHODS);
+}
+
+bool isEthRPCAdminEnabled() {
+    return gArgs.GetBoolArg("-ethrpcadmin", DEFAULT_ETH_RPC_ADMIN_ENABLED);
+}
+
+bool isEthSubscriptionDebugEnabled() {
+    return gArgs.GetBoolArg("-ethsubscriptiondebug", DEFAULT_ETH_SUBSCRIPTION_DEBUG_ENABLED);
+}
+
+uint64_t getEthRPCReadyMaxBlockAge() {
+    return gArgs.GetArg("-ethrpcreadymaxblockage", DEFAULT_ETH_RPC_READY_MAX_BLOCK_AGE);
+}
+
+int64_t getCurrentTime() {
+    return GetTime();
+}
+
+uint32_t getEthRPCWorkers() {
+    return gArgs.GetArg("-ethrpcworkers", DEFAULT_ETH_RPC_WORKERS);
+}
+
+uint32_t getEthRPCTraceWorkers() {
+    return gArgs.GetArg("-ethrpctraceworkers", DEFAULT_ETH_RPC_TRACE_WORKERS);
+}
+
+uint32_t getEthRPCWorkerQueue() {
+    return gArgs.GetArg("-ethrpcworkerqueue", DEFAULT_ETH_RPC_WORKER_QUEUE);
+}
@@ -31,6 +31,22 @@ static constexpr uint32_t DEFAULT_EVM_NOTIFICATION_CHANNEL_BUFFER_SIZE = 10000;
 static constexpr bool DEFAULT_ETH_DEBUG_ENABLED = false;
 static constexpr bool DEFAULT_ETH_DEBUG_TRACE_ENABLED = true;
 static constexpr bool DEFAULT_ETH_SUBSCRIPTION_ENABLED = true;
+static constexpr bool DEFAULT_ETH_SUBSCRIPTION_DEBUG_ENABLED = false;
+static constexpr bool DEFAULT_ETH_IPC_ENABLED = false;
+static constexpr const char *DEFAULT_ETH_IPC_FILENAME = "metachain.ipc";
+
+// Defaults for attributes relating to ETH-RPC access control
+static constexpr uint32_t DEFAULT_ETH_RPC_RATE_LIMIT = 0;
+static constexpr uint32_t DEFAULT_ETH_RPC_HEAVY_RATE_LIMIT = 0;
+static constexpr const char *DEFAULT_ETH_RPC_CONCURRENCY_LIMITS = "debug_*=4";
+static constexpr bool DEFAULT_ETH_RPC_AUTH_ENABLED = false;
+static constexpr const char *DEFAULT_ETH_RPC_JWT_SECRET_FILENAME = "jwt.hex";
+static constexpr const char *DEFAULT_ETH_RPC_AUTH_METHODS = "debug_*,txpool_*,eth_sign*";
+static constexpr bool DEFAULT_ETH_RPC_ADMIN_ENABLED = false;
+static constexpr uint64_t DEFAULT_ETH_RPC_READY_MAX_BLOCK_AGE = 600;
+static constexpr uint32_t DEFAULT_ETH_RPC_WORKERS = 0;
+static constexpr uint32_t DEFAULT_ETH_RPC_TRACE_WORKERS = 0;
+static constexpr uint32_t DEFAULT_ETH_RPC_WORKER_QUEUE = 64;
 
 struct Attributes {
     uint64_t blockGasTargetFactor;
@@ -129,5 +145,26 @@ rust::vec<SystemTxData> getEVMSystemTxsFromBlock(std::array<uint8_t, 32> evmBloc
 uint64_t getDF23Height();
 uint64_t getDF24Height();
 bool migrateTokensFromEVM(std::size_t mnview_ptr, TokenAmount old_amount, TokenAmount &new_amount);
+// Regtest dev helpers
+uint32_t mineBlocks(uint32_t count);
+int64_t increaseMockTime(int64_t seconds);
+bool rollbackToBlock(uint32_t height);
+bool replaceEVMBlockHash(std::array<uint8_t, 32> oldEvmBlockHash, std::array<uint8_t, 32> newEvmBlockHash);
+// ETH-RPC access control
+uint32_t getEthRPCRateLimit();
+uint32_t getEthRPCHeavyRateLimit();
+rust::string getEthRPCAllowedMethods();
+rust::string getEthRPCDeniedMethods();
+rust::string getEthRPCConcurrencyLimits();
+bool isEthRPCAuthEnabled();
+rust::string getEthRPCJwtSecretPath();
+rust::string getEthRPCAuthMethods();
+bool isEthRPCAdminEnabled();
+bool isEthSubscriptionDebugEnabled();
+uint64_t getEthRPCReadyMaxBlockAge();
+int64_t getCurrentTime();
+uint32_t getEthRPCWorkers();
+uint32_t getEthRPCTraceWorkers();
+uint32_t getEthRPCWorkerQueue();
 
 #endif  // DEFI_FFI_FFIEXPORTS_H
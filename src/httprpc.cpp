@@ -16,6 +16,7 @@
 #include <util/translation.h>
 #include <walletinitinterface.h>
 
+#include <algorithm>
 #include <memory>
 #include <stdio.h>
 
@@ -65,8 +66,8 @@ private:
 static std::string strRPCUserColonPass;
 /* Stored RPC timer interface (for unregistration) */
 static std::unique_ptr<HTTPRPCTimerInterface> httpRPCTimerInterface;
-/* The host to be used for CORS header */
-static std::string corsOriginHost;
+/* Origins allowed by CORS: origins, `*` or subdomain patterns such as https://*.example.com */
+static std::vector<std::string> corsOrigins;
 
 static void JSONErrorReply(HTTPRequest* req, const UniValue& objError, const UniValue& id)
 {
@@ -146,13 +147,56 @@ static bool RPCAuthorized(const std::string& strAuth, std::string& strAuthUserna
     return multiUserAuthorized(strUserPass);
 }
 
+/** Lowercase origin or pattern without surrounding whitespace or a trailing slash */
+static std::string NormalizeCorsOrigin(std::string origin) {
+    boost::trim(origin);
+    while (!origin.empty() && origin.back() == '/')
+        origin.pop_back();
+    return ToLower(origin);
+}
+
+/** Whether a normalized request origin matches an -rpcallowcors entry, as the ETH-RPC server does */
+static bool CorsOriginMatches(const std::string& pattern, const std::string& origin) {
+    if (pattern == "*")
+        return true;
+    const auto wildcard = pattern.find("://*.");
+    if (wildcard == std::string::npos)
+        return pattern == origin;
+
+    // Any subdomain of the host with the same scheme and port
+    const auto scheme = pattern.substr(0, wildcard + 3);
+    const auto suffix = pattern.substr(wildcard + 4);
+    if (origin.size() <= scheme.size() + suffix.size() ||
+        origin.compare(0, scheme.size(), scheme) != 0 ||
+        origin.compare(origin.size() - suffix.size(), suffix.size(), suffix) != 0)
+        return false;
+    const auto subdomain = origin.substr(scheme.size(), origin.size() - scheme.size() - suffix.size());
+    return subdomain.front() != '.' && std::all_of(subdomain.begin(), subdomain.end(), [](char c) {
+        return (c >= 'a' && c <= 'z') || IsDigit(c) || c == '-' || c == '.';
+    });
+}
+
 static bool CorsHandler(HTTPRequest *req) {
-    auto host = corsOriginHost;
     // If if it's empty assume cors is disallowed. Do nothing and proceed,
     // with request as usual.
-    if (host.empty())
+    if (corsOrigins.empty())
         return false;
 
+    // Echo the request origin, browsers only accept a single one
+    std::string host = "*";
+    if (std::find(corsOrigins.begin(), corsOrigins.end(), "*") == corsOrigins.end()) {
+        const auto origin = req->GetHeader("Origin");
+        if (!origin.first)
+            return false;
+        const auto normalized = NormalizeCorsOrigin(origin.second);
+        if (std::none_of(corsOrigins.begin(), corsOrigins.end(), [&](const std::string& pattern) {
+                return CorsOriginMatches(pattern, normalized);
+            }))
+            return false;
+        host = origin.second;
+        req->WriteHeader("Vary", "Origin");
+    }
+
     req->WriteHeader("Access-Control-Allow-Origin", host);
     if (host != "*")
         req->WriteHeader("Access-Control-Allow-Credentials", "true");
@@ -275,8 +319,16 @@ bool StartHTTPRPC()
     if (!InitRPCAuthentication())
         return false;
 
-    // Setup Cors origin host name from arg.
-    corsOriginHost = gArgs.GetArg("-rpcallowcors", "");
+    // Setup Cors origins from arg.
+    corsOrigins.clear();
+    const auto allowCors = gArgs.GetArg("-rpcallowcors", "");
+    std::vector<std::string> origins;
+    boost::split(origins, allowCors, boost::is_any_of(","));
+    for (const auto& origin : origins) {
+        const auto normalized = NormalizeCorsOrigin(origin);
+        if (!normalized.empty())
+            corsOrigins.push_back(normalized);
+    }
 
     RegisterHTTPHandler("/", true, HTTPReq_JSONRPC);
     if (g_wallet_init_interface.HasWalletSupport()) {
@@ -659,7 +659,7 @@ void SetupServerArgs()
     gArgs.AddArg("-rpcuser=<user>", "Username for JSON-RPC connections", ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
     gArgs.AddArg("-rpcworkqueue=<n>", strprintf("Set the depth of the work queue to service RPC calls (default: %d)", DEFAULT_HTTP_WORKQUEUE), ArgsManager::ALLOW_ANY | ArgsManager::DEBUG_ONLY, OptionsCategory::RPC);
     gArgs.AddArg("-server", "Accept command line and JSON-RPC commands", ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
-    gArgs.AddArg("-rpcallowcors=<host>", "Allow CORS requests from the given host origin. Include scheme and port (eg: -rpcallowcors=http://127.0.0.1:5000)", ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-rpcallowcors=<host>", "Allow CORS requests from the given comma separated origins. Include scheme and port (eg: -rpcallowcors=http://127.0.0.1:5000), or use * for any origin or subdomain patterns (eg: https://*.example.com). The ETH-RPC and websocket servers also reject requests from other origins", ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
     gArgs.AddArg("-rpcstats", strprintf("Log RPC stats. (default: %u)", DEFAULT_RPC_STATS), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
     gArgs.AddArg("-consolidaterewards=<token-or-pool-symbol>", "Consolidate rewards on startup. Accepted multiple times for each token symbol", ArgsManager::ALLOW_ANY, OptionsCategory::DEBUG_TEST);
     gArgs.AddArg("-rpccache=<0/1/2>", "Cache rpc results - uses additional memory to hold on to the last results per block, but faster (0=none, 1=all, 2=smart)", ArgsManager::ALLOW_ANY, OptionsCategory::DEBUG_TEST);
@@ -672,12 +672,35 @@ void SetupServerArgs()
     gArgs.AddArg("-ethrpcport=<port>", strprintf("Listen for ETH-JSON-RPC connections on <port>. If -1 flag specified, ETH RPC server initialization will be disabled. (default: %u, testnet: %u, changi: %u, devnet: %u, regtest: %u)", defaultBaseParams->ETHRPCPort(), testnetBaseParams->ETHRPCPort(), changiBaseParams->ETHRPCPort(), devnetBaseParams->ETHRPCPort(), regtestBaseParams->ETHRPCPort()), ArgsManager::ALLOW_ANY | ArgsManager::NETWORK_ONLY, OptionsCategory::RPC);
     gArgs.AddArg("-wsbind=<addr>[:port]", "Bind to given address to listen for ETH-WebSockets connections. Do not expose the Eth-WebSockets server to untrusted networks such as the public internet! This option is ignored unless -rpcallowip is also passed. Port is optional and overrides -wsport. This option can be specified multiple times (default: 127.0.0.1 i.e., localhost)", ArgsManager::ALLOW_ANY | ArgsManager::NETWORK_ONLY, OptionsCategory::RPC);
     gArgs.AddArg("-wsport=<port>", strprintf("Listen for ETH-WebSockets connections on <port>. If -1 flag specified, ws server initialization will be disabled. (default: %u, testnet: %u, changi: %u, devnet: %u, regtest: %u)", defaultBaseParams->WSPort(), testnetBaseParams->WSPort(), changiBaseParams->WSPort(), devnetBaseParams->WSPort(), regtestBaseParams->WSPort()), ArgsManager::ALLOW_ANY | ArgsManager::NETWORK_ONLY, OptionsCategory::RPC);
+    gArgs.AddArg("-metricsbind=<addr>[:port]", "Bind to given address to serve Prometheus metrics. This option is ignored unless -rpcallowip is also passed. Port is optional and overrides -metricsport. This option can be specified multiple times (default: 127.0.0.1 i.e., localhost)", ArgsManager::ALLOW_ANY | ArgsManager::NETWORK_ONLY, OptionsCategory::RPC);
+    gArgs.AddArg("-metricsport=<port>", "Serve Prometheus metrics of the EVM and ETH RPC services over HTTP at /metrics on <port>. If -1 flag specified, the metrics server is disabled (default: -1)", ArgsManager::ALLOW_ANY | ArgsManager::NETWORK_ONLY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcratelimit=<n>", strprintf("Maximum ETH-RPC requests per second allowed per client IP, 0 to disable (default: %u)", DEFAULT_ETH_RPC_RATE_LIMIT), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcheavyratelimit=<n>", strprintf("Maximum expensive ETH-RPC requests (debug_*, ots_*, eth_call, eth_estimateGas, eth_createAccessList, eth_getLogs, eth_getFilterLogs) per second allowed per client IP, 0 to disable (default: %u)", DEFAULT_ETH_RPC_HEAVY_RATE_LIMIT), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcallowmethods=<methods>", "Only serve the given comma separated ETH-RPC methods. Entries ending with * match all methods with that prefix (eg: -ethrpcallowmethods=eth_*,net_version)", ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcdenymethods=<methods>", "Refuse to serve the given comma separated ETH-RPC methods. Entries ending with * match all methods with that prefix (eg: -ethrpcdenymethods=eth_sendTransaction,personal_*)", ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcconcurrencylimits=<limits>", strprintf("Comma separated caps on concurrent ETH-RPC requests, as method=limit. Entries ending with * match all methods with that prefix and share the cap (default: %s)", DEFAULT_ETH_RPC_CONCURRENCY_LIMITS), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcauth", strprintf("Require a JWT signed with the -ethrpcjwtsecret secret for the ETH-RPC methods given by -ethrpcauthmethods (default: %u)", DEFAULT_ETH_RPC_AUTH_ENABLED), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcjwtsecret=<path>", strprintf("Location of the hex encoded 32 byte secret used to verify HS256 JWTs of the ETH-RPC server, generated when missing. Relative paths will be prefixed by a net-specific datadir location (default: %s)", DEFAULT_ETH_RPC_JWT_SECRET_FILENAME), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcauthmethods=<methods>", strprintf("Comma separated ETH-RPC methods requiring a JWT when -ethrpcauth is set. Entries ending with * match all methods with that prefix (default: %s)", DEFAULT_ETH_RPC_AUTH_METHODS), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcadmin", strprintf("Serve the admin_* ETH RPCs on the ETH-RPC port to loopback clients, or with -ethrpcauth to clients with a valid JWT. They are always served over IPC (default: %u)", DEFAULT_ETH_RPC_ADMIN_ENABLED), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcreadymaxblockage=<seconds>", strprintf("Maximum age of the latest EVM block for GET /ready of the ETH-RPC server to report the node as ready, 0 to disable (default: %u)", DEFAULT_ETH_RPC_READY_MAX_BLOCK_AGE), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcworkers=<n>", strprintf("Number of threads executing eth_call, eth_estimateGas, eth_createAccessList, debug_feeEstimate and log queries of the ETH-RPC servers, 0 for the number of cores (default: %u)", DEFAULT_ETH_RPC_WORKERS), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpctraceworkers=<n>", strprintf("Number of threads executing debug_trace* and ots_* tracing requests of the ETH-RPC servers, 0 for half the number of cores (default: %u)", DEFAULT_ETH_RPC_TRACE_WORKERS), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethrpcworkerqueue=<n>", strprintf("Maximum ETH-RPC requests waiting for a -ethrpcworkers or -ethrpctraceworkers thread, beyond which requests fail with a server busy error (default: %u)", DEFAULT_ETH_RPC_WORKER_QUEUE), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+#ifndef WIN32
+    gArgs.AddArg("-ethipc", strprintf("Serve the ETH-RPC and subscription methods over a unix domain socket, accessible only to the node user (default: %u)", DEFAULT_ETH_IPC_ENABLED), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethipcpath=<path>", strprintf("Location of the ETH-RPC unix domain socket. Relative paths will be prefixed by a net-specific datadir location (default: %s)", DEFAULT_ETH_IPC_FILENAME), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+#else
+    hidden_args.emplace_back("-ethipc");
+    hidden_args.emplace_back("-ethipcpath");
+#endif
     gArgs.AddArg("-ethmaxconnections=<connections>", strprintf("Set the maximum number of connections allowed by the ETH-RPC server (default: %u, testnet: %u, changi: %u, devnet: %u, regtest: %u)", DEFAULT_ETH_MAX_CONNECTIONS, DEFAULT_ETH_MAX_CONNECTIONS, DEFAULT_ETH_MAX_CONNECTIONS, DEFAULT_ETH_MAX_CONNECTIONS, DEFAULT_ETH_MAX_CONNECTIONS), ArgsManager::ALLOW_ANY | ArgsManager::NETWORK_ONLY, OptionsCategory::RPC);
     gArgs.AddArg("-ethmaxresponsesize=<size>", strprintf("Set the maximum response size in MB by the ETH-RPC server (default: %u, testnet: %u, changi: %u, devnet: %u, regtest: %u)", DEFAULT_ETH_MAX_RESPONSE_SIZE_MB, DEFAULT_ETH_MAX_RESPONSE_SIZE_MB, DEFAULT_ETH_MAX_RESPONSE_SIZE_MB, DEFAULT_ETH_MAX_RESPONSE_SIZE_MB, DEFAULT_ETH_MAX_RESPONSE_SIZE_MB), ArgsManager::ALLOW_ANY | ArgsManager::NETWORK_ONLY, OptionsCategory::RPC);
     gArgs.AddArg("-ethtracingmaxmemoryusage=<size>", strprintf("Set the maximum taw max memory usage size in bytes by the ETH-RPC server (default: %u, testnet: %u, changi: %u, devnet: %u, regtest: %u)", DEFAULT_TRACING_RAW_MAX_MEMORY_USAGE_BYTES, DEFAULT_TRACING_RAW_MAX_MEMORY_USAGE_BYTES, DEFAULT_TRACING_RAW_MAX_MEMORY_USAGE_BYTES, DEFAULT_TRACING_RAW_MAX_MEMORY_USAGE_BYTES, DEFAULT_TRACING_RAW_MAX_MEMORY_USAGE_BYTES), ArgsManager::ALLOW_ANY | ArgsManager::NETWORK_ONLY, OptionsCategory::RPC);
     gArgs.AddArg("-ethdebug", strprintf("Enable debug_* ETH RPCs (default: %b)", DEFAULT_ETH_DEBUG_ENABLED), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
     gArgs.AddArg("-ethdebugtrace", strprintf("Enable debug_trace* ETH RPCs (default: %b)", DEFAULT_ETH_DEBUG_TRACE_ENABLED), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
     gArgs.AddArg("-ethsubscription", strprintf("Enable subscription notifications ETH RPCs (default: %b)", DEFAULT_ETH_SUBSCRIPTION_ENABLED), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
+    gArgs.AddArg("-ethsubscriptiondebug", strprintf("Serve debug_* ETH RPCs on the websocket server as well, requiring a JWT for connections when -ethrpcauth is set (default: %b)", DEFAULT_ETH_SUBSCRIPTION_DEBUG_ENABLED), ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
     gArgs.AddArg("-minerstrategy", "Staking optimisation. Options are none, numeric value indicating the number of subnodes to stake (default: none)", ArgsManager::ALLOW_ANY, OptionsCategory::RPC);
 
 
@@ -1367,6 +1390,13 @@ bool AppInitParameterInteraction()
     if (gArgs.GetArg("-rpcserialversion", DEFAULT_RPC_SERIALIZE_VERSION) > 1)
         return InitError("unknown rpcserialversion requested.");
 
+    for (const auto arg : {"-ethrpcratelimit", "-ethrpcheavyratelimit", "-ethrpcreadymaxblockage",
+                           "-ethrpcworkers", "-ethrpctraceworkers", "-ethrpcworkerqueue"}) {
+        const auto value = gArgs.GetArg(arg, 0);
+        if (value < 0 || value > std::numeric_limits<uint32_t>::max())
+            return InitError(strprintf("%s must be between 0 and %u.", arg, std::numeric_limits<uint32_t>::max()));
+    }
+
     nMaxTipAge = gArgs.GetArg("-maxtipage", DEFAULT_MAX_TIP_AGE);
     fIsFakeNet = Params().NetworkIDString() == "regtest" && gArgs.GetArg("-dummypos", false);
     CTxOut::SERIALIZE_FORCED_TO_OLD_IN_TESTS = Params().NetworkIDString() == "regtest" && gArgs.GetArg("-txnotokens", false);
@@ -1607,7 +1637,7 @@ void SetupCacheSizes(CacheSizes& cacheSizes) {
     LogPrintf("* Using %.1f MiB for in-memory UTXO set (plus up to %.1f MiB of unused mempool space)\n", nCoinCacheUsage * (1.0 / 1024 / 1024), nMempoolSizeMax * (1.0 / 1024 / 1024));
 }
 
-static void SetupRPCPorts(std::vector<std::string>& ethEndpoints, std::vector<std::string>& wsEndpoints) {
+static void SetupRPCPorts(std::vector<std::string>& ethEndpoints, std::vector<std::string>& wsEndpoints, std::vector<std::string>& metricsEndpoints) {
     std::string default_address = "127.0.0.1";
 
     bool setAutoPort{};
@@ -1670,6 +1700,29 @@ static void SetupRPCPorts(std::vector<std::string>& ethEndpoints, std::vector<st
             }
         }
     }
+
+    // Determine which addresses to bind to metrics server
+    int metrics_port = gArgs.GetArg("-metricsport", -1);
+    if (metrics_port != -1) {
+        if (setAutoPort) {
+            metrics_port = 0;
+        }
+        if (!(gArgs.IsArgSet("-rpcallowip") && gArgs.IsArgSet("-metricsbind"))) { // Default to loopback if not allowing external IPs
+            auto endpoint = default_address + ":" + std::to_string(metrics_port);
+            metricsEndpoints.push_back(endpoint);
+            if (gArgs.IsArgSet("-metricsbind")) {
+                LogPrintf("WARNING: option -metricsbind was ignored because -rpcallowip was not specified, refusing to allow everyone to connect\n");
+            }
+        } else if (gArgs.IsArgSet("-metricsbind")) { // Specific bind address
+            for (const std::string& strMetricsBind : gArgs.GetArgs("-metricsbind")) {
+                int port = metrics_port;
+                std::string host;
+                SplitHostPort(strMetricsBind, port, host);
+                auto endpoint = host + ":" + std::to_string(port);
+                metricsEndpoints.push_back(endpoint);
+            }
+        }
+    }
 }
 
 void SetupAnchorSPVDatabases(bool resync, int64_t customCache) {
@@ -2363,8 +2416,8 @@ bool AppInitMain(InitInterfaces& interfaces)
     // Start the ETH RPC, gRPC and websocket servers
     // We start the evm RPC servers as late as possible.
     {
-        std::vector<std::string> eth_endpoints, ws_endpoints;
-        SetupRPCPorts(eth_endpoints, ws_endpoints);
+        std::vector<std::string> eth_endpoints, ws_endpoints, metrics_endpoints;
+        SetupRPCPorts(eth_endpoints, ws_endpoints, metrics_endpoints);
         CrossBoundaryResult result;
 
         // Bind ETH RPC addresses
@@ -2398,6 +2451,38 @@ bool AppInitMain(InitInterfaces& interfaces)
                 }
             }
         }
+
+#ifndef WIN32
+        if (gArgs.GetBoolArg("-ethipc", DEFAULT_ETH_IPC_ENABLED)) {
+            const auto ipcPath = AbsPathForConfigVal(fs::PathFromString(gArgs.GetArg("-ethipcpath", DEFAULT_ETH_IPC_FILENAME)));
+            LogPrint(BCLog::HTTP, "Binding ETH IPC server on %s\n", fs::PathToString(ipcPath));
+            const auto path = rs_try_from_utf8(result, ffi_from_string_to_slice(fs::PathToString(ipcPath)));
+            if (!result.ok) {
+                LogPrint(BCLog::HTTP, "Invalid ETH IPC path, not UTF-8 valid\n");
+                return false;
+            }
+            auto res =  XResultStatusLogged(ain_rs_init_network_ipc_service(result, path))
+            if (!res) {
+                LogPrintf("Binding ETH IPC server on %s failed.\n", fs::PathToString(ipcPath));
+                return false;
+            }
+        }
+#endif
+
+        // Bind metrics addresses
+        for (auto it = metrics_endpoints.begin(); it != metrics_endpoints.end(); ++it) {
+            LogPrint(BCLog::HTTP, "Binding metrics server on endpoint %s\n", *it);
+            const auto addr = rs_try_from_utf8(result, ffi_from_string_to_slice(*it));
+            if (!result.ok) {
+                LogPrint(BCLog::HTTP, "Invalid metrics address, not UTF-8 valid\n");
+                return false;
+            }
+            auto res =  XResultStatusLogged(ain_rs_init_network_metrics_service(result, addr))
+            if (!res) {
+                LogPrintf("Binding metrics server on endpoint %s failed.\n", *it);
+                return false;
+            }
+        }
     }
     uiInterface.InitMessage(_("Done loading").translated);
 
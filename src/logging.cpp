@@ -337,6 +337,8 @@ static std::string GetAutoPortString(const AutoPort type)
             return "ethrpcport";
         case WEBSOCKET:
             return "wsport";
+        case METRICS:
+            return "metricsport";
         default:
             return "Unknown";
     }
@@ -32,6 +32,7 @@ enum AutoPort : uint8_t {
     P2P,
     ETHRPC,
     WEBSOCKET,
+    METRICS,
 };
 
 struct CLogCategoryActive
@@ -0,0 +1,198 @@
+#!/usr/bin/env python3
+# Copyright (c) 2014-2019 The Bitcoin Core developers
+# Copyright (c) DeFi Blockchain Developers
+# Distributed under the MIT software license, see the accompanying
+# file LICENSE or http://www.opensource.org/licenses/mit-license.php.
+"""Test EVM regtest dev RPCs"""
+
+from test_framework.test_framework import DefiTestFramework
+from test_framework.util import assert_equal, assert_greater_than
+
+
+class EVMDevRPCsTest(DefiTestFramework):
+    def set_test_params(self):
+        self.num_nodes = 1
+        self.setup_clean_chain = True
+        self.extra_args = [
+            [
+                "-dummypos=0",
+                "-txnotokens=0",
+                "-amkheight=50",
+                "-bayfrontheight=51",
+                "-dakotaheight=51",
+                "-eunosheight=80",
+                "-fortcanningheight=82",
+                "-fortcanninghillheight=84",
+                "-fortcanningroadheight=86",
+                "-fortcanningcrunchheight=88",
+                "-fortcanningspringheight=90",
+                "-fortcanninggreatworldheight=94",
+                "-fortcanningepilogueheight=96",
+                "-grandcentralheight=101",
+                "-metachainheight=105",
+                "-subsidytest=1",
+                "-ethdebug=1",
+                "-ethrpcadmin=1",
+            ],
+        ]
+
+    def setup(self):
+        self.address = self.nodes[0].get_genesis_keys().ownerAuthAddress
+        self.ethAddress = self.nodes[0].getnewaddress("", "erc55")
+        self.toAddress = self.nodes[0].getnewaddress("", "erc55")
+        # No private key is known for this address
+        self.impersonatedAddress = "0x000000000000000000000000000000000000dead"
+
+        self.nodes[0].generate(105)
+        self.nodes[0].utxostoaccount({self.address: "201@DFI"})
+        self.nodes[0].setgov(
+            {
+                "ATTRIBUTES": {
+                    "v0/params/feature/evm": "true",
+                    "v0/params/feature/transferdomain": "true",
+                    "v0/transferdomain/dvm-evm/enabled": "true",
+                    "v0/transferdomain/dvm-evm/src-formats": ["p2pkh", "bech32"],
+                    "v0/transferdomain/dvm-evm/dest-formats": ["erc55"],
+                    "v0/transferdomain/evm-dvm/src-formats": ["erc55"],
+                    "v0/transferdomain/evm-dvm/auth-formats": ["bech32-erc55"],
+                    "v0/transferdomain/evm-dvm/dest-formats": ["p2pkh", "bech32"],
+                }
+            }
+        )
+        self.nodes[0].generate(2)
+        self.nodes[0].transferdomain(
+            [
+                {
+                    "src": {"address": self.address, "amount": "100@DFI", "domain": 2},
+                    "dst": {
+                        "address": self.ethAddress,
+                        "amount": "100@DFI",
+                        "domain": 3,
+                    },
+                    "singlekeycheck": False,
+                }
+            ]
+        )
+        self.nodes[0].generate(1)
+
+    def send_transfer(self, sender):
+        return self.nodes[0].eth_sendTransaction(
+            {
+                "from": sender,
+                "to": self.toAddress,
+                "value": "0xa",
+                "gas": "0x7a120",
+                "gasPrice": "0x2540BE400",
+            }
+        )
+
+    def test_snapshot_and_revert(self):
+        height = self.nodes[0].getblockcount()
+        evm_block = self.nodes[0].eth_getBlockByNumber("latest")
+        balance = self.nodes[0].eth_getBalance(self.toAddress)
+
+        snapshot_id = self.nodes[0].evm_snapshot()
+        tx_hash = self.send_transfer(self.ethAddress)
+        self.nodes[0].generate(2)
+        assert_equal(self.nodes[0].getblockcount(), height + 2)
+        assert_greater_than(
+            int(self.nodes[0].eth_getBalance(self.toAddress), 16), int(balance, 16)
+        )
+
+        assert_equal(self.nodes[0].evm_revert(snapshot_id), True)
+        assert_equal(self.nodes[0].getblockcount(), height)
+        assert_equal(self.nodes[0].eth_getBlockByNumber("latest"), evm_block)
+        assert_equal(self.nodes[0].eth_getBalance(self.toAddress), balance)
+        assert_equal(self.nodes[0].eth_getTransactionReceipt(tx_hash), None)
+
+        # Reverted snapshots are discarded
+        assert_equal(self.nodes[0].evm_revert(snapshot_id), False)
+
+    def test_mine(self):
+        height = self.nodes[0].getblockcount()
+        evm_height = int(self.nodes[0].eth_blockNumber(), 16)
+        assert_equal(self.nodes[0].evm_mine(2), 2)
+        assert_equal(self.nodes[0].getblockcount(), height + 2)
+        assert_equal(int(self.nodes[0].eth_blockNumber(), 16), evm_height + 2)
+        assert_equal(self.nodes[0].hardhat_mine(), 1)
+        assert_equal(self.nodes[0].getblockcount(), height + 3)
+
+    def test_increase_time(self):
+        offset = self.nodes[0].evm_increaseTime(3600)
+        assert_greater_than(self.nodes[0].evm_increaseTime(60), offset)
+        self.nodes[0].pullup_mocktime()
+
+    def test_set_balance_keeps_block_hash_index(self):
+        latest = self.nodes[0].eth_getBlockByNumber("latest")
+        self.nodes[0].hardhat_setBalance(self.impersonatedAddress, "0xDE0B6B3A7640000")
+        assert_equal(
+            self.nodes[0].eth_getBalance(self.impersonatedAddress), "0xde0b6b3a7640000"
+        )
+
+        replaced = self.nodes[0].eth_getBlockByNumber("latest")
+        assert_equal(replaced["number"], latest["number"])
+        assert_equal(self.nodes[0].eth_getBlockByHash(latest["hash"]), None)
+        assert_equal(
+            self.nodes[0].eth_getBlockByHash(replaced["hash"])["hash"],
+            replaced["hash"],
+        )
+        for tx_hash in replaced["transactions"]:
+            assert_equal(
+                self.nodes[0].eth_getTransactionReceipt(tx_hash)["blockHash"],
+                replaced["hash"],
+            )
+
+    def test_impersonation(self):
+        assert_equal(
+            self.nodes[0].hardhat_impersonateAccount(self.impersonatedAddress), True
+        )
+        tx_hash = self.send_transfer(self.impersonatedAddress)
+        self.nodes[0].generate(1)
+
+        receipt = self.nodes[0].eth_getTransactionReceipt(tx_hash)
+        assert_equal(receipt["status"], "0x1")
+        assert_equal(receipt["from"], self.impersonatedAddress)
+        assert_equal(
+            self.nodes[0].eth_getTransactionByHash(tx_hash)["from"],
+            self.impersonatedAddress,
+        )
+        assert_equal(
+            self.nodes[0].hardhat_stopImpersonatingAccount(self.impersonatedAddress),
+            True,
+        )
+
+        # Clearing the caches keeps the sender
+        assert_equal(self.nodes[0].admin_clearCaches(), True)
+        assert_equal(
+            self.nodes[0].eth_getTransactionByHash(tx_hash)["from"],
+            self.impersonatedAddress,
+        )
+        assert_equal(
+            self.nodes[0].eth_getTransactionReceipt(tx_hash)["from"],
+            self.impersonatedAddress,
+        )
+
+        # The sender is persisted across restarts
+        self.restart_node(0)
+        assert_equal(
+            self.nodes[0].eth_getTransactionByHash(tx_hash)["from"],
+            self.impersonatedAddress,
+        )
+        self.nodes[0].debug_traceTransaction(tx_hash)
+
+    def run_test(self):
+        self.setup()
+
+        self.test_snapshot_and_revert()
+
+        self.test_mine()
+
+        self.test_increase_time()
+
+        self.test_set_balance_keeps_block_hash_index()
+
+        self.test_impersonation()
+
+
+if __name__ == "__main__":
+    EVMDevRPCsTest().main()
@@ -0,0 +1,186 @@
+#!/usr/bin/env python3
+# Copyright (c) 2014-2019 The Bitcoin Core developers
+# Copyright (c) DeFi Blockchain Developers
+# Distributed under the MIT software license, see the accompanying
+# file LICENSE or http://www.opensource.org/licenses/mit-license.php.
+"""Test EVM Otterscan RPCs"""
+
+from test_framework.test_framework import DefiTestFramework
+from test_framework.util import assert_equal
+from test_framework.evm_contract import EVMContract
+from test_framework.evm_key_pair import EvmKeyPair
+
+FACTORY = """
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Child {
+    uint256 public value;
+
+    constructor(uint256 _value) {
+        value = _value;
+    }
+}
+
+contract Factory {
+    function create(uint256 _value) external returns (address) {
+        return address(new Child(_value));
+    }
+}
+"""
+
+
+class EVMOtsRPCsTest(DefiTestFramework):
+    def set_test_params(self):
+        self.num_nodes = 1
+        self.setup_clean_chain = True
+        self.extra_args = [
+            [
+                "-dummypos=0",
+                "-txnotokens=0",
+                "-amkheight=50",
+                "-bayfrontheight=51",
+                "-dakotaheight=51",
+                "-eunosheight=80",
+                "-fortcanningheight=82",
+                "-fortcanninghillheight=84",
+                "-fortcanningroadheight=86",
+                "-fortcanningcrunchheight=88",
+                "-fortcanningspringheight=90",
+                "-fortcanninggreatworldheight=94",
+                "-fortcanningepilogueheight=96",
+                "-grandcentralheight=101",
+                "-metachainheight=105",
+                "-subsidytest=1",
+            ],
+        ]
+
+    def setup(self):
+        node = self.nodes[0]
+        self.address = node.get_genesis_keys().ownerAuthAddress
+        node.generate(105)
+        node.utxostoaccount({self.address: "201@DFI"})
+        node.setgov(
+            {
+                "ATTRIBUTES": {
+                    "v0/params/feature/evm": "true",
+                    "v0/params/feature/transferdomain": "true",
+                    "v0/transferdomain/dvm-evm/enabled": "true",
+                    "v0/transferdomain/dvm-evm/src-formats": ["p2pkh", "bech32"],
+                    "v0/transferdomain/dvm-evm/dest-formats": ["erc55"],
+                    "v0/transferdomain/evm-dvm/src-formats": ["erc55"],
+                    "v0/transferdomain/evm-dvm/auth-formats": ["bech32-erc55"],
+                    "v0/transferdomain/evm-dvm/dest-formats": ["p2pkh", "bech32"],
+                }
+            }
+        )
+        node.generate(2)
+
+        self.evm_key_pair = EvmKeyPair.from_node(node)
+        node.transferdomain(
+            [
+                {
+                    "src": {"address": self.address, "amount": "50@DFI", "domain": 2},
+                    "dst": {
+                        "address": self.evm_key_pair.address,
+                        "amount": "50@DFI",
+                        "domain": 3,
+                    },
+                    "singlekeycheck": False,
+                }
+            ]
+        )
+        node.generate(1)
+
+    def send(self, tx):
+        node = self.nodes[0]
+        signed = node.w3.eth.account.sign_transaction(tx, self.evm_key_pair.privkey)
+        hash = node.w3.eth.send_raw_transaction(signed.rawTransaction)
+        node.generate(1)
+        return node.w3.eth.wait_for_transaction_receipt(hash)
+
+    def tx_params(self):
+        node = self.nodes[0]
+        return {
+            "chainId": node.w3.eth.chain_id,
+            "from": self.evm_key_pair.address,
+            "nonce": node.w3.eth.get_transaction_count(self.evm_key_pair.address),
+            "gasPrice": 10_000_000_000,
+            "gas": 1_000_000,
+        }
+
+    def deploy_factory(self):
+        node = self.nodes[0]
+        abi, bytecode, _ = EVMContract.from_str(FACTORY, "Factory").compile()
+        compiled = node.w3.eth.contract(abi=abi, bytecode=bytecode)
+        receipt = self.send(compiled.constructor().build_transaction(self.tx_params()))
+        self.factory_deploy_hash = receipt["transactionHash"].hex()
+        self.factory = node.w3.eth.contract(
+            address=receipt["contractAddress"], abi=abi
+        )
+
+    def test_factory_created_contract(self):
+        node = self.nodes[0]
+        creator = self.evm_key_pair.address.lower()
+
+        # Creator of a contract deployed directly
+        assert_equal(
+            node.ots_getContractCreator(self.factory.address),
+            {"hash": self.factory_deploy_hash, "creator": creator},
+        )
+
+        # Creator of a contract deployed by a factory call
+        child = self.factory.functions.create(7).call()
+        receipt = self.send(
+            self.factory.functions.create(7).build_transaction(self.tx_params())
+        )
+        assert_equal(receipt["status"], 1)
+        assert_equal(receipt["contractAddress"], None)
+        create_hash = receipt["transactionHash"].hex()
+        assert_equal(
+            node.ots_getContractCreator(child),
+            {"hash": create_hash, "creator": creator},
+        )
+
+        result = node.ots_searchTransactionsBefore(child, 0, 10)
+        assert_equal([tx["hash"] for tx in result["txs"]], [create_hash])
+        result = node.ots_searchTransactionsAfter(child, 0, 10)
+        assert_equal([tx["hash"] for tx in result["txs"]], [create_hash])
+
+        # The index is removed when the block is disconnected
+        block_hash = node.getbestblockhash()
+        node.invalidateblock(block_hash)
+        assert_equal(node.ots_getContractCreator(child), None)
+        result = node.ots_searchTransactionsBefore(child, 0, 10)
+        assert_equal(result["txs"], [])
+        node.reconsiderblock(block_hash)
+        assert_equal(node.getbestblockhash(), block_hash)
+        assert_equal(
+            node.ots_getContractCreator(child),
+            {"hash": create_hash, "creator": creator},
+        )
+
+    def test_page_size_cap(self):
+        node = self.nodes[0]
+        block = node.eth_getBlockByNumber("latest")
+        result = node.ots_getBlockTransactions(block["number"], 0, 1_000_000)
+        assert_equal(
+            result["fullblock"]["transactionCount"], len(block["transactions"])
+        )
+        result = node.ots_searchTransactionsBefore(
+            self.evm_key_pair.address, 0, 1_000_000
+        )
+        assert_equal(len(result["txs"]) <= 100, True)
+
+    def run_test(self):
+        self.setup()
+
+        self.deploy_factory()
+
+        self.test_factory_created_contract()
+
+        self.test_page_size_cap()
+
+
+if __name__ == "__main__":
+    EVMOtsRPCsTest().main()
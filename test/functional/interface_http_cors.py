@@ -15,7 +15,10 @@ class HTTPCorsTest(DefiTestFramework):
     def set_test_params(self):
         self.num_nodes = 1
         self.cors_origin = "http://localhost:8000"
-        self.extra_args = [["-rpcallowcors=" + self.cors_origin]]
+        self.subdomain_origin = "https://app.example.com"
+        self.extra_args = [
+            ["-rpcallowcors=" + self.cors_origin + ",https://*.example.com"]
+        ]
 
     def run_test(self):
         self.test_json_rpc_port()
@@ -29,6 +32,7 @@ class HTTPCorsTest(DefiTestFramework):
         headers = {
             "Authorization": "Basic " + str_to_b64str(authpair),
             "Connection": "keep-alive",
+            "Origin": self.cors_origin,
         }
 
         conn = http.client.HTTPConnection(url.hostname, url.port)
@@ -45,6 +49,22 @@ class HTTPCorsTest(DefiTestFramework):
         assert_equal(res.status, http.client.NO_CONTENT)
         res.close()
 
+        # The matching origin is echoed
+        headers["Origin"] = self.subdomain_origin
+        conn.request("POST", "/", '{"method": "getbestblockhash"}', headers)
+        res = conn.getresponse()
+        self.check_cors_headers(res, origin=self.subdomain_origin)
+        assert_equal(res.status, http.client.OK)
+        res.close()
+
+        # Other origins get no CORS headers
+        headers["Origin"] = "https://evil.com"
+        conn.request("POST", "/", '{"method": "getbestblockhash"}', headers)
+        res = conn.getresponse()
+        assert_equal(res.getheader("Access-Control-Allow-Origin"), None)
+        assert_equal(res.status, http.client.OK)
+        res.close()
+
     def test_eth_json_rpc_port(self):
         url = urllib.parse.urlparse(self.nodes[0].evm_rpc.url)
 
@@ -52,14 +72,13 @@ class HTTPCorsTest(DefiTestFramework):
         headers = {
             "Connection": "keep-alive",
             "Content-Type": "application/json",
+            "Origin": self.cors_origin,
         }
 
         conn = http.client.HTTPConnection(url.hostname, url.port)
         conn.connect()
         conn.request("POST", "/", '{"method": "eth_syncing"}', headers)
         res = conn.getresponse()
-        print(res.status)
-        print(res.headers)
         self.check_cors_headers(res, False, False)
         assert_equal(res.status, http.client.OK)
         res.close()
@@ -70,10 +89,26 @@ class HTTPCorsTest(DefiTestFramework):
         assert_equal(res.status, http.client.OK)
         res.close()
 
+        headers["Origin"] = self.subdomain_origin
+        conn.request("POST", "/", '{"method": "eth_syncing"}', headers)
+        res = conn.getresponse()
+        self.check_cors_headers(res, False, False, self.subdomain_origin)
+        assert_equal(res.status, http.client.OK)
+        res.close()
+
+        # Other origins are rejected
+        headers["Origin"] = "https://evil.com"
+        conn.request("POST", "/", '{"method": "eth_syncing"}', headers)
+        res = conn.getresponse()
+        assert_equal(res.status, http.client.FORBIDDEN)
+        res.close()
+
     def check_cors_headers(
-        self, res, check_allow_methods=True, check_allow_headers=True
+        self, res, check_allow_methods=True, check_allow_headers=True, origin=None
     ):
-        assert_equal(res.getheader("Access-Control-Allow-Origin"), self.cors_origin)
+        assert_equal(
+            res.getheader("Access-Control-Allow-Origin"), origin or self.cors_origin
+        )
         assert_equal(res.getheader("Access-Control-Allow-Credentials"), "true")
         if check_allow_methods:
             assert_equal(
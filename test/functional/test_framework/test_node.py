@@ -288,6 +288,22 @@ class TestNode:
         # web3
         "web3_clientVersion",
         "web3_sha3",
+        # dev
+        "evm_snapshot",
+        "evm_revert",
+        "evm_mine",
+        "evm_increaseTime",
+        "hardhat_mine",
+        "hardhat_setBalance",
+        "hardhat_impersonateAccount",
+        "hardhat_stopImpersonatingAccount",
+        # ots
+        "ots_getBlockTransactions",
+        "ots_searchTransactionsBefore",
+        "ots_searchTransactionsAfter",
+        "ots_getContractCreator",
+        # admin
+        "admin_clearCaches",
     }
 
     def get_genesis_keys(self):
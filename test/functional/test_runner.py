@@ -311,6 +311,8 @@ BASE_SCRIPTS = [
     "feature_evm_contracts.py",
     "feature_evm_proxy.py",
     "feature_evm_contract_env_vars.py",
+    "feature_evm_dev_rpcs.py",
+    "feature_evm_ots_rpcs.py",
     "feature_evm_dfi_intrinsics.py",
     "feature_evm_logs.py",
     "feature_evm_fee.py",